@@ -1,38 +1,90 @@
 #[derive(Debug)]
-pub struct Config {
-    pub year: usize,
-    pub day: usize,
+pub enum Config {
+    Solve { year: usize, day: usize },
+    List,
+    NewDay { year: usize, day: usize },
+    NewYear { year: usize },
+    Tool { year: usize, day: usize, name: String, args: Vec<String> },
 }
 
 impl Config {
     pub fn new(args: &[String]) -> Result<Config, String> {
-        if args.len() < 3 {
-            return Err(String::from("Not enough arguments"));
-        }
+        match args.get(1).map(|s| s.as_str()) {
+            Some("list") => Ok(Config::List),
+            Some("new-day") => {
+                if args.len() < 4 {
+                    return Err(String::from("Usage: aoc new-day <year> <day>"));
+                }
+                // the year may not have any registered solutions yet, scaffold checks its crate
+                let year = args[2].parse::<usize>()
+                                  .map_err(|e| format!("Could not parse year: {}", e))?;
+                let day = parse_day(&args[3])?;
+                if day == 0 {
+                    return Err("day must be between 1 and 25 [inclusive]".to_string());
+                }
 
-        let year = match args[1].parse::<usize>() {
-            Ok(x) => {
-                let valid_years = [2017, 2019, 2020, 2021, 2022];
-                if valid_years.contains(&x) {
-                    Ok(x)
-                } else {
-                    Err(String::from("year is not valid"))
+                Ok(Config::NewDay { year, day })
+            }
+            Some("new-year") => {
+                if args.len() < 3 {
+                    return Err(String::from("Usage: aoc new-year <year>"));
+                }
+                let year = args[2].parse::<usize>()
+                                  .map_err(|e| format!("Could not parse year: {}", e))?;
+                if aoc_macros::years().contains(&year) {
+                    return Err(format!("year {} already exists", year));
                 }
+
+                Ok(Config::NewYear { year })
             }
-            Err(e) => Err(format!("Could not parse year: {}", e))
-        }?;
+            Some("tool") => {
+                if args.len() < 5 {
+                    return Err(String::from("Usage: aoc tool <year> <day> <name> [args..]"));
+                }
+                let year = parse_year(&args[2])?;
+                let day = parse_day(&args[3])?;
+                let name = args[4].clone();
+                if aoc_macros::find_tool(year, day, &name).is_none() {
+                    return Err(format!("{} day {} has no tool called {}", year, day, name));
+                }
 
-        let day = match args[2].parse::<usize>() {
-            Ok(x) => {
-                if x <= 25 {
-                    Ok(x)
-                } else {
-                    Err("day must be between 0 and 25 [inclusive]. 0 runs everything.".to_string())
+                Ok(Config::Tool { year, day, name, args: args[5..].to_vec() })
+            }
+            _ => {
+                if args.len() < 3 {
+                    return Err(String::from("Not enough arguments"));
                 }
+                let year = parse_year(&args[1])?;
+                let day = parse_day(&args[2])?;
+
+                Ok(Config::Solve { year, day })
             }
-            Err(e) => Err(format!("Could not parse day: {}", e))
-        }?;
+        }
+    }
+}
+
+fn parse_year(arg: &str) -> Result<usize, String> {
+    match arg.parse::<usize>() {
+        Ok(x) => {
+            if aoc_macros::years().contains(&x) {
+                Ok(x)
+            } else {
+                Err(String::from("year is not valid"))
+            }
+        }
+        Err(e) => Err(format!("Could not parse year: {}", e))
+    }
+}
 
-        Ok(Config { year, day })
+fn parse_day(arg: &str) -> Result<usize, String> {
+    match arg.parse::<usize>() {
+        Ok(x) => {
+            if x <= 25 {
+                Ok(x)
+            } else {
+                Err("day must be between 0 and 25 [inclusive]. 0 runs everything.".to_string())
+            }
+        }
+        Err(e) => Err(format!("Could not parse day: {}", e))
     }
 }
@@ -3,7 +3,16 @@ use std::process;
 
 use config::Config;
 
+// The year crates are only reached through the solution registry, so they have to be linked
+// in explicitly for their solutions to register.
+use y2017 as _;
+use y2019 as _;
+use y2020 as _;
+use y2021 as _;
+use y2022 as _;
+
 mod config;
+mod scaffold;
 
 fn main() {
     let args: Vec<String> = env::args().collect();
@@ -13,14 +22,84 @@ fn main() {
         process::exit(1);
     });
 
-    match conf.year {
-        2017 => y2017::solve(conf.day),
-        2019 => y2019::solve(conf.day),
-        2020 => y2020::solve(conf.day),
-        2021 => y2021::solve(conf.day),
-        2022 => y2022::solve(conf.day),
-        _ => {
-            panic!("Invalid AOC year {}", conf.year);
+    match conf {
+        Config::Solve { year, day } => solve(year, day),
+        Config::List => list(),
+        Config::NewDay { year, day } => {
+            scaffold::new_day(year, day).unwrap_or_else(|err| {
+                eprintln!("Could not create day because:\n\t{}", err);
+                process::exit(1);
+            });
+        }
+        Config::NewYear { year } => {
+            scaffold::new_year(year).unwrap_or_else(|err| {
+                eprintln!("Could not create year because:\n\t{}", err);
+                process::exit(1);
+            });
+        }
+        Config::Tool { year, day, name, args } => {
+            let tool = aoc_macros::find_tool(year, day, &name).unwrap();
+            (tool.run)(&args).unwrap_or_else(|err| {
+                eprintln!("{} failed because:\n\t{}", name, err);
+                process::exit(1);
+            });
+        }
+    }
+}
+
+fn solve(year: usize, day: usize) {
+    if day == 0 {
+        let mut days = aoc_macros::solutions()
+            .iter()
+            .filter(|s| s.year == year)
+            .map(|s| s.day)
+            .collect::<Vec<_>>();
+        days.dedup();
+
+        for day in days {
+            println!("Day {day}");
+            solve(year, day);
+        }
+        return;
+    }
+
+    let parts = aoc_macros::day_solutions(year, day);
+    if parts.is_empty() {
+        panic!("Day {day} not implemented. ");
+    }
+
+    for solution in parts {
+        (solution.solve)();
+    }
+}
+
+fn list() {
+    for year in aoc_macros::years() {
+        let solutions = aoc_macros::solutions()
+            .into_iter()
+            .filter(|s| s.year == year)
+            .collect::<Vec<_>>();
+
+        let days = solutions.iter()
+                            .filter(|s| s.part == 1)
+                            .map(|s| {
+                                if solutions.iter().any(|o| o.day == s.day && o.part == 2) {
+                                    s.day.to_string()
+                                } else {
+                                    format!("{} (part 1)", s.day)
+                                }
+                            })
+                            .collect::<Vec<_>>();
+
+        println!("{}: {}", year, days.join(", "));
+    }
+
+    let tools = aoc_macros::tools();
+    if !tools.is_empty() {
+        println!();
+        println!("Tools (aoc tool <year> <day> <name>):");
+        for tool in tools {
+            println!("{} day {}: {}", tool.year, tool.day, tool.name);
         }
     }
 }
@@ -0,0 +1,285 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// How a year crate stores its puzzle inputs
+#[derive(Debug)]
+enum InputLayout {
+    /// Inputs are pasted into a `PUZZLE_INPUT` constant in each module (2019)
+    Inline,
+    /// Inputs live in `src/inputs/d{day}{extension}` and are loaded with `reader`
+    File { reader: String, extension: String },
+}
+
+fn workspace_root() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap().to_path_buf()
+}
+
+fn read(path: &Path) -> Result<String, String> {
+    fs::read_to_string(path).map_err(|e| format!("Could not read '{}': {}", path.display(), e))
+}
+
+fn write(path: &Path, content: &str) -> Result<(), String> {
+    fs::write(path, content).map_err(|e| format!("Could not write '{}': {}", path.display(), e))?;
+    println!("Wrote {}", path.display());
+    Ok(())
+}
+
+/// Creates the module, input placeholder and `mod` declaration for a new day
+pub fn new_day(year: usize, day: usize) -> Result<(), String> {
+    let src = workspace_root().join(format!("y{}", year)).join("src");
+    if !src.exists() {
+        return Err(format!("'{}' does not exist, create the year with `aoc new-year {}`",
+                           src.display(), year));
+    }
+    let module = src.join(format!("d{}.rs", day));
+    if module.exists() {
+        return Err(format!("'{}' already exists", module.display()));
+    }
+
+    let layout = detect_layout(&src)?;
+    let lib_path = src.join("lib.rs");
+    let lib = add_mod_declaration(&read(&lib_path)?, day)?;
+
+    write(&module, &day_template(year, day, &layout))?;
+    if let InputLayout::File { extension, .. } = &layout {
+        let input = src.join("inputs").join(format!("d{}{}", day, extension));
+        if !input.exists() {
+            write(&input, "")?;
+        }
+    }
+    write(&lib_path, &lib)
+}
+
+/// Creates a new year crate and links it into the workspace and the runner
+pub fn new_year(year: usize) -> Result<(), String> {
+    let root = workspace_root();
+    let crate_dir = root.join(format!("y{}", year));
+    if crate_dir.exists() {
+        return Err(format!("'{}' already exists", crate_dir.display()));
+    }
+
+    let workspace_manifest = add_workspace_member(&read(&root.join("Cargo.toml"))?, year)?;
+    let runner_manifest = add_runner_dependency(&read(&root.join("aoc/Cargo.toml"))?, year)?;
+    let runner_main = add_runner_link(&read(&root.join("aoc/src/main.rs"))?, year)?;
+
+    let inputs_dir = crate_dir.join("src").join("inputs");
+    fs::create_dir_all(&inputs_dir)
+        .map_err(|e| format!("Could not create '{}': {}", inputs_dir.display(), e))?;
+
+    write(&crate_dir.join("Cargo.toml"), &year_manifest_template(year))?;
+    write(&crate_dir.join("src/lib.rs"), YEAR_LIB_TEMPLATE)?;
+    write(&crate_dir.join("src/inputs.rs"), INPUTS_TEMPLATE)?;
+    write(&root.join("Cargo.toml"), &workspace_manifest)?;
+    write(&root.join("aoc/Cargo.toml"), &runner_manifest)?;
+    write(&root.join("aoc/src/main.rs"), &runner_main)
+}
+
+fn detect_layout(src: &Path) -> Result<InputLayout, String> {
+    let inputs = if src.join("inputs/mod.rs").exists() {
+        src.join("inputs/mod.rs")
+    } else if src.join("inputs.rs").exists() {
+        src.join("inputs.rs")
+    } else {
+        return Ok(InputLayout::Inline);
+    };
+
+    let content = read(&inputs)?;
+    let reader = content.split("pub fn ")
+                        .nth(1)
+                        .and_then(|s| s.split('(').next())
+                        .ok_or(format!("No reader function found in '{}'", inputs.display()))?
+                        .to_string();
+    let extension = if content.contains(".txt") { ".txt" } else { "" }.to_string();
+
+    Ok(InputLayout::File { reader, extension })
+}
+
+fn day_template(year: usize, day: usize, layout: &InputLayout) -> String {
+    let (header, input) = match layout {
+        InputLayout::Inline => (
+            "use aoc_macros::aoc;\n\nconst PUZZLE_INPUT: &str = \"\";\n".to_string(),
+            "PUZZLE_INPUT".to_string(),
+        ),
+        InputLayout::File { reader, .. } => (
+            format!("use aoc_macros::aoc;\n\nuse crate::inputs::{};\n", reader),
+            format!("&{}({})", reader, day),
+        ),
+    };
+
+    format!("{header}
+fn parse_input(input: &str) -> Vec<&str> {{
+    input.lines().collect()
+}}
+
+#[aoc(year = {year}, day = {day}, part = 1)]
+pub fn solve_a() {{
+    let ans = parse_input({input}).len();
+
+    println!(\"Solution A: {{}}\", ans);
+}}
+
+#[aoc(year = {year}, day = {day}, part = 2)]
+pub fn solve_b() {{
+    let ans = parse_input({input}).len();
+
+    println!(\"Solution B: {{}}\", ans);
+}}
+
+#[cfg(test)]
+mod tests {{
+    use super::parse_input;
+
+    const TEST_INPUT: &str = \"\";
+
+    #[test]
+    fn test_parse_input() {{
+        assert_eq!(parse_input(TEST_INPUT).len(), 0);
+    }}
+}}
+")
+}
+
+fn year_manifest_template(year: usize) -> String {
+    format!("[package]
+name = \"y{year}\"
+version = \"0.1.0\"
+edition = \"2021\"
+
+# See more keys and their definitions at https://doc.rust-lang.org/cargo/reference/manifest.html
+
+[dependencies]
+aoc_macros = {{ path = \"../aoc_macros\" }}
+aoc_utils = {{ path = \"../aoc_utils\" }}
+")
+}
+
+const YEAR_LIB_TEMPLATE: &str = "mod inputs;\n";
+
+const INPUTS_TEMPLATE: &str = "use std::fs;
+use std::path::Path;
+
+pub fn read_contents(day: usize) -> String {
+    let filepath = Path::new(file!()).parent().unwrap()
+                                     .join(format!(\"inputs/d{}\", day));
+
+    fs::read_to_string(&filepath).unwrap()
+}
+";
+
+/// Joins lines back together, keeping the trailing newline of the original text if it had one
+fn join_lines(lines: Vec<String>, original: &str) -> String {
+    let mut content = lines.join("\n");
+    if original.ends_with('\n') {
+        content.push('\n');
+    }
+    content
+}
+
+fn declared_day(line: &str) -> Option<usize> {
+    line.trim()
+        .strip_prefix("mod d")?
+        .strip_suffix(';')?
+        .parse()
+        .ok()
+}
+
+/// Adds the `mod d{day};` declaration for a day to a year's `lib.rs`
+fn add_mod_declaration(lib: &str, day: usize) -> Result<String, String> {
+    let mut lines = lib.lines().map(String::from).collect::<Vec<_>>();
+    let declared = lines.iter()
+                        .enumerate()
+                        .filter_map(|(i, line)| declared_day(line).map(|d| (i, d)))
+                        .collect::<Vec<_>>();
+
+    if declared.iter().any(|&(_, d)| d == day) {
+        return Err(format!("mod d{} is already declared", day));
+    }
+
+    let position = match declared.iter().filter(|&&(_, d)| d < day).last() {
+        Some(&(i, _)) => i + 1,
+        None => match declared.first() {
+            Some(&(i, _)) => i,
+            None => lines.iter()
+                         .rposition(|line| line.starts_with("mod "))
+                         .map_or(0, |i| i + 1),
+        }
+    };
+    lines.insert(position, format!("mod d{};", day));
+
+    Ok(join_lines(lines, lib))
+}
+
+fn add_workspace_member(manifest: &str, year: usize) -> Result<String, String> {
+    let mut lines = manifest.lines().map(String::from).collect::<Vec<_>>();
+    let last = lines.iter()
+                    .rposition(|line| line.trim().starts_with("\"y"))
+                    .ok_or("Could not find the year crates in the workspace members")?;
+    lines.insert(last + 1, format!("    \"y{}\",", year));
+
+    Ok(join_lines(lines, manifest))
+}
+
+fn add_runner_dependency(manifest: &str, year: usize) -> Result<String, String> {
+    let mut lines = manifest.lines().map(String::from).collect::<Vec<_>>();
+    let last = lines.iter()
+                    .rposition(|line| line.starts_with('y') && line.contains("path = \"../y"))
+                    .ok_or("Could not find the year dependencies in aoc/Cargo.toml")?;
+    lines.insert(last + 1, format!("y{year} = {{ path = \"../y{year}\" }}"));
+
+    Ok(join_lines(lines, manifest))
+}
+
+fn add_runner_link(main: &str, year: usize) -> Result<String, String> {
+    let mut lines = main.lines().map(String::from).collect::<Vec<_>>();
+    let last = lines.iter()
+                    .rposition(|line| line.starts_with("use y") && line.ends_with(" as _;"))
+                    .ok_or("Could not find the year crate links in aoc/src/main.rs")?;
+    lines.insert(last + 1, format!("use y{} as _;", year));
+
+    Ok(join_lines(lines, main))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LIB: &str = "mod inputs;
+mod d1;
+mod d3;
+";
+
+    #[test]
+    fn test_add_mod_declaration() {
+        assert_eq!(add_mod_declaration(LIB, 2).unwrap(), "mod inputs;\nmod d1;\nmod d2;\nmod d3;\n");
+        assert_eq!(add_mod_declaration(LIB, 4).unwrap(), "mod inputs;\nmod d1;\nmod d3;\nmod d4;\n");
+        assert!(add_mod_declaration(LIB, 3).is_err());
+    }
+
+    #[test]
+    fn test_add_mod_declaration_in_new_year() {
+        assert_eq!(add_mod_declaration(YEAR_LIB_TEMPLATE, 1).unwrap(), "mod inputs;\nmod d1;\n");
+    }
+
+    #[test]
+    fn test_add_mod_declaration_keeps_missing_newline() {
+        assert_eq!(add_mod_declaration("mod d1;", 2).unwrap(), "mod d1;\nmod d2;");
+    }
+
+    #[test]
+    fn test_add_runner_link() {
+        let main = "use config::Config;
+
+use y2021 as _;
+use y2022 as _;
+
+mod config;";
+
+        assert_eq!(add_runner_link(main, 2023).unwrap(), "use config::Config;
+
+use y2021 as _;
+use y2022 as _;
+use y2023 as _;
+
+mod config;");
+    }
+}
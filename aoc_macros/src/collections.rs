@@ -1,3 +1,6 @@
+//! Collection literals. The non-empty forms build the collection from an array so that
+//! collections with a capacity are allocated once for exactly the listed elements.
+
 #[macro_export]
 macro_rules! hashmap {
     () => {
@@ -6,6 +9,139 @@ macro_rules! hashmap {
     ($( $key:expr => $value:expr ),+ $(,)?) => {
         ::std::collections::HashMap::from([
             $( ($key, $value), )*
-        ]);
+        ])
     };
-}
\ No newline at end of file
+}
+
+#[macro_export]
+macro_rules! hashset {
+    () => {
+        ::std::collections::HashSet::new()
+    };
+    ($( $value:expr ),+ $(,)?) => {
+        ::std::collections::HashSet::from([
+            $( $value, )*
+        ])
+    };
+}
+
+#[macro_export]
+macro_rules! btreemap {
+    () => {
+        ::std::collections::BTreeMap::new()
+    };
+    ($( $key:expr => $value:expr ),+ $(,)?) => {
+        ::std::collections::BTreeMap::from([
+            $( ($key, $value), )*
+        ])
+    };
+}
+
+#[macro_export]
+macro_rules! btreeset {
+    () => {
+        ::std::collections::BTreeSet::new()
+    };
+    ($( $value:expr ),+ $(,)?) => {
+        ::std::collections::BTreeSet::from([
+            $( $value, )*
+        ])
+    };
+}
+
+#[macro_export]
+macro_rules! vecdeque {
+    () => {
+        ::std::collections::VecDeque::new()
+    };
+    ($( $value:expr ),+ $(,)?) => {
+        ::std::collections::VecDeque::from([
+            $( $value, )*
+        ])
+    };
+}
+
+/// Builds a max-heap [`BinaryHeap`](std::collections::BinaryHeap). Wrap the values in
+/// [`Reverse`](std::cmp::Reverse) for a min-heap.
+#[macro_export]
+macro_rules! heap {
+    () => {
+        ::std::collections::BinaryHeap::new()
+    };
+    ($( $value:expr ),+ $(,)?) => {
+        ::std::collections::BinaryHeap::from([
+            $( $value, )*
+        ])
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cmp::Reverse;
+    use std::collections::{BinaryHeap, BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
+
+    #[test]
+    fn test_hashmap() {
+        let empty: HashMap<usize, char> = hashmap!();
+        assert!(empty.is_empty());
+
+        let map = hashmap!(1 => 'a', 2 => 'b',);
+        assert_eq!(map, HashMap::from([(1, 'a'), (2, 'b')]));
+        assert!(map.capacity() >= 2);
+    }
+
+    #[test]
+    fn test_hashset() {
+        let empty: HashSet<usize> = hashset!();
+        assert!(empty.is_empty());
+
+        let set = hashset![1, 2, 2, 3];
+        assert_eq!(set, HashSet::from([1, 2, 3]));
+        assert_eq!(hashset![1, 2,], hashset![2, 1]);
+    }
+
+    #[test]
+    fn test_btreemap() {
+        let empty: BTreeMap<usize, char> = btreemap!();
+        assert!(empty.is_empty());
+
+        let map = btreemap! {
+            3 => 'c',
+            1 => 'a',
+        };
+        assert_eq!(map.into_iter().collect::<Vec<_>>(), vec![(1, 'a'), (3, 'c')]);
+    }
+
+    #[test]
+    fn test_btreeset() {
+        let empty: BTreeSet<usize> = btreeset!();
+        assert!(empty.is_empty());
+
+        let set = btreeset![3, 1, 2, 1,];
+        assert_eq!(set.into_iter().collect::<Vec<_>>(), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn test_vecdeque() {
+        let empty: VecDeque<usize> = vecdeque!();
+        assert!(empty.is_empty());
+
+        let mut queue = vecdeque![(0, 0), (1, 2),];
+        assert_eq!(queue.pop_front(), Some((0, 0)));
+        assert_eq!(queue.pop_back(), Some((1, 2)));
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    fn test_heap() {
+        let empty: BinaryHeap<usize> = heap!();
+        assert!(empty.is_empty());
+
+        let mut heap = heap![2, 5, 1];
+        assert_eq!(heap.pop(), Some(5));
+
+        let mut heap = heap![Reverse(2), Reverse(5), Reverse(1),];
+        assert_eq!(heap.pop(), Some(Reverse(1)));
+        assert_eq!(heap.len(), 2);
+    }
+}
@@ -1 +1,14 @@
-mod collections;
\ No newline at end of file
+// lets the paths generated by `aoc_macros_impl` resolve inside this crate
+extern crate self as aoc_macros;
+
+mod collections;
+#[doc(hidden)]
+pub mod parse;
+mod registry;
+
+pub use aoc_macros_impl::{aoc, AocParse, AocTile};
+pub use parse::ParseError;
+pub use registry::{day_solutions, find_tool, Solution, solutions, Tool, tools, years};
+
+#[doc(hidden)]
+pub use inventory;
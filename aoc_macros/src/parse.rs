@@ -0,0 +1,174 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+/// Error returned by the `FromStr` implementations generated with `#[derive(AocParse)]`
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseError {
+    pub type_name: &'static str,
+    pub line: String,
+    pub message: String,
+}
+
+impl ParseError {
+    pub fn new(type_name: &'static str, line: &str, message: String) -> Self {
+        ParseError { type_name, line: line.to_string(), message }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "could not parse {} from \"{}\": {}", self.type_name, self.line, self.message)
+    }
+}
+
+impl Error for ParseError {}
+
+/// A piece of a parsed `#[aoc(pattern = "..")]`
+#[doc(hidden)]
+pub enum Segment {
+    Literal(&'static str),
+    Field(&'static str),
+}
+
+/// Splits `line` according to `pattern` and returns the text captured by each field in order.
+///
+/// A field captures everything up to the first occurrence of the literal that follows it, or the
+/// rest of the line if it is the last segment. Trailing whitespace (such as a `\r`) is ignored.
+#[doc(hidden)]
+pub fn match_pattern<'a>(type_name: &'static str,
+                         line: &'a str,
+                         pattern: &[Segment]) -> Result<Vec<&'a str>, ParseError> {
+    let text = line.trim_end();
+    let error = |message: String| ParseError::new(type_name, line, message);
+    let found = |rest: &str| if rest.is_empty() {
+        "end of line".to_string()
+    } else {
+        format!("\"{}\"", rest.chars().take(10).collect::<String>())
+    };
+
+    let mut values = Vec::new();
+    let mut pos = 0;
+    for (i, segment) in pattern.iter().enumerate() {
+        let rest = &text[pos..];
+        match segment {
+            Segment::Literal(literal) => {
+                if !rest.starts_with(literal) {
+                    return Err(error(format!("expected \"{}\" at column {}, found {}",
+                                             literal, pos + 1, found(rest))));
+                }
+                pos += literal.len();
+            }
+            Segment::Field(name) => {
+                let len = match pattern.get(i + 1) {
+                    Some(Segment::Literal(next)) => rest.find(next).ok_or_else(|| {
+                        error(format!("expected \"{}\" after `{}` (from column {})", next, name, pos + 1))
+                    })?,
+                    _ => rest.len(),
+                };
+                values.push(&rest[..len]);
+                pos += len;
+            }
+        }
+    }
+
+    if pos < text.len() {
+        return Err(error(format!("unexpected {} at column {}", found(&text[pos..]), pos + 1)));
+    }
+
+    Ok(values)
+}
+
+/// Parses the text captured for a single field
+#[doc(hidden)]
+pub fn parse_field<T>(type_name: &'static str,
+                      line: &str,
+                      field: &'static str,
+                      value: &str) -> Result<T, ParseError>
+    where T: FromStr,
+          T::Err: Display {
+    value.trim()
+         .parse()
+         .map_err(|e| ParseError::new(type_name, line, format!("invalid `{}` \"{}\": {}", field, value, e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::AocParse;
+
+    use super::ParseError;
+
+    #[derive(AocParse, Debug, PartialEq)]
+    #[aoc(pattern = "<x={x}, y={y}, z={z}>")]
+    struct Vector {
+        x: i64,
+        y: i64,
+        z: i64,
+    }
+
+    #[derive(AocParse, Debug, PartialEq)]
+    #[aoc(pattern = "{0}-{1} {{{2}}}")]
+    struct Tuple(usize, usize, char);
+
+    #[derive(AocParse, Debug, PartialEq)]
+    #[aoc(pattern = "#{id} @ {name}")]
+    struct WithSkipped {
+        id: usize,
+        name: String,
+        #[aoc(skip)]
+        seen: bool,
+    }
+
+    #[derive(AocParse, Debug, PartialEq)]
+    enum Instruction {
+        #[aoc(pattern = "noop")]
+        Noop,
+        #[aoc(pattern = "addx {0}")]
+        Add(i64),
+        #[aoc(pattern = "move {count} from {from} to {to}")]
+        Move { count: usize, from: usize, to: usize },
+    }
+
+    #[test]
+    fn test_parse_struct() {
+        assert_eq!("<x=-1, y=0, z= 2>\r".parse::<Vector>().unwrap(), Vector { x: -1, y: 0, z: 2 });
+        assert_eq!("12-34 {a}".parse::<Tuple>().unwrap(), Tuple(12, 34, 'a'));
+        assert_eq!("#3 @ some name".parse::<WithSkipped>().unwrap(),
+                   WithSkipped { id: 3, name: "some name".to_string(), seen: false });
+    }
+
+    #[test]
+    fn test_parse_enum() {
+        let instructions = "noop\naddx -5\nmove 1 from 2 to 3"
+            .lines()
+            .map(|line| line.parse::<Instruction>())
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+
+        assert_eq!(instructions, vec![
+            Instruction::Noop,
+            Instruction::Add(-5),
+            Instruction::Move { count: 1, from: 2, to: 3 },
+        ]);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let error = |line: &str| line.parse::<Vector>().unwrap_err();
+
+        assert_eq!(error("<x=1, y=2>"), ParseError {
+            type_name: "Vector",
+            line: "<x=1, y=2>".to_string(),
+            message: "expected \", z=\" after `y` (from column 9)".to_string(),
+        });
+        assert_eq!(error("<x=1; y=2, z=3>").message, "expected \", y=\" after `x` (from column 4)");
+        assert_eq!(error("(x=1, y=2, z=3>").message, "expected \"<x=\" at column 1, found \"(x=1, y=2,\"");
+        assert_eq!(error("<x=1, y=2, z=3>!").message, "unexpected \"!\" at column 16");
+        assert_eq!(error("<x=a, y=2, z=3>").message, "invalid `x` \"a\": invalid digit found in string");
+        assert_eq!(error("<x=a, y=2, z=3>").to_string(),
+                   "could not parse Vector from \"<x=a, y=2, z=3>\": invalid `x` \"a\": invalid digit found in string");
+
+        let message = "jump 3".parse::<Instruction>().unwrap_err().message;
+        assert!(message.starts_with("matches none of the patterns"), "{}", message);
+    }
+}
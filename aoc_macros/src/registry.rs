@@ -0,0 +1,90 @@
+/// A solver registered with the `#[aoc(year = .., day = .., part = ..)]` attribute
+pub struct Solution {
+    pub year: usize,
+    pub day: usize,
+    pub part: usize,
+    pub solve: fn(),
+}
+
+/// A command line tool registered with `#[aoc(year = .., day = .., tool = "..")]`, for exploring a
+/// day beyond its answers
+pub struct Tool {
+    pub year: usize,
+    pub day: usize,
+    pub name: &'static str,
+    pub run: fn(&[String]) -> Result<(), String>,
+}
+
+inventory::collect!(Solution);
+inventory::collect!(Tool);
+
+/// All registered solutions, ordered by year, day and part
+pub fn solutions() -> Vec<&'static Solution> {
+    let mut solutions = inventory::iter::<Solution>.into_iter().collect::<Vec<_>>();
+    solutions.sort_by_key(|s| (s.year, s.day, s.part));
+
+    solutions
+}
+
+/// The years that have at least one registered solution
+pub fn years() -> Vec<usize> {
+    let mut years = solutions().iter().map(|s| s.year).collect::<Vec<_>>();
+    years.dedup();
+
+    years
+}
+
+/// The registered parts of a single day, ordered by part
+pub fn day_solutions(year: usize, day: usize) -> Vec<&'static Solution> {
+    solutions().into_iter()
+               .filter(|s| s.year == year && s.day == day)
+               .collect()
+}
+
+/// All registered tools, ordered by year, day and name
+pub fn tools() -> Vec<&'static Tool> {
+    let mut tools = inventory::iter::<Tool>.into_iter().collect::<Vec<_>>();
+    tools.sort_by_key(|t| (t.year, t.day, t.name));
+
+    tools
+}
+
+pub fn find_tool(year: usize, day: usize, name: &str) -> Option<&'static Tool> {
+    tools().into_iter().find(|t| t.year == year && t.day == day && t.name == name)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::aoc;
+
+    use super::*;
+
+    #[aoc(year = 2015, day = 1, part = 1)]
+    fn solve_a() {}
+
+    #[aoc(year = 2015, day = 1, part = 2)]
+    fn solve_b() {}
+
+    #[aoc(year = 2015, day = 1, tool = "echo")]
+    fn echo(args: &[String]) -> Result<(), String> {
+        if args.is_empty() { Err("nothing to echo".to_string()) } else { Ok(()) }
+    }
+
+    #[test]
+    fn test_registry() {
+        let parts = day_solutions(2015, 1);
+
+        assert_eq!(parts.iter().map(|s| s.part).collect::<Vec<_>>(), vec![1, 2]);
+        assert!(years().contains(&2015));
+    }
+
+    #[test]
+    fn test_tools() {
+        let tool = find_tool(2015, 1, "echo").unwrap();
+
+        assert!((tool.run)(&["hello".to_string()]).is_ok());
+        assert!((tool.run)(&[]).is_err());
+        assert!(find_tool(2015, 1, "missing").is_none());
+        assert!(find_tool(2015, 2, "echo").is_none());
+    }
+}
@@ -0,0 +1,63 @@
+use proc_macro::TokenStream;
+use syn::{DeriveInput, parse_macro_input};
+
+mod parse;
+mod registry;
+mod tile;
+
+/// Registers a solver function so that the runner can find it.
+///
+/// ```ignore
+/// #[aoc(year = 2021, day = 15, part = 1)]
+/// pub fn solve_a() { .. }
+/// ```
+///
+/// A day can also register named tools, which take the rest of the command line:
+///
+/// ```ignore
+/// #[aoc(year = 2019, day = 22, tool = "shuffle")]
+/// pub fn shuffle(args: &[String]) -> Result<(), String> { .. }
+/// ```
+#[proc_macro_attribute]
+pub fn aoc(args: TokenStream, item: TokenStream) -> TokenStream {
+    registry::expand(args.into(), item.into())
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Derives `FromStr` from a line pattern. Fields are written as `{name}` (or `{0}` for tuple
+/// fields) and are parsed with their own `FromStr`; `{{` and `}}` are literal braces. Enums take a
+/// pattern per variant and use the first one that matches. Fields marked `#[aoc(skip)]` are not
+/// part of the pattern and are set to their default.
+///
+/// ```ignore
+/// #[derive(AocParse)]
+/// #[aoc(pattern = "pos=<{x},{y},{z}>")]
+/// struct Position { x: i64, y: i64, z: i64 }
+/// ```
+#[proc_macro_derive(AocParse, attributes(aoc))]
+pub fn derive_aoc_parse(input: TokenStream) -> TokenStream {
+    parse::expand(parse_macro_input!(input as DeriveInput))
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+
+/// Derives `TryFrom<char>` and `From<_> for char` for an enum of map tiles. Each unit variant lists
+/// the characters it is parsed from, the first of which is the one it is written back as. A single
+/// `#[tile(_)]` variant holding a `char` catches every other character.
+///
+/// ```ignore
+/// #[derive(AocTile)]
+/// enum Tile {
+///     #[tile('#')] Wall,
+///     #[tile('.', ' ')] Open,
+///     #[tile(_)] Key(char),
+/// }
+/// ```
+#[proc_macro_derive(AocTile, attributes(tile))]
+pub fn derive_aoc_tile(input: TokenStream) -> TokenStream {
+    tile::expand(parse_macro_input!(input as DeriveInput))
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
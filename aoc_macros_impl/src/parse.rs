@@ -0,0 +1,216 @@
+use std::collections::HashMap;
+
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use syn::{Attribute, Data, DeriveInput, Error, Fields, Index, LitStr, Member};
+
+enum Segment {
+    Literal(String),
+    Field(String),
+}
+
+fn parse_pattern(pattern: &LitStr) -> syn::Result<Vec<Segment>> {
+    let error = |message: &str| Error::new(pattern.span(), message);
+
+    let text = pattern.value();
+    let mut chars = text.chars().peekable();
+    let mut segments = Vec::new();
+    let mut literal = String::new();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+                literal.push('{');
+            }
+            '}' if chars.peek() == Some(&'}') => {
+                chars.next();
+                literal.push('}');
+            }
+            '{' => {
+                let mut name = String::new();
+                loop {
+                    match chars.next() {
+                        Some('}') => break,
+                        Some(c) => name.push(c),
+                        None => return Err(error("unclosed `{` in pattern")),
+                    }
+                }
+
+                let name = name.trim().to_string();
+                if name.is_empty() {
+                    return Err(error("empty `{}` in pattern"));
+                }
+                if !literal.is_empty() {
+                    segments.push(Segment::Literal(std::mem::take(&mut literal)));
+                } else if let Some(Segment::Field(previous)) = segments.last() {
+                    return Err(error(&format!("`{{{}}}` and `{{{}}}` must be separated by some text",
+                                              previous, name)));
+                }
+                segments.push(Segment::Field(name));
+            }
+            '}' => return Err(error("unmatched `}` in pattern, use `}}` for a literal brace")),
+            c => literal.push(c),
+        }
+    }
+    if !literal.is_empty() {
+        segments.push(Segment::Literal(literal));
+    }
+
+    Ok(segments)
+}
+
+fn pattern_attr(attrs: &[Attribute]) -> syn::Result<Option<LitStr>> {
+    let mut pattern = None;
+    for attr in attrs.iter().filter(|a| a.path().is_ident("aoc")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("pattern") {
+                pattern = Some(meta.value()?.parse()?);
+                Ok(())
+            } else {
+                Err(meta.error("expected `pattern`"))
+            }
+        })?;
+    }
+
+    Ok(pattern)
+}
+
+fn is_skipped(attrs: &[Attribute]) -> syn::Result<bool> {
+    let mut skip = false;
+    for attr in attrs.iter().filter(|a| a.path().is_ident("aoc")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("skip") {
+                skip = true;
+                Ok(())
+            } else {
+                Err(meta.error("expected `skip`"))
+            }
+        })?;
+    }
+
+    Ok(skip)
+}
+
+/// Builds an expression of type `Result<Self, ParseError>` that parses `line` into `path`
+fn construct(type_name: &str,
+             path: TokenStream,
+             fields: &Fields,
+             pattern: Option<LitStr>,
+             span: Span) -> syn::Result<TokenStream> {
+    let pattern = pattern.ok_or(Error::new(span, "missing #[aoc(pattern = \"..\")]"))?;
+    let segments = parse_pattern(&pattern)?;
+
+    let mut members = HashMap::new();
+    let mut skipped = Vec::new();
+    for (i, field) in fields.iter().enumerate() {
+        let (name, member) = match &field.ident {
+            Some(ident) => (ident.to_string(), Member::Named(ident.clone())),
+            None => (i.to_string(), Member::Unnamed(Index::from(i))),
+        };
+        if is_skipped(&field.attrs)? {
+            skipped.push(member);
+        } else {
+            members.insert(name, member);
+        }
+    }
+
+    let mut literals = Vec::new();
+    let mut initializers = Vec::new();
+    for segment in &segments {
+        match segment {
+            Segment::Literal(literal) => {
+                literals.push(quote!(::aoc_macros::parse::Segment::Literal(#literal)));
+            }
+            Segment::Field(name) => {
+                let member = members.remove(name).ok_or_else(|| {
+                    Error::new(pattern.span(), format!("`{{{}}}` is not a field, is skipped or is used twice", name))
+                })?;
+                let index = initializers.len();
+                initializers.push(quote! {
+                    #member: ::aoc_macros::parse::parse_field(#type_name, line, #name, values[#index])?
+                });
+                literals.push(quote!(::aoc_macros::parse::Segment::Field(#name)));
+            }
+        }
+    }
+
+    if let Some(name) = members.keys().min() {
+        return Err(Error::new(pattern.span(),
+                              format!("field `{}` is missing from the pattern, mark it #[aoc(skip)] to default it",
+                                      name)));
+    }
+
+    let matched = quote! {
+        ::aoc_macros::parse::match_pattern(#type_name, line, &[#(#literals),*])
+    };
+
+    Ok(if initializers.is_empty() && skipped.is_empty() {
+        quote!(#matched.map(|_| #path))
+    } else if initializers.is_empty() {
+        quote! {
+            #matched.map(|_| #path {
+                #(#skipped: ::std::default::Default::default(),)*
+            })
+        }
+    } else {
+        quote! {
+            #matched.and_then(|values| {
+                ::std::result::Result::Ok(#path {
+                    #(#initializers,)*
+                    #(#skipped: ::std::default::Default::default(),)*
+                })
+            })
+        }
+    })
+}
+
+pub fn expand(input: DeriveInput) -> syn::Result<TokenStream> {
+    let name = &input.ident;
+    let type_name = name.to_string();
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let body = match &input.data {
+        Data::Struct(data) => {
+            let pattern = pattern_attr(&input.attrs)?;
+            construct(&type_name, quote!(Self), &data.fields, pattern, name.span())?
+        }
+        Data::Enum(data) => {
+            let attempts = data.variants
+                               .iter()
+                               .map(|variant| {
+                                   let ident = &variant.ident;
+                                   let pattern = pattern_attr(&variant.attrs)?;
+                                   construct(&type_name, quote!(Self::#ident), &variant.fields, pattern, ident.span())
+                               })
+                               .collect::<syn::Result<Vec<_>>>()?;
+
+            quote! {
+                let mut errors = ::std::vec::Vec::new();
+                #(
+                    match #attempts {
+                        ::std::result::Result::Ok(value) => return ::std::result::Result::Ok(value),
+                        ::std::result::Result::Err(e) => errors.push(e.message),
+                    }
+                )*
+
+                ::std::result::Result::Err(::aoc_macros::ParseError::new(
+                    #type_name,
+                    line,
+                    format!("matches none of the patterns: {}", errors.join("; ")),
+                ))
+            }
+        }
+        Data::Union(_) => return Err(Error::new(name.span(), "AocParse cannot be derived for unions")),
+    };
+
+    Ok(quote! {
+        impl #impl_generics ::std::str::FromStr for #name #ty_generics #where_clause {
+            type Err = ::aoc_macros::ParseError;
+
+            fn from_str(line: &str) -> ::std::result::Result<Self, Self::Err> {
+                #body
+            }
+        }
+    })
+}
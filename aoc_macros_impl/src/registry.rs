@@ -0,0 +1,89 @@
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use syn::{Error, ItemFn, LitInt, LitStr};
+use syn::parse::Parser;
+
+struct Key {
+    year: usize,
+    day: usize,
+    target: Target,
+}
+
+/// What the function is registered as: one part of the solution, or a named tool for the day
+enum Target {
+    Part(usize),
+    Tool(String),
+}
+
+fn parse_key(args: TokenStream) -> syn::Result<Key> {
+    let mut year: Option<LitInt> = None;
+    let mut day: Option<LitInt> = None;
+    let mut part: Option<LitInt> = None;
+    let mut tool: Option<LitStr> = None;
+
+    let parser = syn::meta::parser(|meta| {
+        let slot = if meta.path.is_ident("year") {
+            &mut year
+        } else if meta.path.is_ident("day") {
+            &mut day
+        } else if meta.path.is_ident("part") {
+            &mut part
+        } else if meta.path.is_ident("tool") {
+            tool = Some(meta.value()?.parse()?);
+            return Ok(());
+        } else {
+            return Err(meta.error("expected `year`, `day`, `part` or `tool`"));
+        };
+
+        *slot = Some(meta.value()?.parse()?);
+        Ok(())
+    });
+    parser.parse2(args)?;
+
+    let get = |lit: Option<LitInt>, name: &str, range: (usize, usize)| -> syn::Result<usize> {
+        let lit = lit.ok_or(Error::new(Span::call_site(), format!("missing `{name}`")))?;
+        let value = lit.base10_parse::<usize>()?;
+        if value < range.0 || value > range.1 {
+            return Err(Error::new(lit.span(),
+                                  format!("`{name}` must be between {} and {}", range.0, range.1)));
+        }
+        Ok(value)
+    };
+
+    let target = match (part, tool) {
+        (Some(_), Some(tool)) => return Err(Error::new(tool.span(), "expected only one of `part` and `tool`")),
+        (None, Some(tool)) => Target::Tool(tool.value()),
+        (part, None) => Target::Part(get(part, "part", (1, 2))?),
+    };
+
+    Ok(Key {
+        year: get(year, "year", (2015, 9999))?,
+        day: get(day, "day", (1, 25))?,
+        target,
+    })
+}
+
+pub fn expand(args: TokenStream, item: TokenStream) -> syn::Result<TokenStream> {
+    let Key { year, day, target } = parse_key(args)?;
+    let func: ItemFn = syn::parse2(item)?;
+    let name = &func.sig.ident;
+
+    let entry = match target {
+        Target::Part(part) => {
+            if !func.sig.inputs.is_empty() {
+                return Err(Error::new_spanned(&func.sig.inputs, "solver functions cannot take arguments"));
+            }
+            quote! { ::aoc_macros::Solution { year: #year, day: #day, part: #part, solve: #name } }
+        }
+        Target::Tool(tool) => {
+            // the signature is checked by the `run` field, which is `fn(&[String]) -> Result<(), String>`
+            quote! { ::aoc_macros::Tool { year: #year, day: #day, name: #tool, run: #name } }
+        }
+    };
+
+    Ok(quote! {
+        #func
+
+        ::aoc_macros::inventory::submit! { #entry }
+    })
+}
@@ -0,0 +1,130 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Attribute, Data, DeriveInput, Error, Fields, LitChar, Token, Variant};
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+
+/// An argument of `#[tile(..)]`, either a character or `_` for the catch-all variant
+enum TileArg {
+    Char(LitChar),
+    Any(Token![_]),
+}
+
+impl Parse for TileArg {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        if input.peek(Token![_]) {
+            input.parse().map(TileArg::Any)
+        } else {
+            input.parse().map(TileArg::Char)
+        }
+    }
+}
+
+fn tile_attr(variant: &Variant) -> syn::Result<(&Attribute, Vec<TileArg>)> {
+    let mut attrs = variant.attrs.iter().filter(|a| a.path().is_ident("tile"));
+    let attr = attrs.next()
+                    .ok_or(Error::new(variant.ident.span(), "missing #[tile('.')] or #[tile(_)]"))?;
+    if let Some(other) = attrs.next() {
+        return Err(Error::new_spanned(other, "duplicate #[tile] attribute"));
+    }
+
+    let args = attr.parse_args_with(Punctuated::<TileArg, Token![,]>::parse_terminated)?;
+    if args.is_empty() {
+        return Err(Error::new_spanned(attr, "expected at least one character"));
+    }
+
+    Ok((attr, args.into_iter().collect()))
+}
+
+pub fn expand(input: DeriveInput) -> syn::Result<TokenStream> {
+    let name = &input.ident;
+    let type_name = name.to_string();
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let data = match &input.data {
+        Data::Enum(data) => data,
+        _ => return Err(Error::new(name.span(), "AocTile can only be derived for enums")),
+    };
+
+    let mut parse_arms = Vec::new();
+    let mut char_arms = Vec::new();
+    let mut expected = Vec::new();
+    let mut seen = Vec::new();
+    let mut catch_all = None;
+
+    for variant in &data.variants {
+        let ident = &variant.ident;
+        let (attr, args) = tile_attr(variant)?;
+
+        if let [TileArg::Any(_)] = args.as_slice() {
+            match &variant.fields {
+                Fields::Unnamed(fields) if fields.unnamed.len() == 1 => {}
+                _ => return Err(Error::new(ident.span(), "#[tile(_)] needs a variant with a single `char` field")),
+            }
+            if catch_all.is_some() {
+                return Err(Error::new_spanned(attr, "only one variant can be #[tile(_)]"));
+            }
+            catch_all = Some(quote!(c => ::std::result::Result::Ok(Self::#ident(c))));
+            char_arms.push(quote!(#name::#ident(c) => c));
+            continue;
+        }
+
+        if !matches!(variant.fields, Fields::Unit) {
+            return Err(Error::new(ident.span(), "variants with fields must be #[tile(_)]"));
+        }
+
+        let mut chars = Vec::new();
+        for arg in args {
+            match arg {
+                TileArg::Char(c) => {
+                    if seen.contains(&c.value()) {
+                        return Err(Error::new(c.span(), format!("{:?} is used more than once", c.value())));
+                    }
+                    seen.push(c.value());
+                    expected.push(format!("{:?}", c.value()));
+                    chars.push(c);
+                }
+                TileArg::Any(underscore) => {
+                    return Err(Error::new(underscore.span, "`_` cannot be combined with characters"));
+                }
+            }
+        }
+
+        // the first character is the one the variant is written back as
+        let first = &chars[0];
+        parse_arms.push(quote!(#(#chars)|* => ::std::result::Result::Ok(Self::#ident)));
+        char_arms.push(quote!(#name::#ident => #first));
+    }
+
+    let fallback = catch_all.unwrap_or_else(|| {
+        let message = format!("expected one of {}", expected.join(", "));
+        quote! {
+            c => ::std::result::Result::Err(::aoc_macros::ParseError::new(
+                #type_name,
+                &c.to_string(),
+                #message.to_string(),
+            ))
+        }
+    });
+
+    Ok(quote! {
+        impl #impl_generics ::std::convert::TryFrom<char> for #name #ty_generics #where_clause {
+            type Error = ::aoc_macros::ParseError;
+
+            fn try_from(c: char) -> ::std::result::Result<Self, Self::Error> {
+                match c {
+                    #(#parse_arms,)*
+                    #fallback,
+                }
+            }
+        }
+
+        impl #impl_generics ::std::convert::From<#name #ty_generics> for char #where_clause {
+            fn from(tile: #name #ty_generics) -> char {
+                match tile {
+                    #(#char_arms,)*
+                }
+            }
+        }
+    })
+}
@@ -0,0 +1,143 @@
+//! Affine maps `x -> a * x + b` modulo `m`. They are closed under composition, so a long sequence of
+//! them (a card shuffle, say) collapses into a single map that can be repeated with fast
+//! exponentiation and undone with its inverse.
+
+use std::fmt::{Display, Formatter};
+
+use crate::number::{Integer, mod_inverse, modulo};
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Affine<T: Integer = i128> {
+    a: T,
+    b: T,
+    modulus: T,
+}
+
+impl<T: Integer> Affine<T> {
+    pub fn new(a: T, b: T, modulus: T) -> Self {
+        assert!(modulus > T::from(0), "modulus {:?} must be positive", modulus);
+        Affine { a: modulo(a, modulus.clone()), b: modulo(b, modulus.clone()), modulus }
+    }
+
+    /// The map that leaves everything where it is
+    pub fn identity(modulus: T) -> Self {
+        Self::new(T::from(1), T::from(0), modulus)
+    }
+
+    pub fn a(&self) -> &T {
+        &self.a
+    }
+
+    pub fn b(&self) -> &T {
+        &self.b
+    }
+
+    pub fn modulus(&self) -> &T {
+        &self.modulus
+    }
+
+    pub fn apply(&self, x: T) -> T {
+        modulo(self.a.clone() * modulo(x, self.modulus.clone()) + self.b.clone(), self.modulus.clone())
+    }
+
+    /// The map that applies `self` and then `other`
+    pub fn then(&self, other: &Self) -> Self {
+        assert_eq!(self.modulus, other.modulus, "maps with different moduli can't be composed");
+        Self::new(other.a.clone() * self.a.clone(),
+                  other.a.clone() * self.b.clone() + other.b.clone(),
+                  self.modulus.clone())
+    }
+
+    /// The map that undoes `self`, which exists when `a` is coprime to the modulus
+    pub fn inverse(&self) -> Option<Self> {
+        // x = a^-1 * (y - b)
+        let a = mod_inverse(self.a.clone(), self.modulus.clone())?;
+        let b = T::from(0) - a.clone() * self.b.clone();
+        Some(Self::new(a, b, self.modulus.clone()))
+    }
+
+    /// `self` applied `n` times, by repeated squaring. A negative `n` repeats the inverse, so it is
+    /// `None` if there is none.
+    pub fn pow(&self, n: T) -> Option<Self> {
+        let zero = T::from(0);
+        let two = T::from(2);
+        let (mut square, mut n) = if n < zero {
+            (self.inverse()?, zero - n)
+        } else {
+            (self.clone(), n)
+        };
+
+        let mut result = Self::identity(self.modulus.clone());
+        while n > T::from(0) {
+            if n.clone() % two.clone() == T::from(1) {
+                result = result.then(&square);
+            }
+            square = square.then(&square);
+            n = n / two.clone();
+        }
+        Some(result)
+    }
+}
+
+impl<T: Integer + Display> Display for Affine<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "x -> {}x + {} (mod {})", self.a, self.b, self.modulus)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::number::gcd;
+
+    use super::*;
+
+    fn maps(modulus: i64) -> Vec<Affine<i64>> {
+        (0..modulus).flat_map(|a| (0..modulus).map(move |b| Affine::new(a, b, modulus))).collect()
+    }
+
+    #[test]
+    fn test_apply_and_then() {
+        let f = Affine::new(3i64, -1, 10);
+        let g = Affine::new(7i64, 4, 10);
+
+        assert_eq!(f, Affine::new(3, 9, 10));
+        assert_eq!(f.apply(4), 1);
+        assert_eq!(f.apply(-6), 1);
+        assert_eq!(f.then(&g).apply(4), g.apply(f.apply(4)));
+        assert_eq!(f.to_string(), "x -> 3x + 9 (mod 10)");
+        assert_eq!(Affine::identity(10i64).then(&f), f);
+    }
+
+    #[test]
+    fn test_inverse() {
+        for f in maps(12) {
+            match f.inverse() {
+                Some(inv) => {
+                    assert_eq!(f.then(&inv), Affine::identity(12));
+                    assert_eq!(inv.then(&f), Affine::identity(12));
+                }
+                None => assert_ne!(gcd(*f.a(), 12), 1, "{} has an inverse", f),
+            }
+        }
+    }
+
+    #[test]
+    fn test_pow() {
+        for f in maps(9) {
+            let mut repeated = Affine::identity(9);
+            for n in 0..20 {
+                assert_eq!(f.pow(n), Some(repeated.clone()), "{}^{}", f, n);
+                if let Some(inv) = f.inverse() {
+                    assert_eq!(f.pow(-n), inv.pow(n));
+                    assert_eq!(f.pow(-n).unwrap().then(&repeated), Affine::identity(9));
+                }
+                repeated = repeated.then(&f);
+            }
+        }
+
+        // big enough that the composition needs 128 bits
+        let f = Affine::new(4_000_000_007i128, 12, 119315717514047);
+        let n = 101741582076661;
+        assert_eq!(f.pow(n).unwrap().then(&f.pow(-n).unwrap()), Affine::identity(119315717514047));
+    }
+}
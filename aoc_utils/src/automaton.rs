@@ -0,0 +1,346 @@
+//! Cellular automata, where every cell moves to its next state at the same time based on its own
+//! state and those of its neighbours. Which cells are neighbours is up to a [`Topology`], so the
+//! same engine runs square grids, hex grids, higher dimensions and anything stranger.
+
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+use std::mem;
+
+use rayon::prelude::*;
+
+use crate::{Hex, Point};
+
+/// The shape of the space the cells live in
+pub trait Topology {
+    type Cell: Copy + Eq + Hash;
+
+    /// The neighbours of `cell`, in an order the rule can rely on
+    fn neighbours(&self, cell: &Self::Cell) -> Vec<Self::Cell>;
+
+    /// The number of neighbours of a cell out in the background, which is what an unbounded
+    /// automaton feeds the rule to work out the next background
+    fn degree(&self) -> usize;
+}
+
+/// The `2 * N` cells one step away along a single axis
+#[derive(Debug, Clone, Copy, Default)]
+pub struct VonNeumann<const N: usize>;
+
+/// The `3^N - 1` cells touching a cell, diagonals included
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Moore<const N: usize>;
+
+/// The six neighbours of a hex tile
+#[derive(Debug, Clone, Copy, Default)]
+pub struct HexTiles;
+
+/// An explicit list of neighbours for every cell, e.g. worked out once from a map. Cells that are
+/// missing have no neighbours.
+#[derive(Debug, Clone)]
+pub struct Adjacency<C>(pub HashMap<C, Vec<C>>);
+
+impl<const N: usize> Topology for VonNeumann<N> {
+    type Cell = Point<N>;
+
+    fn neighbours(&self, cell: &Point<N>) -> Vec<Point<N>> {
+        cell.neighbours().collect()
+    }
+
+    fn degree(&self) -> usize {
+        2 * N
+    }
+}
+
+impl<const N: usize> Topology for Moore<N> {
+    type Cell = Point<N>;
+
+    fn neighbours(&self, cell: &Point<N>) -> Vec<Point<N>> {
+        cell.neighbours_all().collect()
+    }
+
+    fn degree(&self) -> usize {
+        3usize.pow(N as u32) - 1
+    }
+}
+
+impl Topology for HexTiles {
+    type Cell = Hex;
+
+    fn neighbours(&self, cell: &Hex) -> Vec<Hex> {
+        cell.neighbours().collect()
+    }
+
+    fn degree(&self) -> usize {
+        6
+    }
+}
+
+impl<C: Copy + Eq + Hash> Topology for Adjacency<C> {
+    type Cell = C;
+
+    fn neighbours(&self, cell: &C) -> Vec<C> {
+        self.0.get(cell).cloned().unwrap_or_default()
+    }
+
+    fn degree(&self) -> usize {
+        0
+    }
+}
+
+/// The cells of an automaton and the state they are in. Cells that are not stored are in the
+/// `background` state.
+///
+/// A *bounded* automaton only ever updates the cells it started with, while an *unbounded* one
+/// lets the background cells next to them change too. An unbounded automaton only stores the cells
+/// that differ from the background, and updates the background itself by applying the rule to a
+/// cell surrounded by background, so a background that flips every generation works as well.
+pub struct Automaton<T: Topology, S> {
+    topology: T,
+    cells: HashMap<T::Cell, S>,
+    next: HashMap<T::Cell, S>,
+    background: S,
+    bounded: bool,
+}
+
+impl<T: Topology, S: Clone + PartialEq> Automaton<T, S> {
+    pub fn bounded<I: IntoIterator<Item=(T::Cell, S)>>(topology: T, cells: I, background: S) -> Self {
+        Self::new(topology, cells, background, true)
+    }
+
+    pub fn unbounded<I: IntoIterator<Item=(T::Cell, S)>>(topology: T, cells: I, background: S) -> Self {
+        let mut automaton = Self::new(topology, cells, background, false);
+        let background = automaton.background.clone();
+        automaton.cells.retain(|_, state| *state != background);
+        automaton
+    }
+
+    fn new<I: IntoIterator<Item=(T::Cell, S)>>(topology: T, cells: I, background: S, bounded: bool) -> Self {
+        Automaton { topology, cells: cells.into_iter().collect(), next: HashMap::new(), background, bounded }
+    }
+
+    pub fn topology(&self) -> &T {
+        &self.topology
+    }
+
+    pub fn background(&self) -> &S {
+        &self.background
+    }
+
+    pub fn get(&self, cell: &T::Cell) -> &S {
+        self.cells.get(cell).unwrap_or(&self.background)
+    }
+
+    /// The stored cells, which for an unbounded automaton are those not in the background state
+    pub fn cells(&self) -> impl Iterator<Item=(T::Cell, &S)> {
+        self.cells.iter().map(|(&cell, state)| (cell, state))
+    }
+
+    /// The number of stored cells in `state`
+    pub fn count(&self, state: &S) -> usize {
+        self.cells.values().filter(|&s| s == state).count()
+    }
+
+    /// The cells whose next state has to be worked out
+    fn candidates(&self) -> Vec<T::Cell> {
+        if self.bounded {
+            return self.cells.keys().copied().collect();
+        }
+
+        let mut candidates = HashSet::new();
+        for cell in self.cells.keys() {
+            candidates.insert(*cell);
+            candidates.extend(self.topology.neighbours(cell));
+        }
+        candidates.into_iter().collect()
+    }
+
+    fn next_state<R: Fn(&S, &[&S]) -> S>(&self, cell: &T::Cell, rule: &R) -> S {
+        let neighbours = self.topology.neighbours(cell);
+        let states = neighbours.iter().map(|n| self.get(n)).collect::<Vec<_>>();
+        rule(self.get(cell), &states)
+    }
+
+    fn next_background<R: Fn(&S, &[&S]) -> S>(&self, rule: &R) -> S {
+        if self.bounded {
+            return self.background.clone();
+        }
+        rule(&self.background, &vec![&self.background; self.topology.degree()])
+    }
+
+    /// Swaps in the next generation, returning whether any cell changed
+    fn commit(&mut self, states: impl Iterator<Item=(T::Cell, S)>, background: S) -> bool {
+        let mut changed = background != self.background;
+
+        self.next.clear();
+        for (cell, state) in states {
+            changed |= state != *self.get(&cell);
+            if self.bounded || state != background {
+                self.next.insert(cell, state);
+            }
+        }
+
+        mem::swap(&mut self.cells, &mut self.next);
+        self.background = background;
+        changed
+    }
+
+    /// Moves every cell on one generation with `rule(state, neighbour_states)`, returning whether
+    /// anything changed
+    pub fn step<R: Fn(&S, &[&S]) -> S>(&mut self, rule: R) -> bool {
+        let background = self.next_background(&rule);
+        let states = self.candidates()
+                         .into_iter()
+                         .map(|cell| (cell, self.next_state(&cell, &rule)))
+                         .collect::<Vec<_>>();
+
+        self.commit(states.into_iter(), background)
+    }
+
+    /// Like [`step`](Self::step), but works out the cells on all cores
+    pub fn par_step<R>(&mut self, rule: R) -> bool
+        where T: Sync,
+              T::Cell: Send + Sync,
+              S: Send + Sync,
+              R: Fn(&S, &[&S]) -> S + Sync {
+        let background = self.next_background(&rule);
+        let states = self.candidates()
+                         .into_par_iter()
+                         .map(|cell| (cell, self.next_state(&cell, &rule)))
+                         .collect::<Vec<_>>();
+
+        self.commit(states.into_iter(), background)
+    }
+
+    pub fn run<R: Fn(&S, &[&S]) -> S>(&mut self, generations: usize, rule: R) {
+        for _ in 0..generations {
+            self.step(&rule);
+        }
+    }
+
+    /// Steps until nothing changes any more, returning the number of generations that changed
+    /// something
+    pub fn run_until_stable<R: Fn(&S, &[&S]) -> S>(&mut self, rule: R) -> usize {
+        let mut generations = 0;
+        while self.step(&rule) {
+            generations += 1;
+        }
+        generations
+    }
+}
+
+/// The usual live or dead rule, where `born` and `survive` list the numbers of live neighbours for
+/// which a cell comes alive or stays alive
+pub fn life_rule(born: &'static [usize], survive: &'static [usize]) -> impl Fn(&bool, &[&bool]) -> bool + Sync {
+    move |&alive, neighbours| {
+        let live = neighbours.iter().filter(|&&&n| n).count();
+        if alive { survive.contains(&live) } else { born.contains(&live) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Grid, Point2};
+
+    use super::*;
+
+    fn parse_live<const N: usize>(input: &str) -> Vec<(Point<N>, bool)> {
+        let grid = Grid::parse_with(input, |c| Ok::<_, String>(c == '#')).unwrap();
+        grid.iter()
+            .filter(|(_, &alive)| alive)
+            .map(|((x, y), _)| (Point::embed(&[x as i64, y as i64]), true))
+            .collect()
+    }
+
+    #[test]
+    fn test_glider() {
+        let glider = parse_live::<2>(".#.\n..#\n###");
+        let mut life = Automaton::unbounded(Moore::<2>, glider.clone(), false);
+
+        life.run(4, life_rule(&[3], &[2, 3]));
+
+        // after four generations a glider is the same shape one step down and to the right
+        let mut cells = life.cells().map(|(cell, _)| cell).collect::<Vec<_>>();
+        let mut expected = glider.iter().map(|(cell, _)| *cell + Point2::new([1, 1])).collect::<Vec<_>>();
+        cells.sort();
+        expected.sort();
+        assert_eq!(cells, expected);
+    }
+
+    #[test]
+    fn test_conway_cubes() {
+        // 2020 day 17
+        let cubes = ".#.\n..#\n###";
+        let rule = life_rule(&[3], &[2, 3]);
+
+        let mut space = Automaton::unbounded(Moore::<3>, parse_live(cubes), false);
+        space.run(6, &rule);
+        assert_eq!(space.count(&true), 112);
+
+        let mut space = Automaton::unbounded(Moore::<4>, parse_live(cubes), false);
+        for _ in 0..6 {
+            space.par_step(&rule);
+        }
+        assert_eq!(space.count(&true), 848);
+    }
+
+    #[test]
+    fn test_bounded_blinker() {
+        let cells = (0..3).flat_map(|y| (0..3).map(move |x| (Point2::new([x, y]), y == 1)));
+        let mut blinker = Automaton::bounded(Moore::<2>, cells, false);
+        let rule = life_rule(&[3], &[2, 3]);
+
+        assert!(blinker.step(&rule));
+        assert_eq!(blinker.count(&true), 3);
+        assert!(*blinker.get(&Point2::new([1, 0])));
+        assert!(!*blinker.get(&Point2::new([0, 1])));
+        assert_eq!(blinker.cells().count(), 9);
+
+        // cells outside a bounded automaton never come alive
+        assert!(!*blinker.get(&Point2::new([1, -1])));
+        assert_eq!(blinker.run_until_stable(|_, _| false), 1);
+    }
+
+    #[test]
+    fn test_flipping_background() {
+        // every cell flips, so the infinite background does too
+        let mut automaton = Automaton::unbounded(VonNeumann::<1>, [(Point([0]), 1u8)], 0u8);
+
+        automaton.step(|&s, _| 1 - s);
+        assert_eq!(*automaton.background(), 1);
+        assert_eq!(automaton.cells().collect::<Vec<_>>(), vec![(Point([0]), &0)]);
+
+        automaton.step(|&s, _| 1 - s);
+        assert_eq!(*automaton.background(), 0);
+        assert_eq!(automaton.cells().collect::<Vec<_>>(), vec![(Point([0]), &1)]);
+    }
+
+    #[test]
+    fn test_empty_flipping_background() {
+        // like an image enhancer that lights up a dark 3x3 square and darkens a lit one, so even
+        // an image with nothing in it flips every generation
+        let rule = |&lit: &bool, neighbours: &[&bool]| match neighbours.iter().filter(|&&&n| n).count() {
+            0 if !lit => true,
+            8 if lit => false,
+            _ => lit,
+        };
+        let mut image = Automaton::unbounded(Moore::<2>, [], false);
+
+        assert!(image.step(rule));
+        assert!(*image.background());
+        assert!(image.step(rule));
+        assert!(!*image.background());
+        assert_eq!(image.cells().count(), 0);
+    }
+
+    #[test]
+    fn test_adjacency() {
+        // a ring of four cells passing a token clockwise
+        let ring = Adjacency((0..4).map(|i| (i, vec![(i + 3) % 4])).collect());
+        let mut automaton = Automaton::bounded(ring, (0..4).map(|i| (i, i == 0)), false);
+
+        automaton.run(5, |_, neighbours| *neighbours[0]);
+        assert!(*automaton.get(&1));
+        assert_eq!(automaton.count(&true), 1);
+        assert_eq!(automaton.topology().neighbours(&7), vec![]);
+    }
+}
@@ -0,0 +1,220 @@
+//! Cycle detection for deterministic simulations, where each state depends only on the one before.
+//! Every such sequence that runs through finitely many states ends up looping: after `start` steps
+//! it repeats every `length` steps.
+
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// A sequence whose states `start` and `start + length` are the first pair to be equal
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cycle {
+    pub start: usize,
+    pub length: usize,
+}
+
+impl Cycle {
+    /// The earliest step with the same state as step `n`
+    pub fn reduce(&self, n: usize) -> usize {
+        if n < self.start {
+            n
+        } else {
+            self.start + (n - self.start) % self.length
+        }
+    }
+
+    /// The number of steps until the first state is seen for the second time
+    pub fn first_repeat(&self) -> usize {
+        self.start + self.length
+    }
+}
+
+/// Floyd's tortoise and hare, which needs constant memory but only equality on the states
+pub fn floyd<T, F>(start: T, mut step: F) -> Cycle
+    where T: Clone + PartialEq,
+          F: FnMut(&T) -> T {
+    let mut tortoise = step(&start);
+    let mut hare = step(&tortoise);
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        let next = step(&hare);
+        hare = step(&next);
+    }
+
+    // the hare is now a multiple of the length ahead, so they meet again at the start of the loop
+    let mut cycle_start = 0;
+    tortoise = start;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        cycle_start += 1;
+    }
+
+    let mut length = 1;
+    hare = step(&tortoise);
+    while tortoise != hare {
+        hare = step(&hare);
+        length += 1;
+    }
+
+    Cycle { start: cycle_start, length }
+}
+
+/// Brent's algorithm, which like [`floyd`] needs constant memory but usually fewer steps
+pub fn brent<T, F>(start: T, mut step: F) -> Cycle
+    where T: Clone + PartialEq,
+          F: FnMut(&T) -> T {
+    let mut power = 1;
+    let mut length = 1;
+    let mut tortoise = start.clone();
+    let mut hare = step(&start);
+    while tortoise != hare {
+        if power == length {
+            tortoise = hare.clone();
+            power *= 2;
+            length = 0;
+        }
+        hare = step(&hare);
+        length += 1;
+    }
+
+    let mut cycle_start = 0;
+    tortoise = start.clone();
+    hare = start;
+    for _ in 0..length {
+        hare = step(&hare);
+    }
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        cycle_start += 1;
+    }
+
+    Cycle { start: cycle_start, length }
+}
+
+/// Finds the cycle in a single pass by remembering every state
+pub fn find_cycle<T, F>(start: T, step: F) -> Cycle
+    where T: Clone + Eq + Hash,
+          F: FnMut(&T) -> T {
+    find_cycle_by_key(start, step, T::clone)
+}
+
+/// Like [`find_cycle`], but remembers `key(state)` instead of the state itself. The key must be
+/// different for different states.
+pub fn find_cycle_by_key<T, K, F, G>(start: T, step: F, key: G) -> Cycle
+    where K: Eq + Hash,
+          F: FnMut(&T) -> T,
+          G: FnMut(&T) -> K {
+    find_repeat_by_key(start, step, key).0
+}
+
+/// Like [`find_cycle_by_key`], but also hands back the first state to be seen twice, i.e. the state
+/// after `cycle.start` steps
+pub fn find_repeat_by_key<T, K, F, G>(start: T, mut step: F, mut key: G) -> (Cycle, T)
+    where K: Eq + Hash,
+          F: FnMut(&T) -> T,
+          G: FnMut(&T) -> K {
+    let mut seen = HashMap::new();
+    let mut state = start;
+    for i in 0.. {
+        match seen.entry(key(&state)) {
+            Entry::Occupied(first) => return (Cycle { start: *first.get(), length: i - first.get() }, state),
+            Entry::Vacant(entry) => entry.insert(i),
+        };
+        state = step(&state);
+    }
+
+    unreachable!()
+}
+
+/// The state after `n` steps, skipping the repeats once the sequence starts looping
+pub fn fast_forward<T, F>(start: T, n: usize, step: F) -> T
+    where T: Clone + Eq + Hash,
+          F: FnMut(&T) -> T {
+    fast_forward_by_key(start, n, step, T::clone)
+}
+
+/// Like [`fast_forward`], but tells the states apart by `key(state)`
+pub fn fast_forward_by_key<T, K, F, G>(start: T, n: usize, mut step: F, mut key: G) -> T
+    where K: Eq + Hash,
+          F: FnMut(&T) -> T,
+          G: FnMut(&T) -> K {
+    let mut seen = HashMap::new();
+    let mut history = Vec::new();
+    let mut state = start;
+    for i in 0..n {
+        if let Some(&first) = seen.get(&key(&state)) {
+            let cycle = Cycle { start: first, length: i - first };
+            return history.swap_remove(cycle.reduce(n));
+        }
+        seen.insert(key(&state), i);
+
+        let next = step(&state);
+        history.push(state);
+        state = next;
+    }
+
+    state
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 0, 1, 2, 5, 26, 677, 330, ... which settles into a loop after a few steps
+    fn step(x: &u64) -> u64 {
+        (x * x + 1) % 1000
+    }
+
+    fn naive(start: u64, n: usize) -> u64 {
+        (0..n).fold(start, |x, _| step(&x))
+    }
+
+    #[test]
+    fn test_algorithms_agree() {
+        for start in 0..50 {
+            let cycle = find_cycle(start, step);
+
+            assert_eq!(floyd(start, step), cycle, "start {}", start);
+            assert_eq!(brent(start, step), cycle, "start {}", start);
+            assert_eq!(naive(start, cycle.start), naive(start, cycle.first_repeat()));
+            if cycle.start > 0 {
+                assert_ne!(naive(start, cycle.start - 1), naive(start, cycle.first_repeat() - 1));
+            }
+        }
+    }
+
+    #[test]
+    fn test_pure_loop() {
+        let cycle = brent(0, |x| (x + 3) % 7);
+
+        assert_eq!(cycle, Cycle { start: 0, length: 7 });
+        assert_eq!(cycle.reduce(1_000_000), 1_000_000 % 7);
+        assert_eq!(find_cycle(5u8, |&x| x), Cycle { start: 0, length: 1 });
+    }
+
+    #[test]
+    fn test_find_repeat() {
+        let (cycle, state) = find_repeat_by_key(7, step, |&x| x);
+
+        assert_eq!(cycle, find_cycle(7, step));
+        assert_eq!(state, naive(7, cycle.start));
+    }
+
+    #[test]
+    fn test_fast_forward() {
+        for n in [0, 1, 2, 10, 123, 1000] {
+            assert_eq!(fast_forward(7, n, step), naive(7, n), "n = {}", n);
+        }
+        let cycle = find_cycle(7, step);
+        assert_eq!(fast_forward(7, 1_000_000_000_000, step), naive(7, cycle.reduce(1_000_000_000_000)));
+
+        let state = fast_forward_by_key(vec![1, 2, 3], 1_000_000_001, |v| {
+            let mut v = v.clone();
+            v.rotate_left(1);
+            v
+        }, |v| v[0]);
+        assert_eq!(state, vec![3, 1, 2]);
+    }
+}
@@ -0,0 +1,305 @@
+use std::fmt::{Display, Formatter};
+use std::ops::{Add, AddAssign, Index, IndexMut, Mul, Neg, Sub, SubAssign};
+
+/// An integer point (or vector) in `N` dimensions
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point<const N: usize>(pub [i64; N]);
+
+pub type Point2 = Point<2>;
+pub type Point3 = Point<3>;
+pub type Point4 = Point<4>;
+
+impl<const N: usize> Point<N> {
+    pub const ORIGIN: Self = Point([0; N]);
+
+    pub const fn new(coords: [i64; N]) -> Self {
+        Point(coords)
+    }
+
+    /// The point with `coords` in its first dimensions and 0 in the rest, e.g. to lift a 2D input
+    /// into a higher dimensional space
+    pub fn embed(coords: &[i64]) -> Self {
+        assert!(coords.len() <= N, "cannot embed {} coordinates in {} dimensions", coords.len(), N);
+        let mut point = Self::ORIGIN;
+        point.0[..coords.len()].copy_from_slice(coords);
+        point
+    }
+
+    pub fn manhattan(&self, other: &Self) -> i64 {
+        (0..N).map(|i| (self.0[i] - other.0[i]).abs()).sum()
+    }
+
+    pub fn chebyshev(&self, other: &Self) -> i64 {
+        (0..N).map(|i| (self.0[i] - other.0[i]).abs()).max().unwrap_or(0)
+    }
+
+    /// The point with every coordinate replaced by its sign, i.e. the unit step towards it
+    pub fn signum(&self) -> Self {
+        Point(self.0.map(i64::signum))
+    }
+
+    /// The `2 * N` points one step away along a single axis
+    pub fn neighbours(&self) -> impl Iterator<Item=Self> {
+        let point = *self;
+        (0..N).flat_map(move |axis| {
+            [-1, 1].map(|d| {
+                let mut next = point;
+                next.0[axis] += d;
+                next
+            })
+        })
+    }
+
+    /// The `3^N - 1` points that differ by at most 1 in every coordinate, diagonals included
+    pub fn neighbours_all(&self) -> impl Iterator<Item=Self> {
+        let point = *self;
+        (0..3usize.pow(N as u32)).filter_map(move |mut code| {
+            let mut next = point;
+            for coord in next.0.iter_mut() {
+                *coord += (code % 3) as i64 - 1;
+                code /= 3;
+            }
+            (next != point).then_some(next)
+        })
+    }
+}
+
+macro_rules! impl_axes {
+    ($n:literal, $( $axis:ident => $i:literal ),+) => {
+        impl Point<$n> {
+            $(
+                pub const fn $axis(&self) -> i64 {
+                    self.0[$i]
+                }
+            )+
+        }
+    };
+}
+
+impl_axes!(2, x => 0, y => 1);
+impl_axes!(3, x => 0, y => 1, z => 2);
+impl_axes!(4, x => 0, y => 1, z => 2, w => 3);
+
+impl Point<2> {
+    /// Rotates a quarter turn counter-clockwise about the origin, taking `y` to point up. With `y`
+    /// pointing down, as in a parsed grid, this is a clockwise turn on screen.
+    pub const fn rotate_left(&self) -> Self {
+        Point([-self.0[1], self.0[0]])
+    }
+
+    /// The inverse of [`rotate_left`](Self::rotate_left)
+    pub const fn rotate_right(&self) -> Self {
+        Point([self.0[1], -self.0[0]])
+    }
+
+    /// Rotates `quarter_turns` times to the left, or to the right if negative
+    pub fn rotate(&self, quarter_turns: i64) -> Self {
+        match quarter_turns.rem_euclid(4) {
+            0 => *self,
+            1 => self.rotate_left(),
+            2 => -*self,
+            _ => self.rotate_right(),
+        }
+    }
+}
+
+impl Point<3> {
+    /// Rotates a quarter turn about the x axis (right-handed, counter-clockwise looking down it)
+    pub const fn rotate_x(&self) -> Self {
+        let [x, y, z] = self.0;
+        Point([x, -z, y])
+    }
+
+    pub const fn rotate_y(&self) -> Self {
+        let [x, y, z] = self.0;
+        Point([z, y, -x])
+    }
+
+    pub const fn rotate_z(&self) -> Self {
+        let [x, y, z] = self.0;
+        Point([-y, x, z])
+    }
+
+    /// The point under each of the 24 rotations of the cube, starting with the identity. The
+    /// order is the same for every point, so index `i` is the same rotation for all of them.
+    pub fn rotations(&self) -> [Self; 24] {
+        let mut rotations = [*self; 24];
+        let mut i = 0;
+        for (axes, even) in [([0, 1, 2], true), ([1, 2, 0], true), ([2, 0, 1], true),
+                             ([0, 2, 1], false), ([2, 1, 0], false), ([1, 0, 2], false)] {
+            for signs in 0..8 {
+                let sign = |bit: usize| if signs & (1 << bit) == 0 { 1 } else { -1 };
+                // a rotation is a permutation with sign flips that keeps the handedness
+                if (sign(0) * sign(1) * sign(2) == 1) != even {
+                    continue;
+                }
+                rotations[i] = Point([0, 1, 2].map(|k| sign(k) * self.0[axes[k]]));
+                i += 1;
+            }
+        }
+        rotations
+    }
+}
+
+impl<const N: usize> Default for Point<N> {
+    fn default() -> Self {
+        Self::ORIGIN
+    }
+}
+
+impl<const N: usize> From<[i64; N]> for Point<N> {
+    fn from(coords: [i64; N]) -> Self {
+        Point(coords)
+    }
+}
+
+impl From<(i64, i64)> for Point<2> {
+    fn from((x, y): (i64, i64)) -> Self {
+        Point([x, y])
+    }
+}
+
+impl From<(i64, i64, i64)> for Point<3> {
+    fn from((x, y, z): (i64, i64, i64)) -> Self {
+        Point([x, y, z])
+    }
+}
+
+impl<const N: usize> Index<usize> for Point<N> {
+    type Output = i64;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.0[index]
+    }
+}
+
+impl<const N: usize> IndexMut<usize> for Point<N> {
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        &mut self.0[index]
+    }
+}
+
+impl<const N: usize> Add for Point<N> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Point(std::array::from_fn(|i| self.0[i] + rhs.0[i]))
+    }
+}
+
+impl<const N: usize> Sub for Point<N> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Point(std::array::from_fn(|i| self.0[i] - rhs.0[i]))
+    }
+}
+
+impl<const N: usize> AddAssign for Point<N> {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl<const N: usize> SubAssign for Point<N> {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl<const N: usize> Neg for Point<N> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Point(self.0.map(|c| -c))
+    }
+}
+
+impl<const N: usize> Mul<i64> for Point<N> {
+    type Output = Self;
+
+    fn mul(self, rhs: i64) -> Self::Output {
+        Point(self.0.map(|c| c * rhs))
+    }
+}
+
+impl<const N: usize> Display for Point<N> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let coords = self.0.iter().map(|c| c.to_string()).collect::<Vec<_>>();
+        write!(f, "({})", coords.join(", "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+
+    #[test]
+    fn test_operators() {
+        let mut a = Point::new([1, -2, 3]);
+        let b = Point::from((4, 5, -6));
+
+        assert_eq!(a + b, Point([5, 3, -3]));
+        assert_eq!(a - b, Point([-3, -7, 9]));
+        assert_eq!(-a * 2, Point([-2, 4, -6]));
+        a += b;
+        a -= Point([1, 1, 1]);
+        assert_eq!((a.x(), a.y(), a.z()), (4, 2, -4));
+        assert_eq!(a.signum(), Point([1, 1, -1]));
+        assert_eq!(Point4::embed(&[3, 4]), Point([3, 4, 0, 0]));
+        assert_eq!(a.to_string(), "(4, 2, -4)");
+    }
+
+    #[test]
+    fn test_distances() {
+        let a = Point2::new([1, 1]);
+        let b = Point2::new([-2, 5]);
+
+        assert_eq!(a.manhattan(&b), 7);
+        assert_eq!(a.chebyshev(&b), 4);
+        assert_eq!(Point4::ORIGIN.manhattan(&Point([1, -1, 1, -1])), 4);
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let point = Point2::new([3, 7]);
+        let neighbours = point.neighbours().collect::<Vec<_>>();
+        assert_eq!(neighbours, vec![Point([2, 7]), Point([4, 7]), Point([3, 6]), Point([3, 8])]);
+        assert!(point.neighbours_all().all(|p| p.chebyshev(&point) == 1));
+        assert_eq!(point.neighbours_all().count(), 8);
+
+        assert_eq!(Point3::ORIGIN.neighbours().count(), 6);
+        assert_eq!(Point3::ORIGIN.neighbours_all().count(), 26);
+        assert_eq!(Point4::ORIGIN.neighbours_all().collect::<HashSet<_>>().len(), 80);
+    }
+
+    #[test]
+    fn test_rotate_2d() {
+        let point = Point2::new([2, 1]);
+
+        assert_eq!(point.rotate_left(), Point([-1, 2]));
+        assert_eq!(point.rotate_right(), Point([1, -2]));
+        assert_eq!(point.rotate(2), -point);
+        assert_eq!(point.rotate(-1), point.rotate_right());
+        assert_eq!(point.rotate(7), point.rotate_right());
+    }
+
+    #[test]
+    fn test_rotate_3d() {
+        let point = Point3::new([1, 2, 3]);
+
+        assert_eq!(point.rotate_z(), Point([-2, 1, 3]));
+        assert_eq!(point.rotate_x().rotate_x().rotate_x().rotate_x(), point);
+        assert_eq!(point.rotate_y().rotate_y(), Point([-1, 2, -3]));
+
+        let rotations = point.rotations();
+        assert_eq!(rotations[0], point);
+        assert_eq!(rotations.iter().collect::<HashSet<_>>().len(), 24);
+        for rotated in [point.rotate_x(), point.rotate_y(), point.rotate_z().rotate_x()] {
+            assert!(rotations.contains(&rotated));
+        }
+        assert!(!rotations.contains(&Point([-1, 2, 3])), "a reflection is not a rotation");
+    }
+}
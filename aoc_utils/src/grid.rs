@@ -0,0 +1,276 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::ops::{Index, IndexMut};
+use std::str::FromStr;
+
+/// A rectangular map of tiles stored row by row and indexed by `(x, y)`, where `x` is the column
+/// and `y` the row counted from the top.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+/// Error returned when a grid could not be parsed. `line` and `column` are 1-based.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GridError {
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+impl Display for GridError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "invalid grid at line {}, column {}: {}", self.line, self.column, self.message)
+    }
+}
+
+impl Error for GridError {}
+
+const OFFSETS_4: [(isize, isize); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+
+const OFFSETS_8: [(isize, isize); 8] = [
+    (-1, -1), (0, -1), (1, -1),
+    (-1, 0), (1, 0),
+    (-1, 1), (0, 1), (1, 1),
+];
+
+impl<T> Grid<T> {
+    /// Creates a `width` x `height` grid filled with `value`
+    pub fn new(width: usize, height: usize, value: T) -> Self where T: Clone {
+        Grid { width, height, cells: vec![value; width * height] }
+    }
+
+    /// Parses a grid, converting each character with `tile`. Every line must have the same number
+    /// of characters; a trailing `\r` and trailing blank lines are ignored.
+    pub fn parse_with<E, F>(input: &str, mut tile: F) -> Result<Self, GridError>
+        where E: Display,
+              F: FnMut(char) -> Result<T, E> {
+        let mut lines = input.lines()
+                             .map(|line| line.strip_suffix('\r').unwrap_or(line))
+                             .collect::<Vec<_>>();
+        while lines.last().is_some_and(|line| line.is_empty()) {
+            lines.pop();
+        }
+
+        let width = lines.first().map_or(0, |line| line.chars().count());
+        let mut cells = Vec::with_capacity(width * lines.len());
+        for (y, line) in lines.iter().enumerate() {
+            let mut count = 0;
+            for (x, c) in line.chars().enumerate() {
+                let value = tile(c).map_err(|e| GridError {
+                    line: y + 1,
+                    column: x + 1,
+                    message: e.to_string(),
+                })?;
+                cells.push(value);
+                count += 1;
+            }
+
+            if count != width {
+                return Err(GridError {
+                    line: y + 1,
+                    column: count.min(width) + 1,
+                    message: format!("expected {} columns, found {}", width, count),
+                });
+            }
+        }
+
+        Ok(Grid { width, height: lines.len(), cells })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, x: usize, y: usize) -> bool {
+        x < self.width && y < self.height
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Option<&T> {
+        if self.contains(x, y) { self.cells.get(y * self.width + x) } else { None }
+    }
+
+    pub fn get_mut(&mut self, x: usize, y: usize) -> Option<&mut T> {
+        if self.contains(x, y) { self.cells.get_mut(y * self.width + x) } else { None }
+    }
+
+    /// Moves `(x, y)` by `(dx, dy)`, returning `None` if that leaves the grid
+    pub fn offset(&self, (x, y): (usize, usize), (dx, dy): (isize, isize)) -> Option<(usize, usize)> {
+        let x = x.checked_add_signed(dx)?;
+        let y = y.checked_add_signed(dy)?;
+        if self.contains(x, y) { Some((x, y)) } else { None }
+    }
+
+    /// The up to 4 orthogonal neighbours of `(x, y)` that lie inside the grid
+    pub fn neighbours(&self, x: usize, y: usize) -> impl Iterator<Item=(usize, usize)> + '_ {
+        OFFSETS_4.iter().filter_map(move |&d| self.offset((x, y), d))
+    }
+
+    /// The up to 8 orthogonal and diagonal neighbours of `(x, y)` that lie inside the grid
+    pub fn neighbours_diagonal(&self, x: usize, y: usize) -> impl Iterator<Item=(usize, usize)> + '_ {
+        OFFSETS_8.iter().filter_map(move |&d| self.offset((x, y), d))
+    }
+
+    /// All positions in reading order
+    pub fn positions(&self) -> impl Iterator<Item=(usize, usize)> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| (x, y)))
+    }
+
+    /// All positions and their tiles in reading order
+    pub fn iter(&self) -> impl Iterator<Item=((usize, usize), &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item=((usize, usize), &mut T)> {
+        self.positions().zip(self.cells.iter_mut())
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item=&[T]> {
+        // `chunks` panics on a chunk size of 0, which only an empty grid would give
+        self.cells.chunks(self.width.max(1))
+    }
+
+    /// The position of the first tile (in reading order) matching `predicate`
+    pub fn position<P: FnMut(&T) -> bool>(&self, mut predicate: P) -> Option<(usize, usize)> {
+        self.iter().find(|(_, tile)| predicate(tile)).map(|(pos, _)| pos)
+    }
+
+    pub fn map<U, F: FnMut(&T) -> U>(&self, f: F) -> Grid<U> {
+        Grid { width: self.width, height: self.height, cells: self.cells.iter().map(f).collect() }
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (x, y): (usize, usize)) -> &Self::Output {
+        self.get(x, y)
+            .unwrap_or_else(|| panic!("({}, {}) is outside the {}x{} grid", x, y, self.width, self.height))
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut Self::Output {
+        let (width, height) = (self.width, self.height);
+        self.get_mut(x, y)
+            .unwrap_or_else(|| panic!("({}, {}) is outside the {}x{} grid", x, y, width, height))
+    }
+}
+
+impl<T> FromStr for Grid<T>
+    where T: TryFrom<char>,
+          T::Error: Display {
+    type Err = GridError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Grid::parse_with(s, T::try_from)
+    }
+}
+
+impl<T: Clone + Into<char>> Display for Grid<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for tile in row {
+                write!(f, "{}", tile.clone().into())?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_macros::AocTile;
+
+    use super::*;
+
+    const TEST_INPUT: &str = "#.#\r
+..#
+###
+
+";
+
+    #[derive(Debug, Clone, Copy, PartialEq, AocTile)]
+    enum Tile {
+        #[tile('.')]
+        Open,
+        #[tile('#', 'X')]
+        Wall,
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, AocTile)]
+    enum Maze {
+        #[tile('#')]
+        Wall,
+        #[tile('.')]
+        Open,
+        #[tile(_)]
+        Key(char),
+    }
+
+    #[test]
+    fn test_from_str() {
+        let grid = TEST_INPUT.parse::<Grid<Tile>>().unwrap();
+
+        assert_eq!((grid.width(), grid.height()), (3, 3));
+        assert_eq!(grid[(1, 0)], Tile::Open);
+        assert_eq!(grid[(2, 1)], Tile::Wall);
+        assert_eq!(grid.get(3, 0), None);
+        assert_eq!(grid.position(|&t| t == Tile::Open), Some((1, 0)));
+        assert_eq!(grid.to_string(), "#.#\n..#\n###");
+        assert_eq!("X.".parse::<Grid<Tile>>().unwrap().to_string(), "#.");
+    }
+
+    #[test]
+    fn test_catch_all_tile() {
+        let grid = "#a.\n@.#".parse::<Grid<Maze>>().unwrap();
+
+        assert_eq!(grid[(1, 0)], Maze::Key('a'));
+        assert_eq!(grid.position(|&t| t == Maze::Key('@')), Some((0, 1)));
+        assert_eq!(grid.to_string(), "#a.\n@.#");
+    }
+
+    #[test]
+    fn test_from_str_errors() {
+        let error = "#.\n.x".parse::<Grid<Tile>>().unwrap_err();
+        assert_eq!((error.line, error.column), (2, 2));
+        assert_eq!(error.message, "could not parse Tile from \"x\": expected one of '.', '#', 'X'");
+
+        let error = "#.#\n.#".parse::<Grid<Tile>>().unwrap_err();
+        assert_eq!((error.line, error.column), (2, 3));
+        assert_eq!(error.to_string(), "invalid grid at line 2, column 3: expected 3 columns, found 2");
+    }
+
+    #[test]
+    fn test_parse_with() {
+        let mut grid = Grid::parse_with("123\n456", |c| c.to_digit(10).ok_or("not a digit")).unwrap();
+        grid[(0, 1)] = 0;
+
+        assert_eq!(grid.rows().collect::<Vec<_>>(), vec![&[1, 2, 3], &[0, 5, 6]]);
+        assert_eq!(grid.map(|d| d * 2)[(2, 1)], 12);
+        assert_eq!(grid.iter().filter(|(_, &d)| d % 2 == 0).count(), 3);
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let grid = Grid::new(3, 2, '.');
+
+        assert_eq!(grid.neighbours(0, 0).collect::<Vec<_>>(), vec![(1, 0), (0, 1)]);
+        assert_eq!(grid.neighbours(1, 1).collect::<Vec<_>>(), vec![(1, 0), (2, 1), (0, 1)]);
+        assert_eq!(grid.neighbours_diagonal(2, 1).count(), 3);
+        assert_eq!(grid.neighbours_diagonal(1, 0).count(), 5);
+        assert_eq!(grid.offset((2, 1), (-2, -1)), Some((0, 0)));
+        assert_eq!(grid.offset((2, 1), (1, 0)), None);
+    }
+}
@@ -0,0 +1,282 @@
+//! Hexagonal grids in axial coordinates. The third cube coordinate is `s = -q - r`; `r` grows to
+//! the south in both orientations.
+
+use std::fmt::{Display, Formatter};
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+use crate::Point2;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct Hex {
+    pub q: i64,
+    pub r: i64,
+}
+
+/// The six unit steps, going round counter-clockwise
+const UNITS: [Hex; 6] = [
+    Hex::new(1, 0),
+    Hex::new(1, -1),
+    Hex::new(0, -1),
+    Hex::new(-1, 0),
+    Hex::new(-1, 1),
+    Hex::new(0, 1),
+];
+
+impl Hex {
+    pub const ORIGIN: Hex = Hex::new(0, 0);
+
+    pub const fn new(q: i64, r: i64) -> Self {
+        Hex { q, r }
+    }
+
+    pub fn from_cube(q: i64, r: i64, s: i64) -> Self {
+        assert_eq!(q + r + s, 0, "cube coordinates ({}, {}, {}) must sum to 0", q, r, s);
+        Hex { q, r }
+    }
+
+    pub const fn s(&self) -> i64 {
+        -self.q - self.r
+    }
+
+    pub const fn cube(&self) -> (i64, i64, i64) {
+        (self.q, self.r, self.s())
+    }
+
+    /// The number of steps between two hexes
+    pub fn distance(&self, other: &Hex) -> i64 {
+        let d = *self - *other;
+        d.q.abs().max(d.r.abs()).max(d.s().abs())
+    }
+
+    pub fn neighbours(&self) -> impl Iterator<Item=Hex> {
+        let hex = *self;
+        UNITS.iter().map(move |&unit| hex + unit)
+    }
+
+    /// The `6 * radius` hexes exactly `radius` steps away, or just this hex if `radius` is 0
+    pub fn ring(&self, radius: i64) -> Vec<Hex> {
+        if radius == 0 {
+            return vec![*self];
+        }
+
+        let mut ring = Vec::with_capacity(6 * radius as usize);
+        let mut hex = *self + UNITS[4] * radius;
+        for unit in UNITS {
+            for _ in 0..radius {
+                ring.push(hex);
+                hex += unit;
+            }
+        }
+        ring
+    }
+
+    /// All hexes at most `radius` steps away, ring by ring from the centre
+    pub fn within(&self, radius: i64) -> Vec<Hex> {
+        (0..=radius).flat_map(|r| self.ring(r)).collect()
+    }
+}
+
+/// Which way the hexes are laid out, which decides how the six directions are named
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Orientation {
+    /// Flat tops, with neighbours to the `n`, `ne`, `se`, `s`, `sw` and `nw`
+    Flat,
+    /// Pointy tops, with neighbours to the `e`, `ne`, `nw`, `w`, `sw` and `se`
+    Pointy,
+}
+
+impl Orientation {
+    pub const fn directions(self) -> [(&'static str, Hex); 6] {
+        match self {
+            Orientation::Flat => [
+                ("n", Hex::new(0, -1)),
+                ("ne", Hex::new(1, -1)),
+                ("se", Hex::new(1, 0)),
+                ("s", Hex::new(0, 1)),
+                ("sw", Hex::new(-1, 1)),
+                ("nw", Hex::new(-1, 0)),
+            ],
+            Orientation::Pointy => [
+                ("e", Hex::new(1, 0)),
+                ("ne", Hex::new(1, -1)),
+                ("nw", Hex::new(0, -1)),
+                ("w", Hex::new(-1, 0)),
+                ("sw", Hex::new(-1, 1)),
+                ("se", Hex::new(0, 1)),
+            ],
+        }
+    }
+
+    /// The unit step for a direction name such as `ne`
+    pub fn direction(self, name: &str) -> Result<Hex, String> {
+        self.directions()
+            .iter()
+            .find(|(n, _)| *n == name)
+            .map(|&(_, unit)| unit)
+            .ok_or(format!("'{}' is not a direction on a {:?} hex grid", name, self))
+    }
+
+    /// Parses a list of steps, either separated by commas (`ne,ne,s`) or run together (`nwwswee`)
+    pub fn parse_steps(self, input: &str) -> Result<Vec<Hex>, String> {
+        let input = input.trim();
+        if input.contains(',') {
+            return input.split(',').map(|name| self.direction(name.trim())).collect();
+        }
+
+        // some names are prefixes of others (`n` and `ne` in the flat layout), so take the longest match
+        let mut steps = Vec::new();
+        let mut rest = input;
+        while !rest.is_empty() {
+            let len = if rest.get(..2).is_some_and(|name| self.direction(name).is_ok()) { 2 } else { 1 };
+            let name = rest.get(..len).ok_or(format!("invalid step at '{}'", rest))?;
+            steps.push(self.direction(name)?);
+            rest = &rest[len..];
+        }
+
+        Ok(steps)
+    }
+
+    /// Doubled coordinates, where each step moves a whole square sideways and half a square along
+    /// the other axis (doubled rows for flat hexes, doubled columns for pointy ones)
+    pub fn to_doubled(self, hex: Hex) -> Point2 {
+        match self {
+            Orientation::Flat => Point2::new([hex.q, 2 * hex.r + hex.q]),
+            Orientation::Pointy => Point2::new([2 * hex.q + hex.r, hex.r]),
+        }
+    }
+
+    pub fn from_doubled(self, point: Point2) -> Hex {
+        match self {
+            Orientation::Flat => Hex::new(point.x(), (point.y() - point.x()) / 2),
+            Orientation::Pointy => Hex::new((point.x() - point.y()) / 2, point.y()),
+        }
+    }
+}
+
+impl Add for Hex {
+    type Output = Hex;
+
+    fn add(self, rhs: Hex) -> Self::Output {
+        Hex::new(self.q + rhs.q, self.r + rhs.r)
+    }
+}
+
+impl Sub for Hex {
+    type Output = Hex;
+
+    fn sub(self, rhs: Hex) -> Self::Output {
+        Hex::new(self.q - rhs.q, self.r - rhs.r)
+    }
+}
+
+impl AddAssign for Hex {
+    fn add_assign(&mut self, rhs: Hex) {
+        *self = *self + rhs;
+    }
+}
+
+impl SubAssign for Hex {
+    fn sub_assign(&mut self, rhs: Hex) {
+        *self = *self - rhs;
+    }
+}
+
+impl Neg for Hex {
+    type Output = Hex;
+
+    fn neg(self) -> Self::Output {
+        Hex::new(-self.q, -self.r)
+    }
+}
+
+impl Mul<i64> for Hex {
+    type Output = Hex;
+
+    fn mul(self, rhs: i64) -> Self::Output {
+        Hex::new(self.q * rhs, self.r * rhs)
+    }
+}
+
+impl Display for Hex {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({}, {}, {})", self.q, self.r, self.s())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+
+    fn walk(orientation: Orientation, input: &str) -> Hex {
+        orientation.parse_steps(input).unwrap().into_iter().fold(Hex::ORIGIN, |a, b| a + b)
+    }
+
+    #[test]
+    fn test_flat_distances() {
+        // 2017 day 11
+        for (input, distance) in [
+            ("ne,ne,ne", 3),
+            ("ne,ne,sw,sw", 0),
+            ("ne,ne,s,s", 2),
+            ("se,sw,se,sw,sw", 3),
+        ] {
+            assert_eq!(walk(Orientation::Flat, input).distance(&Hex::ORIGIN), distance, "{}", input);
+        }
+    }
+
+    #[test]
+    fn test_pointy_steps() {
+        // 2020 day 24
+        assert_eq!(walk(Orientation::Pointy, "esew"), Hex::ORIGIN + Orientation::Pointy.direction("se").unwrap());
+        assert_eq!(walk(Orientation::Pointy, "nwwswee"), Hex::ORIGIN);
+        assert_eq!(Orientation::Pointy.parse_steps("sesenwnenenew").unwrap().len(), 7);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(Orientation::Pointy.direction("n").is_err());
+        assert!(Orientation::Flat.direction("e").is_err());
+        assert!(Orientation::Flat.parse_steps("ne,x").is_err());
+        assert!(Orientation::Pointy.parse_steps("nex").is_err());
+        assert!(Orientation::Pointy.parse_steps("es").is_err());
+    }
+
+    #[test]
+    fn test_orientations_share_steps() {
+        for orientation in [Orientation::Flat, Orientation::Pointy] {
+            let units = orientation.directions().map(|(_, unit)| unit);
+            assert_eq!(units.iter().collect::<HashSet<_>>(), UNITS.iter().collect::<HashSet<_>>());
+            assert!(units.iter().all(|u| u.distance(&Hex::ORIGIN) == 1));
+            // opposite directions are three apart
+            assert!((0..3).all(|i| units[i] == -units[i + 3]));
+        }
+    }
+
+    #[test]
+    fn test_rings() {
+        let centre = Hex::from_cube(2, -3, 1);
+
+        assert_eq!(centre.ring(0), vec![centre]);
+        assert_eq!(centre.ring(1).into_iter().collect::<HashSet<_>>(), centre.neighbours().collect());
+        for radius in 1..5 {
+            let ring = centre.ring(radius);
+            assert_eq!(ring.len() as i64, 6 * radius);
+            assert_eq!(ring.iter().collect::<HashSet<_>>().len() as i64, 6 * radius);
+            assert!(ring.iter().all(|h| h.distance(&centre) == radius));
+        }
+        assert_eq!(centre.within(3).len(), 37);
+    }
+
+    #[test]
+    fn test_doubled() {
+        for orientation in [Orientation::Flat, Orientation::Pointy] {
+            for hex in Hex::ORIGIN.within(3) {
+                assert_eq!(orientation.from_doubled(orientation.to_doubled(hex)), hex);
+            }
+        }
+        assert_eq!(Orientation::Flat.to_doubled(Hex::new(0, -1)), Point2::new([0, -2]));
+        assert_eq!(Orientation::Pointy.to_doubled(Hex::new(1, 0)), Point2::new([2, 0]));
+    }
+}
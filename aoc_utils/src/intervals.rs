@@ -0,0 +1,394 @@
+//! Sets of integers stored as ranges, for puzzles where the ranges are far too big to list every
+//! number: intervals on a line, sets of intervals, and boxes made of one interval per axis.
+
+use std::fmt::{Display, Formatter};
+use std::ops::{Range, RangeInclusive};
+
+use crate::Point;
+
+/// The integers `start..end`, which is empty unless `start < end`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Interval {
+    pub start: i64,
+    pub end: i64,
+}
+
+impl Interval {
+    pub const fn new(start: i64, end: i64) -> Self {
+        Interval { start, end }
+    }
+
+    /// The integers `first..=last`
+    pub const fn inclusive(first: i64, last: i64) -> Self {
+        Interval { start: first, end: last + 1 }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.start >= self.end
+    }
+
+    pub fn len(&self) -> u64 {
+        if self.is_empty() { 0 } else { self.end.abs_diff(self.start) }
+    }
+
+    pub fn contains(&self, x: i64) -> bool {
+        self.start <= x && x < self.end
+    }
+
+    /// Whether every number in `other` is also in this interval
+    pub fn contains_interval(&self, other: &Interval) -> bool {
+        other.is_empty() || (self.start <= other.start && other.end <= self.end)
+    }
+
+    pub fn overlaps(&self, other: &Interval) -> bool {
+        self.intersection(other).is_some()
+    }
+
+    /// The numbers in both intervals, or `None` if there are none
+    pub fn intersection(&self, other: &Interval) -> Option<Interval> {
+        let overlap = Interval::new(self.start.max(other.start), self.end.min(other.end));
+        (!overlap.is_empty()).then_some(overlap)
+    }
+
+    /// The numbers in this interval but not in `other`, as up to two pieces in order
+    pub fn difference(&self, other: &Interval) -> Vec<Interval> {
+        if self.intersection(other).is_none() {
+            return if self.is_empty() { vec![] } else { vec![*self] };
+        }
+
+        [Interval::new(self.start, other.start), Interval::new(other.end, self.end)]
+            .into_iter()
+            .filter(|piece| !piece.is_empty())
+            .collect()
+    }
+}
+
+impl From<Range<i64>> for Interval {
+    fn from(range: Range<i64>) -> Self {
+        Interval::new(range.start, range.end)
+    }
+}
+
+impl From<RangeInclusive<i64>> for Interval {
+    fn from(range: RangeInclusive<i64>) -> Self {
+        Interval::inclusive(*range.start(), *range.end())
+    }
+}
+
+impl Display for Interval {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}..{}", self.start, self.end)
+    }
+}
+
+/// A set of integers, kept as sorted intervals that neither overlap nor touch
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct IntervalSet {
+    intervals: Vec<Interval>,
+}
+
+impl IntervalSet {
+    pub fn new() -> Self {
+        IntervalSet::default()
+    }
+
+    pub fn intervals(&self) -> &[Interval] {
+        &self.intervals
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    /// The number of integers in the set
+    pub fn len(&self) -> u64 {
+        self.intervals.iter().map(Interval::len).sum()
+    }
+
+    pub fn contains(&self, x: i64) -> bool {
+        // the first interval that ends after `x` is the only one that can hold it
+        let i = self.intervals.partition_point(|interval| interval.end <= x);
+        self.intervals.get(i).is_some_and(|interval| interval.contains(x))
+    }
+
+    pub fn insert<I: Into<Interval>>(&mut self, interval: I) {
+        let mut interval = interval.into();
+        if interval.is_empty() {
+            return;
+        }
+
+        // merge with everything that overlaps or touches it
+        let first = self.intervals.partition_point(|other| other.end < interval.start);
+        let last = self.intervals.partition_point(|other| other.start <= interval.end);
+        if first < last {
+            interval.start = interval.start.min(self.intervals[first].start);
+            interval.end = interval.end.max(self.intervals[last - 1].end);
+        }
+        self.intervals.splice(first..last, [interval]);
+    }
+
+    pub fn remove<I: Into<Interval>>(&mut self, interval: I) {
+        let interval = interval.into();
+        if interval.is_empty() {
+            return;
+        }
+
+        let first = self.intervals.partition_point(|other| other.end <= interval.start);
+        let last = self.intervals.partition_point(|other| other.start < interval.end);
+        let pieces = self.intervals[first..last].iter()
+                                                 .flat_map(|other| other.difference(&interval))
+                                                 .collect::<Vec<_>>();
+        self.intervals.splice(first..last, pieces);
+    }
+
+    pub fn union(&self, other: &IntervalSet) -> IntervalSet {
+        let mut union = self.clone();
+        for &interval in &other.intervals {
+            union.insert(interval);
+        }
+        union
+    }
+
+    pub fn intersection(&self, other: &IntervalSet) -> IntervalSet {
+        let mut intersection = Vec::new();
+        let (mut i, mut j) = (0, 0);
+        while i < self.intervals.len() && j < other.intervals.len() {
+            let (a, b) = (self.intervals[i], other.intervals[j]);
+            intersection.extend(a.intersection(&b));
+            if a.end < b.end { i += 1 } else { j += 1 }
+        }
+        IntervalSet { intervals: intersection }
+    }
+
+    pub fn difference(&self, other: &IntervalSet) -> IntervalSet {
+        let mut difference = self.clone();
+        for &interval in &other.intervals {
+            difference.remove(interval);
+        }
+        difference
+    }
+}
+
+impl<I: Into<Interval>> FromIterator<I> for IntervalSet {
+    fn from_iter<T: IntoIterator<Item=I>>(iter: T) -> Self {
+        let mut set = IntervalSet::new();
+        for interval in iter {
+            set.insert(interval);
+        }
+        set
+    }
+}
+
+/// An axis-aligned box of integer points in `N` dimensions, one interval per axis
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Cuboid<const N: usize> {
+    pub axes: [Interval; N],
+}
+
+impl<const N: usize> Cuboid<N> {
+    pub const fn new(axes: [Interval; N]) -> Self {
+        Cuboid { axes }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.axes.iter().any(Interval::is_empty)
+    }
+
+    /// The number of points in the box
+    pub fn volume(&self) -> u64 {
+        self.axes.iter().map(Interval::len).product()
+    }
+
+    pub fn contains(&self, point: &Point<N>) -> bool {
+        self.axes.iter().zip(point.0).all(|(axis, x)| axis.contains(x))
+    }
+
+    pub fn overlaps(&self, other: &Cuboid<N>) -> bool {
+        self.intersection(other).is_some()
+    }
+
+    pub fn intersection(&self, other: &Cuboid<N>) -> Option<Cuboid<N>> {
+        let mut axes = self.axes;
+        for (axis, other) in axes.iter_mut().zip(other.axes) {
+            *axis = axis.intersection(&other)?;
+        }
+        Some(Cuboid { axes })
+    }
+
+    /// The points in this box but not in `other`, as up to `2 * N` disjoint boxes
+    pub fn difference(&self, other: &Cuboid<N>) -> Vec<Cuboid<N>> {
+        let Some(overlap) = self.intersection(other) else {
+            return if self.is_empty() { vec![] } else { vec![*self] };
+        };
+
+        // peel off the slabs either side of the overlap one axis at a time, narrowing what is left
+        // down to the overlap on the axes done so far
+        let mut pieces = Vec::new();
+        let mut rest = *self;
+        for axis in 0..N {
+            for slab in rest.axes[axis].difference(&overlap.axes[axis]) {
+                let mut piece = rest;
+                piece.axes[axis] = slab;
+                pieces.push(piece);
+            }
+            rest.axes[axis] = overlap.axes[axis];
+        }
+        pieces
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use super::*;
+
+    fn numbers(interval: &Interval) -> BTreeSet<i64> {
+        (interval.start..interval.end).collect()
+    }
+
+    fn set_numbers(set: &IntervalSet) -> BTreeSet<i64> {
+        set.intervals().iter().flat_map(numbers).collect()
+    }
+
+    /// Every interval with both ends in `-1..=6`, empty ones included
+    fn small_intervals() -> Vec<Interval> {
+        (-1..=6).flat_map(|start| (-1..=6).map(move |end| Interval::new(start, end))).collect()
+    }
+
+    /// A set is valid if its intervals are non-empty, in order and neither overlap nor touch
+    fn assert_valid(set: &IntervalSet) {
+        assert!(set.intervals().iter().all(|i| !i.is_empty()), "{:?}", set);
+        assert!(set.intervals().windows(2).all(|w| w[0].end < w[1].start), "{:?}", set);
+    }
+
+    #[test]
+    fn test_interval_ops() {
+        for a in small_intervals() {
+            assert_eq!(a.len(), numbers(&a).len() as u64);
+            assert!((-2..8).all(|x| a.contains(x) == numbers(&a).contains(&x)));
+
+            for b in small_intervals() {
+                let (na, nb) = (numbers(&a), numbers(&b));
+                let both = na.intersection(&nb).copied().collect::<BTreeSet<_>>();
+
+                assert_eq!(a.intersection(&b).map_or(BTreeSet::new(), |i| numbers(&i)), both, "{} & {}", a, b);
+                assert_eq!(a.overlaps(&b), !both.is_empty());
+                assert_eq!(a.contains_interval(&b), nb.is_subset(&na), "{} contains {}", a, b);
+
+                let difference = a.difference(&b);
+                assert!(difference.iter().all(|i| !i.is_empty()));
+                assert_eq!(difference.iter().flat_map(numbers).collect::<BTreeSet<_>>(),
+                           na.difference(&nb).copied().collect(), "{} - {}", a, b);
+            }
+        }
+    }
+
+    #[test]
+    fn test_interval_conversions() {
+        assert_eq!(Interval::from(3..=5), Interval::new(3, 6));
+        assert_eq!(Interval::from(3..5), Interval::inclusive(3, 4));
+        assert_eq!(Interval::new(i64::MIN, i64::MAX).len(), u64::MAX);
+        assert_eq!(Interval::new(5, 2).len(), 0);
+        assert_eq!(Interval::new(-3, 4).to_string(), "-3..4");
+    }
+
+    #[test]
+    fn test_set_insert_and_remove() {
+        // every pair of operations on every pair of small intervals, checked against a plain set
+        for a in small_intervals() {
+            for b in small_intervals() {
+                for remove in [false, true] {
+                    let mut set: IntervalSet = [Interval::new(-1, 1), Interval::new(3, 4)].into_iter().collect();
+                    let mut expected = set_numbers(&set);
+
+                    set.insert(a);
+                    expected.extend(numbers(&a));
+                    if remove {
+                        set.remove(b);
+                        expected.retain(|x| !b.contains(*x));
+                    } else {
+                        set.insert(b);
+                        expected.extend(numbers(&b));
+                    }
+
+                    assert_valid(&set);
+                    assert_eq!(set_numbers(&set), expected, "{} then {} {}", a, if remove { "-" } else { "+" }, b);
+                    assert_eq!(set.len(), expected.len() as u64);
+                    assert!((-2..8).all(|x| set.contains(x) == expected.contains(&x)));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_set_ops() {
+        let sets = small_intervals().into_iter()
+                                    .flat_map(|a| [Interval::new(7, 9), Interval::new(-4, -2)].map(|b| [a, b]))
+                                    .map(|intervals| intervals.into_iter().collect::<IntervalSet>())
+                                    .collect::<Vec<_>>();
+
+        for a in &sets {
+            for b in &sets {
+                let (na, nb) = (set_numbers(a), set_numbers(b));
+
+                let union = a.union(b);
+                let intersection = a.intersection(b);
+                let difference = a.difference(b);
+                for set in [&union, &intersection, &difference] {
+                    assert_valid(set);
+                }
+
+                assert_eq!(set_numbers(&union), na.union(&nb).copied().collect());
+                assert_eq!(set_numbers(&intersection), na.intersection(&nb).copied().collect());
+                assert_eq!(set_numbers(&difference), na.difference(&nb).copied().collect());
+            }
+        }
+    }
+
+    #[test]
+    fn test_cuboid_difference() {
+        let small = [Interval::new(0, 3), Interval::new(1, 2), Interval::new(2, 5), Interval::new(4, 4),
+                     Interval::new(-1, 6)];
+        let boxes = small.iter()
+                         .flat_map(|&x| small.iter().map(move |&y| Cuboid::new([x, y])))
+                         .collect::<Vec<_>>();
+        let points = |c: &Cuboid<2>| {
+            (-1..6).flat_map(|x| (-1..6).map(move |y| Point([x, y])))
+                   .filter(|p| c.contains(p))
+                   .collect::<BTreeSet<_>>()
+        };
+
+        for a in &boxes {
+            assert_eq!(a.volume(), points(a).len() as u64);
+
+            for b in &boxes {
+                let (pa, pb) = (points(a), points(b));
+                assert_eq!(a.overlaps(b), !pa.is_disjoint(&pb));
+                assert_eq!(a.intersection(b).map_or(BTreeSet::new(), |c| points(&c)),
+                           pa.intersection(&pb).copied().collect());
+
+                let pieces = a.difference(b);
+                assert!(pieces.len() <= 4);
+                assert!(pieces.iter().all(|p| !p.is_empty()));
+                assert_eq!(pieces.iter().map(Cuboid::volume).sum::<u64>(), pa.difference(&pb).count() as u64,
+                           "pieces of {:?} - {:?} overlap", a, b);
+                assert_eq!(pieces.iter().flat_map(points).collect::<BTreeSet<_>>(),
+                           pa.difference(&pb).copied().collect());
+            }
+        }
+    }
+
+    #[test]
+    fn test_cuboid_3d() {
+        let a = Cuboid::new([Interval::new(0, 3); 3]);
+        let b = Cuboid::new([Interval::new(1, 2); 3]);
+
+        assert_eq!(a.difference(&b).len(), 6);
+        assert_eq!(a.difference(&b).iter().map(Cuboid::volume).sum::<u64>(), 26);
+        assert_eq!(b.difference(&a), vec![]);
+        assert_eq!(a.intersection(&b), Some(b));
+        assert!(a.contains(&Point([2, 0, 1])));
+        assert!(!b.contains(&Point([2, 0, 1])));
+    }
+}
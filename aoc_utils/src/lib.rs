@@ -0,0 +1,19 @@
+pub mod affine;
+pub mod automaton;
+pub mod cycle;
+pub mod geometry;
+pub mod grid;
+pub mod hex;
+pub mod intervals;
+pub mod machine;
+pub mod number;
+pub mod ocr;
+pub mod search;
+pub mod sparse;
+
+pub use affine::Affine;
+pub use geometry::{Point, Point2, Point3, Point4};
+pub use grid::{Grid, GridError};
+pub use hex::{Hex, Orientation};
+pub use intervals::{Cuboid, Interval, IntervalSet};
+pub use sparse::SparseGrid;
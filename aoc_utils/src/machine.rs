@@ -0,0 +1,360 @@
+//! Register machines for the puzzles that hand over a small assembly program. A day only says what
+//! each of its instructions does by implementing [`Instruction`], and the [`Machine`] takes care of
+//! the program counter, the registers, step limits, spotting loops and tracing.
+//!
+//! Anything an instruction needs besides the registers (a message queue, a count of some
+//! instruction, an accumulator) goes into a state `S` of the day's choosing. The same instructions
+//! can mean different things with different states, like `snd` and `rcv` in 2017 day 18.
+
+use std::collections::HashMap;
+use std::fmt::{Debug, Display, Formatter};
+use std::hash::Hash;
+use std::str::FromStr;
+
+/// Registers are 0 until they are first written
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Registers<K: Eq + Hash = char>(HashMap<K, i64>);
+
+impl<K: Eq + Hash> Default for Registers<K> {
+    fn default() -> Self {
+        Registers(HashMap::new())
+    }
+}
+
+impl<K: Eq + Hash> Registers<K> {
+    pub fn get(&self, register: &K) -> i64 {
+        self.0.get(register).copied().unwrap_or(0)
+    }
+
+    pub fn set(&mut self, register: K, value: i64) {
+        self.0.insert(register, value);
+    }
+
+    pub fn get_mut(&mut self, register: K) -> &mut i64 {
+        self.0.entry(register).or_insert(0)
+    }
+
+    /// What `value` stands for, reading it from the registers if it names one
+    pub fn value(&self, value: &Value<K>) -> i64 {
+        match value {
+            Value::Register(register) => self.get(register),
+            Value::Literal(v) => *v,
+        }
+    }
+
+    /// Every register that has been touched, in no particular order
+    pub fn iter(&self) -> impl Iterator<Item=(&K, i64)> {
+        self.0.iter().map(|(k, v)| (k, *v))
+    }
+}
+
+impl<K: Eq + Hash> FromIterator<(K, i64)> for Registers<K> {
+    fn from_iter<T: IntoIterator<Item=(K, i64)>>(iter: T) -> Self {
+        Registers(iter.into_iter().collect())
+    }
+}
+
+/// An operand that is either a register or a number
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Value<K = char> {
+    Register(K),
+    Literal(i64),
+}
+
+impl<K: FromStr> FromStr for Value<K> {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Ok(v) = s.parse::<i64>() {
+            return Ok(Value::Literal(v));
+        }
+        s.parse::<K>().map(Value::Register).map_err(|_| format!("'{}' is neither a number nor a register", s))
+    }
+}
+
+impl<K: Display> Display for Value<K> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Value::Register(register) => write!(f, "{}", register),
+            Value::Literal(v) => write!(f, "{}", v),
+        }
+    }
+}
+
+/// What the machine does once an instruction is done
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Flow {
+    /// Carry on with the next instruction
+    Next,
+    /// Move the program counter by this much
+    Jump(i64),
+    /// Stop without moving on, so the instruction runs again next time the machine runs. It
+    /// doesn't count as a step.
+    Wait,
+    /// Stop for good
+    Halt,
+}
+
+/// An instruction set. `S` is whatever else the instructions read and write besides the registers.
+pub trait Instruction<S = ()> {
+    type Register: Eq + Hash;
+
+    fn execute(&self, registers: &mut Registers<Self::Register>, state: &mut S) -> Flow;
+}
+
+/// Why the machine stopped
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Stop {
+    /// The program counter went past either end of the program
+    Exited,
+    /// An instruction halted
+    Halted,
+    /// An instruction is waiting. Running again picks up from it.
+    Waiting,
+    /// The step limit was reached
+    Limit,
+    /// The instruction at this position was about to run a second time
+    Loop(usize),
+}
+
+type Trace<'a, I, K> = Box<dyn FnMut(usize, &I, &Registers<K>) + 'a>;
+
+pub struct Machine<'a, I: Instruction<S>, S = ()> {
+    program: &'a [I],
+    pc: i64,
+    registers: Registers<I::Register>,
+    state: S,
+    steps: usize,
+    limit: Option<usize>,
+    visited: Option<Vec<bool>>,
+    trace: Option<Trace<'a, I, I::Register>>,
+}
+
+impl<'a, I: Instruction<S>, S: Default> Machine<'a, I, S> {
+    pub fn new(program: &'a [I]) -> Self {
+        Self::with_state(program, S::default())
+    }
+}
+
+impl<'a, I: Instruction<S>, S> Machine<'a, I, S> {
+    pub fn with_state(program: &'a [I], state: S) -> Self {
+        Machine {
+            program,
+            pc: 0,
+            registers: Registers::default(),
+            state,
+            steps: 0,
+            limit: None,
+            visited: None,
+            trace: None,
+        }
+    }
+
+    /// Stops the machine once it has run `limit` steps in total
+    pub fn with_limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Stops the machine before any instruction runs a second time. That is a sure sign of an
+    /// endless loop when the instructions only ever jump by fixed amounts and don't branch.
+    pub fn with_loop_detection(mut self) -> Self {
+        self.visited = Some(vec![false; self.program.len()]);
+        self
+    }
+
+    /// Calls `trace` with the program counter, the instruction and the registers before every
+    /// instruction runs
+    pub fn with_trace<F: FnMut(usize, &I, &Registers<I::Register>) + 'a>(mut self, trace: F) -> Self {
+        self.trace = Some(Box::new(trace));
+        self
+    }
+
+    pub fn program(&self) -> &'a [I] {
+        self.program
+    }
+
+    /// Where the machine is in the program, which is `None` once it has left it
+    pub fn pc(&self) -> Option<usize> {
+        (0..self.program.len() as i64).contains(&self.pc).then_some(self.pc as usize)
+    }
+
+    /// The number of instructions run so far
+    pub fn steps(&self) -> usize {
+        self.steps
+    }
+
+    pub fn registers(&self) -> &Registers<I::Register> {
+        &self.registers
+    }
+
+    pub fn registers_mut(&mut self) -> &mut Registers<I::Register> {
+        &mut self.registers
+    }
+
+    pub fn state(&self) -> &S {
+        &self.state
+    }
+
+    pub fn state_mut(&mut self) -> &mut S {
+        &mut self.state
+    }
+
+    pub fn into_parts(self) -> (Registers<I::Register>, S) {
+        (self.registers, self.state)
+    }
+
+    /// Runs a single instruction, unless the machine has to stop first
+    pub fn step(&mut self) -> Result<(), Stop> {
+        let pc = self.pc().ok_or(Stop::Exited)?;
+        if self.limit.is_some_and(|limit| self.steps >= limit) {
+            return Err(Stop::Limit);
+        }
+        if self.visited.as_ref().is_some_and(|visited| visited[pc]) {
+            return Err(Stop::Loop(pc));
+        }
+
+        let instruction = &self.program[pc];
+        if let Some(trace) = self.trace.as_mut() {
+            trace(pc, instruction, &self.registers);
+        }
+
+        let flow = instruction.execute(&mut self.registers, &mut self.state);
+        if flow == Flow::Wait {
+            return Err(Stop::Waiting);
+        }
+
+        self.steps += 1;
+        if let Some(visited) = self.visited.as_mut() {
+            visited[pc] = true;
+        }
+        match flow {
+            Flow::Next => self.pc += 1,
+            Flow::Jump(offset) => self.pc += offset,
+            Flow::Halt => return Err(Stop::Halted),
+            Flow::Wait => unreachable!(),
+        }
+        Ok(())
+    }
+
+    /// Runs until the machine has to stop
+    pub fn run(&mut self) -> Stop {
+        loop {
+            if let Err(stop) = self.step() {
+                return stop;
+            }
+        }
+    }
+}
+
+impl<'a, I: Instruction<S>, S: Debug> Debug for Machine<'a, I, S> where I::Register: Debug {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Machine")
+         .field("pc", &self.pc)
+         .field("steps", &self.steps)
+         .field("registers", &self.registers)
+         .field("state", &self.state)
+         .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+
+    use super::*;
+
+    /// A tiny instruction set: `inc x`, `dec x`, `jnz x offset`, `out x` and `hlt`
+    #[derive(Debug)]
+    enum Op {
+        Inc(char),
+        Dec(char),
+        Jnz(Value, Value),
+        Out(Value),
+        Hlt,
+    }
+
+    impl Instruction<Vec<i64>> for Op {
+        type Register = char;
+
+        fn execute(&self, registers: &mut Registers, output: &mut Vec<i64>) -> Flow {
+            match self {
+                Op::Inc(r) => *registers.get_mut(*r) += 1,
+                Op::Dec(r) => *registers.get_mut(*r) -= 1,
+                Op::Jnz(x, offset) if registers.value(x) != 0 => return Flow::Jump(registers.value(offset)),
+                Op::Jnz(..) => {}
+                Op::Out(x) => output.push(registers.value(x)),
+                Op::Hlt => return Flow::Halt,
+            }
+            Flow::Next
+        }
+    }
+
+    fn countdown(from: i64) -> Vec<Op> {
+        vec![
+            Op::Jnz(Value::Literal(1), Value::Literal(2)),
+            Op::Hlt,
+            Op::Out(Value::Register('a')),
+            Op::Dec('a'),
+            Op::Jnz(Value::Register('a'), Value::Literal(-2)),
+            Op::Out(Value::Literal(from)),
+        ]
+    }
+
+    #[test]
+    fn test_value() {
+        assert_eq!("-12".parse::<Value>(), Ok(Value::Literal(-12)));
+        assert_eq!("b".parse::<Value>(), Ok(Value::Register('b')));
+        assert_eq!("abc".parse::<Value<String>>(), Ok(Value::Register("abc".to_string())));
+        assert!("abc".parse::<Value>().is_err());
+
+        let registers = Registers::from_iter([('b', 5)]);
+        assert_eq!(registers.value(&Value::Register('b')), 5);
+        assert_eq!(registers.value(&Value::Register('c')), 0);
+        assert_eq!(registers.value(&Value::Literal(7)), 7);
+    }
+
+    #[test]
+    fn test_run() {
+        let program = countdown(3);
+        let mut machine = Machine::<Op, Vec<i64>>::new(&program);
+        machine.registers_mut().set('a', 3);
+
+        assert_eq!(machine.run(), Stop::Exited);
+        assert_eq!(machine.pc(), None);
+        assert_eq!(machine.state(), &vec![3, 2, 1, 3]);
+        assert_eq!(machine.steps(), 1 + 3 * 3 + 1);
+
+        let program = [Op::Out(Value::Literal(1)), Op::Hlt, Op::Out(Value::Literal(2))];
+        let mut machine = Machine::<Op, Vec<i64>>::new(&program);
+        assert_eq!(machine.run(), Stop::Halted);
+        assert_eq!(machine.pc(), Some(1));
+        assert_eq!(machine.into_parts().1, vec![1]);
+    }
+
+    #[test]
+    fn test_limit_and_loops() {
+        let program = [Op::Inc('a'), Op::Jnz(Value::Literal(1), Value::Literal(-1))];
+
+        let mut machine = Machine::<Op, Vec<i64>>::new(&program).with_limit(7);
+        assert_eq!(machine.run(), Stop::Limit);
+        assert_eq!(machine.registers().get(&'a'), 4);
+
+        let mut machine = Machine::<Op, Vec<i64>>::new(&program).with_loop_detection();
+        assert_eq!(machine.run(), Stop::Loop(0));
+        assert_eq!((machine.steps(), machine.registers().get(&'a')), (2, 1));
+    }
+
+    #[test]
+    fn test_trace() {
+        let program = countdown(2);
+        let trace = RefCell::new(Vec::new());
+        let mut machine = Machine::<Op, Vec<i64>>::new(&program)
+            .with_trace(|pc, _, registers| trace.borrow_mut().push((pc, registers.get(&'a'))));
+        machine.registers_mut().set('a', 2);
+        machine.run();
+        drop(machine);
+
+        assert_eq!(trace.into_inner(), vec![(0, 2), (2, 2), (3, 2), (4, 1), (2, 1), (3, 1), (4, 0), (5, 0)]);
+    }
+}
@@ -0,0 +1,256 @@
+//! Number theory for the puzzles built around modular arithmetic. Everything except the discrete
+//! log is generic over [`Integer`], so the same code runs on `i64`, on `i128` when the product of
+//! two residues would overflow a 64-bit type, and with the `bigint` feature on [`BigInt`] when not
+//! even that is enough.
+
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::hash::Hash;
+use std::ops::{Add, Div, Mul, Rem, Sub};
+
+#[cfg(feature = "bigint")]
+pub use num_bigint::BigInt;
+
+/// A signed integer type. The arithmetic has to be exact, so pick a type with room for the product
+/// of two numbers below the modulus.
+pub trait Integer: Clone + Ord + Hash + Debug + From<u8>
+    + Add<Output=Self> + Sub<Output=Self> + Mul<Output=Self> + Div<Output=Self> + Rem<Output=Self> {}
+
+impl<T> Integer for T where T: Clone + Ord + Hash + Debug + From<u8>
+    + Add<Output=T> + Sub<Output=T> + Mul<Output=T> + Div<Output=T> + Rem<Output=T> {}
+
+fn zero<T: Integer>() -> T {
+    T::from(0)
+}
+
+fn one<T: Integer>() -> T {
+    T::from(1)
+}
+
+fn abs<T: Integer>(a: T) -> T {
+    if a < zero() { zero::<T>() - a } else { a }
+}
+
+/// The greatest common divisor, which is never negative
+pub fn gcd<T: Integer>(a: T, b: T) -> T {
+    let (mut a, mut b) = (abs(a), abs(b));
+    while b > zero() {
+        (a, b) = (b.clone(), a % b);
+    }
+    a
+}
+
+/// The least common multiple, which is never negative
+pub fn lcm<T: Integer>(a: T, b: T) -> T {
+    if a == zero() || b == zero() {
+        return zero();
+    }
+    let g = gcd(a.clone(), b.clone());
+    abs(a / g * b)
+}
+
+/// Returns `(g, x, y)` where `g = gcd(a, b)` and `a * x + b * y = g`
+pub fn extended_gcd<T: Integer>(a: T, b: T) -> (T, T, T) {
+    let (mut r0, mut r1) = (a, b);
+    let (mut x0, mut x1) = (one::<T>(), zero::<T>());
+    let (mut y0, mut y1) = (zero::<T>(), one::<T>());
+
+    while r1 != zero() {
+        let q = r0.clone() / r1.clone();
+        (r0, r1) = (r1.clone(), r0 - q.clone() * r1);
+        (x0, x1) = (x1.clone(), x0 - q.clone() * x1);
+        (y0, y1) = (y1.clone(), y0 - q * y1);
+    }
+
+    if r0 < zero() {
+        (zero::<T>() - r0, zero::<T>() - x0, zero::<T>() - y0)
+    } else {
+        (r0, x0, y0)
+    }
+}
+
+/// `a` reduced into `0..modulus`, even when it is negative
+pub fn modulo<T: Integer>(a: T, modulus: T) -> T {
+    let r = a % modulus.clone();
+    if r < zero() { r + modulus } else { r }
+}
+
+/// `base` to the power of `exp` modulo `modulus`, by repeated squaring
+pub fn mod_pow<T: Integer>(base: T, exp: T, modulus: T) -> T {
+    assert!(exp >= zero(), "negative exponent {:?}, take the inverse first", exp);
+
+    let two = T::from(2);
+    let mut base = modulo(base, modulus.clone());
+    let mut exp = exp;
+    let mut result = modulo(one(), modulus.clone());
+    while exp > zero() {
+        if exp.clone() % two.clone() == one() {
+            result = result * base.clone() % modulus.clone();
+        }
+        base = base.clone() * base % modulus.clone();
+        exp = exp / two.clone();
+    }
+    result
+}
+
+/// The `x` in `0..modulus` with `a * x = 1`, which exists when `a` and `modulus` are coprime
+pub fn mod_inverse<T: Integer>(a: T, modulus: T) -> Option<T> {
+    let (g, x, _) = extended_gcd(modulo(a, modulus.clone()), modulus.clone());
+    if g == one() { Some(modulo(x, modulus)) } else { None }
+}
+
+/// Solves the system `x = residue (mod modulus)` for every pair, returning the smallest solution
+/// that is not negative and the modulus it repeats with. The moduli don't have to be coprime, in
+/// which case there is no solution unless the congruences agree wherever their moduli overlap.
+pub fn crt<T: Integer, I: IntoIterator<Item=(T, T)>>(congruences: I) -> Option<(T, T)> {
+    congruences.into_iter().try_fold((zero(), one()), |(x, m): (T, T), (residue, modulus)| {
+        // x + m * k = residue (mod modulus), solved for k in steps of modulus / g
+        let g = gcd(m.clone(), modulus.clone());
+        let diff = residue - x.clone();
+        if diff.clone() % g.clone() != zero() {
+            return None;
+        }
+
+        let step = modulus / g.clone();
+        let inverse = mod_inverse(m.clone() / g.clone(), step.clone())?;
+        let k = modulo(modulo(diff / g, step.clone()) * inverse, step.clone());
+        let lcm = m.clone() * step;
+        Some((modulo(x + m * k, lcm.clone()), lcm))
+    })
+}
+
+/// The smallest `x` that isn't negative with `base^x = target (mod modulus)`, by baby-step
+/// giant-step. `base` has to be coprime to `modulus`. It takes time and memory in the square root of
+/// the modulus, so it only makes sense for 64-bit moduli.
+pub fn discrete_log(base: i64, target: i64, modulus: i64) -> Option<i64> {
+    let modulus = modulus as i128;
+    let (base, target) = (modulo(base as i128, modulus), modulo(target as i128, modulus));
+    let steps = (modulus as f64).sqrt().ceil() as i128;
+
+    // base^j for every j below steps, keeping the smallest j for each value
+    let mut baby = HashMap::new();
+    let mut value = modulo(1, modulus);
+    for j in 0..steps {
+        baby.entry(value).or_insert(j);
+        value = value * base % modulus;
+    }
+
+    // target * base^(-steps * i) for every i, until one lands on a baby step
+    let giant = mod_pow(mod_inverse(base, modulus)?, steps, modulus);
+    let mut value = target;
+    for i in 0..steps {
+        if let Some(j) = baby.get(&value) {
+            return Some((i * steps + j) as i64);
+        }
+        value = value * giant % modulus;
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gcd_lcm() {
+        for (a, b, exp) in [(78, 15, 3), (99, 53, 1), (48, 56, 8), (-12, 18, 6), (0, 7, 7)] {
+            assert_eq!(gcd(a, b), exp);
+            assert_eq!(gcd(b, a), exp);
+        }
+        assert_eq!(lcm(4, 6), 12);
+        assert_eq!(lcm(-4, 6), 12);
+        assert_eq!(lcm(0, 6), 0);
+        assert_eq!([18i64, 28, 44].into_iter().fold(1, lcm), 2772);
+    }
+
+    #[test]
+    fn test_extended_gcd() {
+        for a in -30i64..30 {
+            for b in -30i64..30 {
+                let (g, x, y) = extended_gcd(a, b);
+                assert_eq!(g, gcd(a, b), "gcd({}, {})", a, b);
+                assert_eq!(a * x + b * y, g, "{} * {} + {} * {}", a, x, b, y);
+            }
+        }
+    }
+
+    #[test]
+    fn test_mod_pow_and_inverse() {
+        assert_eq!(mod_pow(7i64, 0, 13), 1);
+        assert_eq!(mod_pow(7i64, 1, 1), 0);
+        assert_eq!(mod_pow(-2i64, 3, 11), 3);
+        assert_eq!(mod_pow(3i128, 200, 1_000_000_007), 136_318_165);
+
+        for m in 1i64..40 {
+            for a in -40..40 {
+                match mod_inverse(a, m) {
+                    Some(x) => assert_eq!(modulo(a * x, m), 1 % m, "{}^-1 mod {}", a, m),
+                    None => assert_ne!(gcd(a, m), 1, "{} mod {} has an inverse", a, m),
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_crt() {
+        // 2020 day 13: bus 17 at t, bus 13 at t + 2 and bus 19 at t + 3
+        assert_eq!(crt([(0i64, 17), (-2, 13), (-3, 19)]), Some((3417, 4199)));
+        assert_eq!(crt(Vec::<(i64, i64)>::new()), Some((0, 1)));
+
+        // against brute force, including moduli that share factors
+        for m1 in 1i64..13 {
+            for m2 in 1i64..13 {
+                for a1 in 0..m1 {
+                    for a2 in 0..m2 {
+                        let exp = (0..lcm(m1, m2)).find(|x| x % m1 == a1 && x % m2 == a2);
+                        assert_eq!(crt([(a1, m1), (a2, m2)]), exp.map(|x| (x, lcm(m1, m2))));
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_discrete_log() {
+        // 2020 day 25
+        assert_eq!(discrete_log(7, 5764801, 20201227), Some(8));
+        assert_eq!(discrete_log(7, 17807724, 20201227), Some(11));
+
+        for m in 2i64..50 {
+            for base in 1..m {
+                for target in 0..m {
+                    let exp = (0..m).find(|&x| mod_pow(base, x, m) == target);
+                    let ans = discrete_log(base, target, m);
+                    if gcd(base, m) == 1 {
+                        assert_eq!(ans, exp, "log_{} {} mod {}", base, target, m);
+                    } else {
+                        assert_eq!(ans, None);
+                    }
+                }
+            }
+        }
+
+        // bases and targets outside 0..modulus stand for the same residues as inside
+        assert_eq!(discrete_log(-3, 6, 11), Some(3));
+        assert_eq!(discrete_log(-1, 10, 11), Some(1));
+        assert_eq!(discrete_log(7 + 20201227, 5764801, 20201227), Some(8));
+        assert_eq!(discrete_log(7 - 2 * 20201227, 17807724 - 20201227, 20201227), Some(11));
+    }
+
+    #[cfg(feature = "bigint")]
+    #[test]
+    fn test_bigint() {
+        // 2^127 - 1 is prime, so the product of two residues needs more than 128 bits
+        let one = BigInt::from(1u8);
+        let p = (one.clone() << 127usize) - one.clone();
+        let a = BigInt::from(u64::MAX) * BigInt::from(12345);
+
+        let inverse = mod_inverse(a.clone(), p.clone()).unwrap();
+        assert_eq!(modulo(a.clone() * inverse, p.clone()), one);
+        assert_eq!(mod_pow(a, p.clone() - one.clone(), p.clone()), one);
+
+        let (x, m) = crt([(BigInt::from(3), p.clone()), (BigInt::from(5), BigInt::from(7))]).unwrap();
+        assert_eq!(m, p.clone() * BigInt::from(7));
+        assert_eq!((x.clone() % p, x % BigInt::from(7)), (BigInt::from(3), BigInt::from(5)));
+    }
+}
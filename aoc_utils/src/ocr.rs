@@ -0,0 +1,161 @@
+//! Reads the block letters some puzzles draw as their answer. Two fonts turn up: a small one six
+//! pixels high, where most letters are four pixels wide, and a large one ten pixels high and six
+//! wide. Letters are told apart by the empty columns between them.
+
+use crate::{Grid, Point2, SparseGrid};
+
+const SMALL: [(char, &str); 18] = [
+    ('A', ".##.\n#..#\n#..#\n####\n#..#\n#..#"),
+    ('B', "###.\n#..#\n###.\n#..#\n#..#\n###."),
+    ('C', ".##.\n#..#\n#...\n#...\n#..#\n.##."),
+    ('E', "####\n#...\n###.\n#...\n#...\n####"),
+    ('F', "####\n#...\n###.\n#...\n#...\n#..."),
+    ('G', ".##.\n#..#\n#...\n#.##\n#..#\n.###"),
+    ('H', "#..#\n#..#\n####\n#..#\n#..#\n#..#"),
+    ('I', "###\n.#.\n.#.\n.#.\n.#.\n###"),
+    ('J', "..##\n...#\n...#\n...#\n#..#\n.##."),
+    ('K', "#..#\n#.#.\n##..\n#.#.\n#.#.\n#..#"),
+    ('L', "#...\n#...\n#...\n#...\n#...\n####"),
+    ('O', ".##.\n#..#\n#..#\n#..#\n#..#\n.##."),
+    ('P', "###.\n#..#\n#..#\n###.\n#...\n#..."),
+    ('R', "###.\n#..#\n#..#\n###.\n#.#.\n#..#"),
+    ('S', ".###\n#...\n#...\n.##.\n...#\n###."),
+    ('U', "#..#\n#..#\n#..#\n#..#\n#..#\n.##."),
+    ('Y', "#...#\n#...#\n.#.#.\n..#..\n..#..\n..#.."),
+    ('Z', "####\n...#\n..#.\n.#..\n#...\n####"),
+];
+
+const LARGE: [(char, &str); 15] = [
+    ('A', "..##..\n.#..#.\n#....#\n#....#\n#....#\n######\n#....#\n#....#\n#....#\n#....#"),
+    ('B', "#####.\n#....#\n#....#\n#....#\n#####.\n#....#\n#....#\n#....#\n#....#\n#####."),
+    ('C', ".####.\n#....#\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#....#\n.####."),
+    ('E', "######\n#.....\n#.....\n#.....\n#####.\n#.....\n#.....\n#.....\n#.....\n######"),
+    ('F', "######\n#.....\n#.....\n#.....\n#####.\n#.....\n#.....\n#.....\n#.....\n#....."),
+    ('G', ".####.\n#....#\n#.....\n#.....\n#.....\n#..###\n#....#\n#....#\n#...##\n.###.#"),
+    ('H', "#....#\n#....#\n#....#\n#....#\n######\n#....#\n#....#\n#....#\n#....#\n#....#"),
+    ('J', "...###\n....#.\n....#.\n....#.\n....#.\n....#.\n....#.\n#...#.\n#...#.\n.###.."),
+    ('K', "#....#\n#...#.\n#..#..\n#.#...\n##....\n##....\n#.#...\n#..#..\n#...#.\n#....#"),
+    ('L', "#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n######"),
+    ('N', "#....#\n##...#\n##...#\n#.#..#\n#.#..#\n#..#.#\n#..#.#\n#...##\n#...##\n#....#"),
+    ('P', "#####.\n#....#\n#....#\n#....#\n#####.\n#.....\n#.....\n#.....\n#.....\n#....."),
+    ('R', "#####.\n#....#\n#....#\n#....#\n#####.\n#..#..\n#...#.\n#...#.\n#....#\n#....#"),
+    ('X', "#....#\n#....#\n.#..#.\n.#..#.\n..##..\n..##..\n.#..#.\n.#..#.\n#....#\n#....#"),
+    ('Z', "######\n.....#\n.....#\n....#.\n...#..\n..#...\n.#....\n#.....\n#.....\n######"),
+];
+
+/// Reads the letters drawn by the lit points, with `y` growing downwards
+pub fn read_points<I: IntoIterator<Item=Point2>>(points: I) -> Result<String, String> {
+    let image: SparseGrid<bool> = points.into_iter().collect();
+    let Some((min, max)) = image.bounding_box() else {
+        return Err("there are no letters in an empty image".to_string());
+    };
+
+    let font: &[(char, &str)] = match max.y() - min.y() + 1 {
+        6 => &SMALL,
+        10 => &LARGE,
+        height => return Err(format!("no font is {} pixels high", height)),
+    };
+
+    // every run of columns with a lit pixel is a letter
+    let mut letters = Vec::new();
+    let mut start = None;
+    for x in min.x()..=max.x() + 1 {
+        let lit = (min.y()..=max.y()).any(|y| image[Point2::new([x, y])]);
+        match (start, lit) {
+            (None, true) => start = Some(x),
+            (Some(from), false) => {
+                letters.push(from..x);
+                start = None;
+            }
+            _ => {}
+        }
+    }
+
+    letters.into_iter()
+           .map(|columns| {
+               let glyph = (min.y()..=max.y())
+                   .map(|y| columns.clone().map(|x| if image[Point2::new([x, y])] { '#' } else { '.' }).collect::<String>())
+                   .collect::<Vec<_>>()
+                   .join("\n");
+
+               font.iter()
+                   .find(|(_, pattern)| *pattern == glyph)
+                   .map(|&(letter, _)| letter)
+                   .ok_or(format!("unknown letter at x = {}:\n{}", columns.start, glyph))
+           })
+           .collect()
+}
+
+/// Reads the letters in a grid, where `lit` picks out the pixels that are part of them
+pub fn read_grid<T, F: Fn(&T) -> bool>(grid: &Grid<T>, lit: F) -> Result<String, String> {
+    read_points(grid.iter()
+                    .filter(|(_, tile)| lit(tile))
+                    .map(|((x, y), _)| Point2::new([x as i64, y as i64])))
+}
+
+/// Reads the letters drawn with `#` in a block of text
+pub fn read_text(text: &str) -> Result<String, String> {
+    read_points(text.lines().enumerate().flat_map(|(y, line)| {
+        line.chars()
+            .enumerate()
+            .filter(|&(_, c)| c == '#')
+            .map(move |(x, _)| Point2::new([x as i64, y as i64]))
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Draws `word` in `font` with a blank column between letters
+    fn draw(font: &[(char, &str)], word: &str) -> String {
+        let glyphs = word.chars()
+                         .map(|c| font.iter().find(|(letter, _)| *letter == c).unwrap().1)
+                         .map(|glyph| glyph.lines().collect::<Vec<_>>())
+                         .collect::<Vec<_>>();
+
+        (0..glyphs[0].len())
+            .map(|row| glyphs.iter().map(|g| g[row]).collect::<Vec<_>>().join("."))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn test_fonts() {
+        let small = SMALL.iter().map(|&(c, _)| c).collect::<String>();
+        let large = LARGE.iter().map(|&(c, _)| c).collect::<String>();
+
+        assert_eq!(read_text(&draw(&SMALL, &small)), Ok(small));
+        assert_eq!(read_text(&draw(&LARGE, &large)), Ok(large));
+    }
+
+    #[test]
+    fn test_read_text() {
+        // 2021 day 13, drawn with spaces
+        let text = "
+####   ##  ##  #  #   ##  ##   ##  #  #
+#       # #  # #  #    # #  # #  # #  #
+###     # #  # ####    # #    #  # ####
+#       # #### #  #    # # ## #### #  #
+#    #  # #  # #  # #  # #  # #  # #  #
+#     ##  #  # #  #  ##   ### #  # #  #";
+
+        assert_eq!(read_text(text), Ok("FJAHJGAH".to_string()));
+    }
+
+    #[test]
+    fn test_read_grid() {
+        let grid = Grid::parse_with(&draw(&SMALL, "HI"), |c| Ok::<_, String>(c == '#')).unwrap();
+
+        assert_eq!(read_grid(&grid, |&lit| lit), Ok("HI".to_string()));
+    }
+
+    #[test]
+    fn test_errors() {
+        assert!(read_text("").is_err());
+        assert!(read_text("#\n#\n#").unwrap_err().contains("3 pixels high"));
+
+        let error = read_text("#..#\n#..#\n####\n#..#\n#..#\n#.##").unwrap_err();
+        assert!(error.starts_with("unknown letter at x = 0"), "{}", error);
+    }
+}
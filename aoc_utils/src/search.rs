@@ -0,0 +1,388 @@
+//! Graph searches over any state type. A search space is described by a [`Graph`], which gives the
+//! states reachable from a state and the cost of each move. Closures of the form
+//! `FnMut(&N) -> Vec<(N, usize)>` are graphs, so most days can describe the moves inline.
+
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::collections::hash_map::Entry;
+use std::hash::Hash;
+
+pub trait Graph<N> {
+    /// The states reachable in one move from `node`, each with the cost of that move
+    fn neighbours(&mut self, node: &N) -> Vec<(N, usize)>;
+}
+
+impl<N, F> Graph<N> for F where F: FnMut(&N) -> Vec<(N, usize)> {
+    fn neighbours(&mut self, node: &N) -> Vec<(N, usize)> {
+        self(node)
+    }
+}
+
+/// A path found by a search, from the start to the goal (both included)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Path<N> {
+    pub cost: usize,
+    pub nodes: Vec<N>,
+}
+
+impl<N> Path<N> {
+    /// The number of moves taken, which may differ from the cost in a weighted graph
+    pub fn steps(&self) -> usize {
+        self.nodes.len() - 1
+    }
+
+    pub fn goal(&self) -> &N {
+        self.nodes.last().unwrap()
+    }
+}
+
+/// Every state seen by a search, with the best known cost to reach it and where it was reached
+/// from. States are stored once and referred to by their index.
+struct Explored<N> {
+    nodes: Vec<N>,
+    index: HashMap<N, usize>,
+    costs: Vec<usize>,
+    parents: Vec<usize>,
+}
+
+impl<N: Clone + Eq + Hash> Explored<N> {
+    fn new(start: N) -> Self {
+        Explored {
+            nodes: vec![start.clone()],
+            index: HashMap::from([(start, 0)]),
+            costs: vec![0],
+            parents: vec![0],
+        }
+    }
+
+    /// Records that `node` can be reached from `parent` at `cost`. Returns the node's index if that
+    /// is the first or the cheapest way found so far.
+    fn relax(&mut self, node: N, parent: usize, cost: usize) -> Option<usize> {
+        match self.index.entry(node) {
+            Entry::Occupied(entry) => {
+                let i = *entry.get();
+                if cost < self.costs[i] {
+                    self.costs[i] = cost;
+                    self.parents[i] = parent;
+                    Some(i)
+                } else {
+                    None
+                }
+            }
+            Entry::Vacant(entry) => {
+                let i = self.nodes.len();
+                self.nodes.push(entry.key().clone());
+                entry.insert(i);
+                self.costs.push(cost);
+                self.parents.push(parent);
+                Some(i)
+            }
+        }
+    }
+
+    fn path(&self, mut i: usize) -> Vec<N> {
+        let mut path = vec![self.nodes[i].clone()];
+        while i != 0 {
+            i = self.parents[i];
+            path.push(self.nodes[i].clone());
+        }
+        path.reverse();
+        path
+    }
+
+    fn into_costs(self) -> HashMap<N, usize> {
+        self.nodes.into_iter().zip(self.costs).collect()
+    }
+}
+
+/// Breadth-first search for the path with the fewest moves to a state satisfying `is_goal`. Move
+/// costs are ignored, every move counts as one step.
+pub fn bfs<N, G, P>(graph: &mut G, start: N, mut is_goal: P) -> Option<Path<N>>
+    where N: Clone + Eq + Hash,
+          G: Graph<N>,
+          P: FnMut(&N) -> bool {
+    let mut explored = Explored::new(start);
+    let mut queue = VecDeque::from([0]);
+
+    while let Some(i) = queue.pop_front() {
+        if is_goal(&explored.nodes[i]) {
+            return Some(Path { cost: explored.costs[i], nodes: explored.path(i) });
+        }
+
+        let steps = explored.costs[i] + 1;
+        for (next, _) in graph.neighbours(&explored.nodes[i].clone()) {
+            if !explored.index.contains_key(&next) {
+                queue.extend(explored.relax(next, i, steps));
+            }
+        }
+    }
+
+    None
+}
+
+/// The number of moves needed to reach every state reachable from `start`
+pub fn bfs_distances<N, G>(graph: &mut G, start: N) -> HashMap<N, usize>
+    where N: Clone + Eq + Hash,
+          G: Graph<N> {
+    let mut explored = Explored::new(start);
+    let mut queue = VecDeque::from([0]);
+
+    while let Some(i) = queue.pop_front() {
+        let steps = explored.costs[i] + 1;
+        for (next, _) in graph.neighbours(&explored.nodes[i].clone()) {
+            if !explored.index.contains_key(&next) {
+                queue.extend(explored.relax(next, i, steps));
+            }
+        }
+    }
+
+    explored.into_costs()
+}
+
+/// Breadth-first search from both ends at once, which explores far fewer states when the
+/// branching factor is high. Every move must be reversible, i.e. the graph must be undirected.
+pub fn bidirectional_bfs<N, G>(graph: &mut G, start: N, goal: N) -> Option<Path<N>>
+    where N: Clone + Eq + Hash,
+          G: Graph<N> {
+    if start == goal {
+        return Some(Path { cost: 0, nodes: vec![start] });
+    }
+
+    let mut sides = [Explored::new(start), Explored::new(goal)];
+    let mut frontiers = [vec![0], vec![0]];
+
+    while !frontiers[0].is_empty() && !frontiers[1].is_empty() {
+        // grow whichever side has the smaller frontier by one full layer
+        let side = if frontiers[0].len() <= frontiers[1].len() { 0 } else { 1 };
+        let mut next_frontier = Vec::new();
+        let mut best: Option<(usize, N)> = None;
+
+        for i in std::mem::take(&mut frontiers[side]) {
+            let steps = sides[side].costs[i] + 1;
+            for (next, _) in graph.neighbours(&sides[side].nodes[i].clone()) {
+                if sides[side].index.contains_key(&next) {
+                    continue;
+                }
+                if let Some(&j) = sides[1 - side].index.get(&next) {
+                    let total = steps + sides[1 - side].costs[j];
+                    if best.as_ref().is_none_or(|(cost, _)| total < *cost) {
+                        best = Some((total, next.clone()));
+                    }
+                }
+                next_frontier.extend(sides[side].relax(next, i, steps));
+            }
+        }
+
+        if let Some((cost, meeting)) = best {
+            let mut nodes = sides[0].path(sides[0].index[&meeting]);
+            let mut back = sides[1].path(sides[1].index[&meeting]);
+            back.pop();
+            nodes.extend(back.into_iter().rev());
+            return Some(Path { cost, nodes });
+        }
+        frontiers[side] = next_frontier;
+    }
+
+    None
+}
+
+/// Dijkstra's search for the cheapest path to a state satisfying `is_goal`
+pub fn dijkstra<N, G, P>(graph: &mut G, start: N, is_goal: P) -> Option<Path<N>>
+    where N: Clone + Eq + Hash,
+          G: Graph<N>,
+          P: FnMut(&N) -> bool {
+    astar(graph, start, is_goal, |_| 0)
+}
+
+/// The cost of the cheapest path to every state reachable from `start`
+pub fn dijkstra_all<N, G>(graph: &mut G, start: N) -> HashMap<N, usize>
+    where N: Clone + Eq + Hash,
+          G: Graph<N> {
+    let (explored, _) = best_first(graph, start, |_| false, |_| 0);
+    explored.into_costs()
+}
+
+/// A* search for the cheapest path to a state satisfying `is_goal`. `heuristic` estimates the
+/// remaining cost from a state, and the path is only guaranteed to be the cheapest if it never
+/// overestimates.
+pub fn astar<N, G, P, H>(graph: &mut G, start: N, is_goal: P, heuristic: H) -> Option<Path<N>>
+    where N: Clone + Eq + Hash,
+          G: Graph<N>,
+          P: FnMut(&N) -> bool,
+          H: FnMut(&N) -> usize {
+    let (explored, goal) = best_first(graph, start, is_goal, heuristic);
+    goal.map(|i| Path { cost: explored.costs[i], nodes: explored.path(i) })
+}
+
+fn best_first<N, G, P, H>(graph: &mut G,
+                          start: N,
+                          mut is_goal: P,
+                          mut heuristic: H) -> (Explored<N>, Option<usize>)
+    where N: Clone + Eq + Hash,
+          G: Graph<N>,
+          P: FnMut(&N) -> bool,
+          H: FnMut(&N) -> usize {
+    let mut heap = BinaryHeap::from([Reverse((heuristic(&start), 0, 0))]);
+    let mut explored = Explored::new(start);
+
+    while let Some(Reverse((_, cost, i))) = heap.pop() {
+        if cost > explored.costs[i] {
+            // a cheaper way here was found after this entry was queued
+            continue;
+        }
+        let node = explored.nodes[i].clone();
+        if is_goal(&node) {
+            return (explored, Some(i));
+        }
+
+        for (next, step) in graph.neighbours(&node) {
+            let next_cost = cost + step;
+            if let Some(j) = explored.relax(next, i, next_cost) {
+                heap.push(Reverse((next_cost + heuristic(&explored.nodes[j]), next_cost, j)));
+            }
+        }
+    }
+
+    (explored, None)
+}
+
+/// Counts the distinct paths from `start` to states satisfying `is_goal`. Goal states are not
+/// searched past. The states reachable from `start` must not form a cycle, which usually means
+/// the state has to carry whatever stops a path from going round in circles (such as the set of
+/// places visited so far).
+pub fn count_paths<N, G, P>(graph: &mut G, start: N, mut is_goal: P) -> usize
+    where N: Clone + Eq + Hash,
+          G: Graph<N>,
+          P: FnMut(&N) -> bool {
+    fn count<N, G, P>(graph: &mut G, node: N, is_goal: &mut P, memo: &mut HashMap<N, usize>) -> usize
+        where N: Clone + Eq + Hash,
+              G: Graph<N>,
+              P: FnMut(&N) -> bool {
+        if is_goal(&node) {
+            return 1;
+        }
+        if let Some(&n) = memo.get(&node) {
+            return n;
+        }
+
+        let total = graph.neighbours(&node)
+                         .into_iter()
+                         .map(|(next, _)| count(graph, next, is_goal, memo))
+                         .sum();
+        memo.insert(node, total);
+        total
+    }
+
+    count(graph, start, &mut is_goal, &mut HashMap::new())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MAZE: &str = "\
+#########
+#S..#...#
+#.#.#.#.#
+#.#...#G#
+#########";
+
+    type Cell = (usize, usize);
+
+    fn maze_graph(maze: &str) -> impl FnMut(&Cell) -> Vec<(Cell, usize)> + '_ {
+        let lines = maze.lines().map(|l| l.as_bytes()).collect::<Vec<_>>();
+        move |&(x, y)| {
+            [(x - 1, y), (x + 1, y), (x, y - 1), (x, y + 1)]
+                .into_iter()
+                .filter(|&(x, y)| lines[y][x] != b'#')
+                .map(|p| (p, 1))
+                .collect()
+        }
+    }
+
+    #[test]
+    fn test_bfs() {
+        let path = bfs(&mut maze_graph(MAZE), (1, 1), |&p| p == (7, 3)).unwrap();
+
+        assert_eq!(path.cost, 12);
+        assert_eq!(path.steps(), 12);
+        assert_eq!(path.nodes[0], (1, 1));
+        assert_eq!(*path.goal(), (7, 3));
+        assert!(path.nodes.windows(2).all(|w| w[0].0.abs_diff(w[1].0) + w[0].1.abs_diff(w[1].1) == 1));
+
+        assert!(bfs(&mut maze_graph(MAZE), (1, 1), |&p| p == (0, 0)).is_none());
+    }
+
+    #[test]
+    fn test_bfs_distances() {
+        let distances = bfs_distances(&mut maze_graph(MAZE), (1, 1));
+
+        assert_eq!(distances.len(), 15);
+        assert_eq!(distances[&(1, 3)], 2);
+        assert_eq!(distances[&(7, 3)], 12);
+        assert_eq!(distances.values().max(), Some(&12));
+    }
+
+    #[test]
+    fn test_bidirectional_bfs() {
+        let path = bidirectional_bfs(&mut maze_graph(MAZE), (1, 1), (7, 3)).unwrap();
+        assert_eq!(path, bfs(&mut maze_graph(MAZE), (1, 1), |&p| p == (7, 3)).unwrap());
+
+        let path = bidirectional_bfs(&mut maze_graph(MAZE), (5, 1), (5, 2)).unwrap();
+        assert_eq!(path.nodes, vec![(5, 1), (5, 2)]);
+        assert_eq!(bidirectional_bfs(&mut maze_graph(MAZE), (3, 3), (3, 3)).unwrap().cost, 0);
+
+        let mut line = |&n: &i64| vec![(n - 1, 1), (n + 1, 1)];
+        assert_eq!(bidirectional_bfs(&mut line, 0, 7).unwrap().nodes, (0..=7).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_dijkstra() {
+        // the direct edge is more expensive than the detour
+        let mut graph = |&n: &char| match n {
+            'a' => vec![('b', 7), ('c', 2)],
+            'c' => vec![('b', 3), ('d', 10)],
+            'b' => vec![('d', 1)],
+            _ => vec![],
+        };
+
+        let path = dijkstra(&mut graph, 'a', |&n| n == 'd').unwrap();
+        assert_eq!(path.cost, 6);
+        assert_eq!(path.nodes, vec!['a', 'c', 'b', 'd']);
+        assert_eq!(bfs(&mut graph, 'a', |&n| n == 'd').unwrap().steps(), 2);
+
+        let costs = dijkstra_all(&mut graph, 'a');
+        assert_eq!(costs, HashMap::from([('a', 0), ('b', 5), ('c', 2), ('d', 6)]));
+    }
+
+    #[test]
+    fn test_astar() {
+        let goal = (7, 3);
+        let manhattan = |&(x, y): &(usize, usize)| x.abs_diff(goal.0) + y.abs_diff(goal.1);
+        let mut visited = 0;
+        let mut graph = maze_graph(MAZE);
+        let mut counting = |p: &(usize, usize)| {
+            visited += 1;
+            graph(p)
+        };
+
+        let path = astar(&mut counting, (1, 1), |&p| p == goal, manhattan).unwrap();
+        assert_eq!(path.cost, 12);
+        assert!(visited < 15);
+    }
+
+    #[test]
+    fn test_count_paths() {
+        // paths through a 3x3 lattice moving only right or down
+        let mut graph = |&(x, y): &(u8, u8)| {
+            let mut next = vec![];
+            if x < 3 { next.push(((x + 1, y), 1)); }
+            if y < 3 { next.push(((x, y + 1), 1)); }
+            next
+        };
+
+        assert_eq!(count_paths(&mut graph, (0, 0), |&p| p == (3, 3)), 20);
+        // paths are not followed past a goal, so only 8 of the paths to (3, 3) avoid (1, 1)
+        assert_eq!(count_paths(&mut graph, (0, 0), |&p| p == (3, 3) || p == (1, 1)), 2 + 8);
+    }
+}
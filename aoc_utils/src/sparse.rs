@@ -0,0 +1,184 @@
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use std::ops::Index;
+
+use crate::{Grid, Point, Point2};
+
+/// An unbounded grid that only stores the cells that have been set. Every other cell reads as the
+/// `background` value, which can change over time for maps whose infinite region flips.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SparseGrid<T, const N: usize = 2> {
+    cells: HashMap<Point<N>, T>,
+    background: T,
+}
+
+impl<T, const N: usize> SparseGrid<T, N> {
+    pub fn new(background: T) -> Self {
+        SparseGrid { cells: HashMap::new(), background }
+    }
+
+    /// Copies the tiles of a parsed grid with the top left corner at the origin, lifting them into
+    /// the `z = 0` plane (and so on) if `N` is more than 2
+    pub fn from_grid(grid: &Grid<T>, background: T) -> Self where T: Clone {
+        let cells = grid.iter()
+                        .map(|((x, y), tile)| (Point::embed(&[x as i64, y as i64]), tile.clone()))
+                        .collect();
+        SparseGrid { cells, background }
+    }
+
+    pub fn background(&self) -> &T {
+        &self.background
+    }
+
+    pub fn set_background(&mut self, background: T) {
+        self.background = background;
+    }
+
+    /// The value at `point`, which is the background unless it has been set
+    pub fn get(&self, point: &Point<N>) -> &T {
+        self.cells.get(point).unwrap_or(&self.background)
+    }
+
+    /// The value at `point`, storing a copy of the background first if it has not been set
+    pub fn get_mut(&mut self, point: Point<N>) -> &mut T where T: Clone {
+        let background = &self.background;
+        self.cells.entry(point).or_insert_with(|| background.clone())
+    }
+
+    pub fn insert(&mut self, point: Point<N>, value: T) -> Option<T> {
+        self.cells.insert(point, value)
+    }
+
+    pub fn remove(&mut self, point: &Point<N>) -> Option<T> {
+        self.cells.remove(point)
+    }
+
+    /// Whether `point` has been set, even if to the background value
+    pub fn contains(&self, point: &Point<N>) -> bool {
+        self.cells.contains_key(point)
+    }
+
+    /// The number of cells that have been set
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// The cells that have been set, in no particular order
+    pub fn iter(&self) -> impl Iterator<Item=(Point<N>, &T)> {
+        self.cells.iter().map(|(&point, value)| (point, value))
+    }
+
+    pub fn points(&self) -> impl Iterator<Item=Point<N>> + '_ {
+        self.cells.keys().copied()
+    }
+
+    /// The number of set cells holding `value`
+    pub fn count(&self, value: &T) -> usize where T: PartialEq {
+        self.cells.values().filter(|&v| v == value).count()
+    }
+
+    pub fn retain<F: FnMut(&Point<N>, &mut T) -> bool>(&mut self, f: F) {
+        self.cells.retain(f)
+    }
+
+    /// The smallest and largest coordinates of the set cells along every axis, or `None` if no
+    /// cell has been set
+    pub fn bounding_box(&self) -> Option<(Point<N>, Point<N>)> {
+        let mut points = self.cells.keys();
+        let first = *points.next()?;
+
+        Some(points.fold((first, first), |(mut min, mut max), point| {
+            for axis in 0..N {
+                min[axis] = min[axis].min(point[axis]);
+                max[axis] = max[axis].max(point[axis]);
+            }
+            (min, max)
+        }))
+    }
+}
+
+impl<T> SparseGrid<T, 2> {
+    /// Draws the bounding box row by row, with `y` growing downwards and unset cells drawn as the
+    /// background
+    pub fn render<F: FnMut(&T) -> char>(&self, mut tile: F) -> String {
+        let Some((min, max)) = self.bounding_box() else {
+            return String::new();
+        };
+
+        (min.y()..=max.y())
+            .map(|y| (min.x()..=max.x()).map(|x| tile(self.get(&Point2::new([x, y])))).collect::<String>())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+impl<T, const N: usize> Index<Point<N>> for SparseGrid<T, N> {
+    type Output = T;
+
+    fn index(&self, point: Point<N>) -> &Self::Output {
+        self.get(&point)
+    }
+}
+
+/// A set of points, stored as `true` on a `false` background
+impl<const N: usize> FromIterator<Point<N>> for SparseGrid<bool, N> {
+    fn from_iter<I: IntoIterator<Item=Point<N>>>(iter: I) -> Self {
+        SparseGrid { cells: iter.into_iter().map(|point| (point, true)).collect(), background: false }
+    }
+}
+
+impl<T: Clone + Into<char>> Display for SparseGrid<T, 2> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.render(|tile| tile.clone().into()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+
+    #[test]
+    fn test_background() {
+        let mut grid = SparseGrid::new('.');
+        grid.insert(Point2::new([-2, 1]), '#');
+        *grid.get_mut(Point2::new([1, -1])) = '#';
+        grid.get_mut(Point2::new([0, 0]));
+
+        assert_eq!(grid[Point2::new([-2, 1])], '#');
+        assert_eq!(grid[Point2::new([100, 100])], '.');
+        assert!(grid.contains(&Point2::ORIGIN));
+        assert_eq!((grid.len(), grid.count(&'#')), (3, 2));
+        assert_eq!(grid.bounding_box(), Some((Point([-2, -1]), Point([1, 1]))));
+        assert_eq!(grid.to_string(), "...#\n....\n#...");
+
+        grid.set_background(' ');
+        grid.remove(&Point2::ORIGIN);
+        assert_eq!(grid.to_string(), "   #\n    \n#   ");
+    }
+
+    #[test]
+    fn test_from_grid() {
+        let grid = Grid::parse_with("#.\n.#", |c| Ok::<_, String>(c == '#')).unwrap();
+        let mut sparse: SparseGrid<bool, 3> = SparseGrid::from_grid(&grid, false);
+        sparse.retain(|_, &mut active| active);
+
+        assert_eq!(sparse.points().collect::<HashSet<_>>(),
+                   [Point([0, 0, 0]), Point([1, 1, 0])].into_iter().collect());
+        assert_eq!(sparse.bounding_box(), Some((Point([0, 0, 0]), Point([1, 1, 0]))));
+    }
+
+    #[test]
+    fn test_points() {
+        let grid: SparseGrid<bool> = [Point([0, 0]), Point([2, 1])].into_iter().collect();
+
+        assert_eq!(grid.render(|&b| if b { '#' } else { ' ' }), "#  \n  #");
+        assert!(SparseGrid::<bool>::new(false).bounding_box().is_none());
+        assert_eq!(SparseGrid::<char>::new('.').to_string(), "");
+    }
+}
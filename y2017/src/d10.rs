@@ -1,6 +1,7 @@
 use std::collections::HashMap;
 use std::fmt::{Display, Formatter};
 
+use aoc_macros::aoc;
 use itertools::Itertools;
 
 fn get_input() -> Vec<usize> {
@@ -88,6 +89,7 @@ impl Display for KnotHash {
 }
 
 
+#[aoc(year = 2017, day = 10, part = 1)]
 pub fn solve_a() {
     let inputs = get_input();
     let mut sk = KnotHash::new(256);
@@ -98,6 +100,7 @@ pub fn solve_a() {
     println!("Solution A: {}", ans);
 }
 
+#[aoc(year = 2017, day = 10, part = 2)]
 pub fn solve_b() {
     let input = format_input(&get_input()
         .iter()
@@ -1,95 +1,33 @@
-use std::ops::AddAssign;
-
-use itertools::Itertools;
+use aoc_macros::aoc;
+use aoc_utils::{Hex, Orientation};
 
 use crate::inputs::read_content;
 
-fn get_inputs() -> Vec<String> {
-    read_content(11)
-        .split(",")
-        .map(|w| w.to_string())
-        .collect_vec()
-}
-
-#[derive(Eq, PartialEq, Debug)]
-struct Point(i32, i32);
-
-
-impl AddAssign for Point {
-    fn add_assign(&mut self, rhs: Self) {
-        self.0 += rhs.0;
-        self.1 += rhs.1;
-    }
-}
-
-impl Point {
-    fn new() -> Point { Point(0, 0) }
-
-    fn move_point(&mut self, direction: &str) {
-        match direction {
-            "n" => {
-                *self += Point(0, 2);
-            }
-            "nw" => {
-                *self += Point(-2, 1);
-            }
-            "ne" => {
-                *self += Point(2, 1);
-            }
-            "s" => {
-                *self += Point(0, -2);
-            }
-            "sw" => {
-                *self += Point(-2, -1);
-            }
-            "se" => {
-                *self += Point(2, -1);
-            }
-            _ => {
-                panic!("Invalid direction: {}", direction);
-            }
-        }
-    }
-
-    fn distance_from_origin(&self) -> usize {
-        let x = self.0.abs() as usize;
-        let y = self.1.abs() as usize;
-
-        let horizontal = x / 2;
-
-        let vertical = if y > horizontal {
-            (y - horizontal) / 2
-        } else {
-            0
-        };
-
-        horizontal + vertical
-    }
+fn get_inputs() -> Vec<Hex> {
+    Orientation::Flat.parse_steps(&read_content(11)).unwrap()
 }
 
+#[aoc(year = 2017, day = 11, part = 1)]
 pub fn solve_a() {
-    let directions = get_inputs();
+    let steps = get_inputs();
 
-    let mut point = Point::new();
+    let point = steps.into_iter().fold(Hex::ORIGIN, |point, step| point + step);
 
-    for dir in directions.iter() {
-        point.move_point(dir);
-    }
-
-    let ans = point.distance_from_origin();
+    let ans = point.distance(&Hex::ORIGIN);
     println!("Solution A: {}", ans);
 }
 
+#[aoc(year = 2017, day = 11, part = 2)]
 pub fn solve_b() {
-    let directions = get_inputs();
-    let mut point = Point::new();
+    let steps = get_inputs();
+    let mut point = Hex::ORIGIN;
 
     let mut furthest = 0;
 
-    for dir in directions.iter() {
-        point.move_point(dir);
+    for step in steps {
+        point += step;
 
-        let dist = point.distance_from_origin();
+        let dist = point.distance(&Hex::ORIGIN);
         if dist > furthest {
             furthest = dist;
         }
@@ -100,20 +38,19 @@ pub fn solve_b() {
 
 #[cfg(test)]
 mod tests {
-    use crate::d11::Point;
+    use aoc_utils::{Hex, Orientation};
 
     #[test]
     fn test_point_movement() {
         for (d, exp) in [
-            ("n", Point(0, 2)),
-            ("nw", Point(-2, 1)),
-            ("ne", Point(2, 1)),
-            ("s", Point(0, -2)),
-            ("sw", Point(-2, -1)),
-            ("se", Point(2, -1))
+            ("n", Hex::new(0, -1)),
+            ("nw", Hex::new(-1, 0)),
+            ("ne", Hex::new(1, -1)),
+            ("s", Hex::new(0, 1)),
+            ("sw", Hex::new(-1, 1)),
+            ("se", Hex::new(1, 0))
         ] {
-            let mut point = Point::new();
-            point.move_point(d);
+            let point = Hex::ORIGIN + Orientation::Flat.direction(d).unwrap();
 
             assert_eq!(point, exp);
         }
@@ -122,23 +59,23 @@ mod tests {
     #[test]
     fn test_distance_from_origin() {
         for (dirs, exp_dist, exp_point) in [
-            ("ne,ne,ne", 3, Point(6, 3)),
-            ("ne,ne,sw,sw", 0, Point(0, 0)),
-            ("ne,ne,s,s", 2, Point(4, -2)),
-            ("se,sw,se,sw,sw", 3, Point(-2, -5)),
-            ("ne,se,ne,se", 4, Point(8, 0)),
-            ("ne,se,ne,se,ne,ne", 6, Point(12, 2)),
-            ("ne,se,ne,se,ne,ne,n,n", 6, Point(12, 6)),
-            ("ne,se,ne,se,ne,ne,n,n,n", 7, Point(12, 8)),
+            ("ne,ne,ne", 3, Hex::new(3, -3)),
+            ("ne,ne,sw,sw", 0, Hex::new(0, 0)),
+            ("ne,ne,s,s", 2, Hex::new(2, 0)),
+            ("se,sw,se,sw,sw", 3, Hex::new(-1, 3)),
+            ("ne,se,ne,se", 4, Hex::new(4, -2)),
+            ("ne,se,ne,se,ne,ne", 6, Hex::new(6, -4)),
+            ("ne,se,ne,se,ne,ne,n,n", 6, Hex::new(6, -6)),
+            ("ne,se,ne,se,ne,ne,n,n,n", 7, Hex::new(6, -7)),
         ] {
-            let mut point = Point::new();
+            let mut point = Hex::ORIGIN;
 
-            for dir in dirs.split(",") {
-                point.move_point(dir);
+            for step in Orientation::Flat.parse_steps(dirs).unwrap() {
+                point += step;
             }
 
             assert_eq!(point, exp_point);
-            assert_eq!(point.distance_from_origin(), exp_dist);
+            assert_eq!(point.distance(&Hex::ORIGIN), exp_dist);
         }
     }
-}
\ No newline at end of file
+}
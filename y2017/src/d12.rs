@@ -1,5 +1,7 @@
 use std::collections::{HashMap, HashSet, VecDeque};
 
+use aoc_macros::aoc;
+
 use crate::inputs::read_content;
 
 struct Graph {
@@ -97,6 +99,7 @@ impl Graph {
     }
 }
 
+#[aoc(year = 2017, day = 12, part = 1)]
 pub fn solve_a() {
     let input = read_content(12);
     let graph = Graph::new(&input);
@@ -105,6 +108,7 @@ pub fn solve_a() {
     println!("Solution A: {}", ans);
 }
 
+#[aoc(year = 2017, day = 12, part = 2)]
 pub fn solve_b() {
     let input = read_content(12);
     let graph = Graph::new(&input);
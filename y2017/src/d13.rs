@@ -1,5 +1,7 @@
 use std::collections::HashMap;
 
+use aoc_macros::aoc;
+
 use crate::inputs::read_content;
 
 #[derive(Debug)]
@@ -91,6 +93,7 @@ impl Firewall {
 }
 
 
+#[aoc(year = 2017, day = 13, part = 1)]
 pub fn solve_a() {
     let firewall = Firewall::new(&read_content(13));
     let ans = firewall.run_packet();
@@ -98,6 +101,7 @@ pub fn solve_a() {
     println!("Solution A: {}", ans);
 }
 
+#[aoc(year = 2017, day = 13, part = 2)]
 pub fn solve_b() {
     let firewall = Firewall::new(&read_content(13));
     let ans = firewall.find_delay();
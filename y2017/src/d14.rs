@@ -1,5 +1,6 @@
 use std::collections::{BTreeSet, VecDeque};
 
+use aoc_macros::aoc;
 use itertools::Itertools;
 use rayon::prelude::*;
 
@@ -7,6 +8,7 @@ use crate::d10::{format_input, knot_hash};
 
 const PUZZLE_INPUT: &str = "uugsqrei";
 
+#[aoc(year = 2017, day = 14, part = 1)]
 pub fn solve_a() {
     let total: usize = (0..128).into_par_iter()
         .map(|i| {
@@ -28,6 +30,7 @@ fn form_row(input: &str) -> String {
         .collect::<String>()
 }
 
+#[aoc(year = 2017, day = 14, part = 2)]
 pub fn solve_b() {
     let map = form_map();
     let ans = count_regions(&map);
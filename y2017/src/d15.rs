@@ -1,5 +1,6 @@
 use std::thread;
 
+use aoc_macros::aoc;
 use crossbeam::channel::unbounded;
 
 // Generator A starts with 116
@@ -7,6 +8,7 @@ use crossbeam::channel::unbounded;
 const A: u64 = 116;
 const B: u64 = 299;
 
+#[aoc(year = 2017, day = 15, part = 1)]
 pub fn solve_a() {
     let ans = count_matches(40_000_000, A, B);
     println!("Solution A: {}", ans);
@@ -49,6 +51,7 @@ fn last_16_bytes_match(a: u64, b: u64) -> bool {
     &a[a.len() - 16..] == &b[b.len() - 16..]
 }
 
+#[aoc(year = 2017, day = 15, part = 2)]
 pub fn solve_b() {
     let ans = count_matches_with_clause(5_000_000, A, B);
     println!("Solution B: {}", ans);
@@ -1,5 +1,7 @@
 use std::collections::HashMap;
 
+use aoc_macros::aoc;
+use aoc_utils::cycle;
 use itertools::Itertools;
 use regex::Regex;
 
@@ -89,6 +91,7 @@ fn position_hash(positions: &HashMap<usize, char>) -> String {
     (0..positions.len()).map(|i| positions.get(&i).unwrap()).join("")
 }
 
+#[aoc(year = 2017, day = 16, part = 1)]
 pub fn solve_a() {
     let moves = get_dance_moves(&read_content(16));
 
@@ -101,35 +104,21 @@ pub fn solve_a() {
     println!("Solution A: {}", ans)
 }
 
+#[aoc(year = 2017, day = 16, part = 2)]
 pub fn solve_b() {
     let moves = get_dance_moves(&read_content(16));
-    let mut positions = get_initial_state();
-    let ans = get_order(&moves, &mut positions, 1_000_000_000);
+    let positions = get_initial_state();
+    let ans = get_order(&moves, &positions, 1_000_000_000);
 
     println!("Solution B: {}", ans);
 }
 
-fn get_order(moves: &Vec<Dance>, positions: &mut HashMap<usize, char>, limit: usize) -> String {
-    let mut seen: HashMap<String, usize> = HashMap::new();
-
-    for i in 1..=limit {
-        dance(moves, positions);
-        let hash = position_hash(&positions);
-
-        // not the most optimal if (limit % delta == 0)
-        if let Some(&k) = seen.get(&hash) {
-            let delta = i - k;
-            let offset = limit % delta;
-
-            for (k, v) in &seen {
-                if *v == offset {
-                    return k.to_string();
-                }
-            }
-        } else {
-            seen.insert(hash, i);
-        }
-    }
+fn get_order(moves: &Vec<Dance>, positions: &HashMap<usize, char>, limit: usize) -> String {
+    let positions = cycle::fast_forward_by_key(positions.clone(), limit, |positions| {
+        let mut next = positions.clone();
+        dance(moves, &mut next);
+        next
+    }, position_hash);
 
     position_hash(&positions)
 }
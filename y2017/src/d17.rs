@@ -1,5 +1,7 @@
 use std::ptr;
 
+use aoc_macros::aoc;
+
 const N: usize = 303;
 
 
@@ -93,6 +95,7 @@ impl List<usize> {
 }
 
 
+#[aoc(year = 2017, day = 17, part = 1)]
 pub fn solve_a() {
     let mut list = List::new(N);
 
@@ -108,6 +111,7 @@ pub fn solve_a() {
 }
 
 
+#[aoc(year = 2017, day = 17, part = 2)]
 pub fn solve_b() {
     let mut ans = 0;
     let mut i = 0;  // index position
@@ -1,109 +1,92 @@
-use std::collections::{HashMap, VecDeque};
+use std::collections::VecDeque;
+
+use aoc_macros::aoc;
+use aoc_utils::machine::{self, Flow, Machine, Registers, Value};
 
 use crate::inputs::read_content;
+use crate::network::{Link, run_network};
 
 #[derive(Debug)]
 enum Instruction {
-    Snd(InsVal),
-    Rcv(InsVal),
-    Set(InsVal, InsVal),
-    Mul(InsVal, InsVal),
-    Add(InsVal, InsVal),
-    Mod(InsVal, InsVal),
-    Jgz(InsVal, InsVal),
-}
-
-#[derive(Debug)]
-enum InsVal {
-    Integer(i64),
-    Char(char),
-}
-
-impl InsVal {
-    fn new(input: &str) -> Self {
-        match input.parse::<i64>() {
-            Ok(v) => { InsVal::Integer(v) }
-            Err(_) => {
-                assert_eq!(input.len(), 1);
-                InsVal::Char(input.chars().next().unwrap())
-            }
-        }
-    }
-
-    fn get_char(&self) -> Option<char> {
-        match self {
-            InsVal::Integer(_) => None,
-            InsVal::Char(c) => Some(*c),
-        }
-    }
-
-    fn get_integer(&self) -> Option<i64> {
-        match self {
-            InsVal::Char(_) => None,
-            InsVal::Integer(v) => Some(*v),
-        }
-    }
+    Snd(Value),
+    Rcv(char),
+    Set(char, Value),
+    Mul(char, Value),
+    Add(char, Value),
+    Mod(char, Value),
+    Jgz(Value, Value),
 }
 
 fn parse_instructions(input: &str) -> Vec<Instruction> {
     input.lines()
         .map(|line| {
             let parts = line.trim().split(" ").collect::<Vec<_>>();
-            let src = InsVal::new(parts[1]);
+            let value = |x: &str| x.parse::<Value>().unwrap_or_else(|e| panic!("{}", e));
+            let register = |x: &str| {
+                assert_eq!(x.len(), 1);
+                x.chars().next().unwrap()
+            };
 
             match parts[0] {
-                "snd" => { Instruction::Snd(src) }
-                "rcv" => { Instruction::Rcv(src) }
-                "set" => { Instruction::Set(src, InsVal::new(parts[2])) }
-                "add" => { Instruction::Add(src, InsVal::new(parts[2])) }
-                "mul" => { Instruction::Mul(src, InsVal::new(parts[2])) }
-                "mod" => { Instruction::Mod(src, InsVal::new(parts[2])) }
-                "jgz" => { Instruction::Jgz(src, InsVal::new(parts[2])) }
+                "snd" => { Instruction::Snd(value(parts[1])) }
+                "rcv" => { Instruction::Rcv(register(parts[1])) }
+                "set" => { Instruction::Set(register(parts[1]), value(parts[2])) }
+                "add" => { Instruction::Add(register(parts[1]), value(parts[2])) }
+                "mul" => { Instruction::Mul(register(parts[1]), value(parts[2])) }
+                "mod" => { Instruction::Mod(register(parts[1]), value(parts[2])) }
+                "jgz" => { Instruction::Jgz(value(parts[1]), value(parts[2])) }
                 _ => { panic!("Invalid command: {}", parts[0]); }
             }
         })
         .collect()
 }
 
-struct Register {
-    register: HashMap<char, i64>,
-}
-
-impl Register {
-    fn new() -> Self {
-        Register { register: HashMap::new() }
-    }
-
-    fn get(&mut self, ins_val: &InsVal) -> i64 {
-        if let Some(c) = ins_val.get_char() {
-            self.register.get(&c).map_or(0, |v| *v)
-        } else {
-            ins_val.get_integer().unwrap()
-        }
-    }
-
-    fn get_mut(&mut self, ins_val: &InsVal) -> &mut i64 {
-        let c = ins_val.get_char().unwrap();
-        if !self.register.contains_key(&c) {
-            self.register.insert(c, 0);
+impl Instruction {
+    /// Runs every instruction except `snd` and `rcv`, which mean something different in each part
+    fn execute_common(&self, registers: &mut Registers) -> Flow {
+        match self {
+            Instruction::Set(x, v) => registers.set(*x, registers.value(v)),
+            Instruction::Mul(x, y) => *registers.get_mut(*x) *= registers.value(y),
+            Instruction::Add(x, y) => *registers.get_mut(*x) += registers.value(y),
+            Instruction::Mod(x, y) => *registers.get_mut(*x) %= registers.value(y),
+            Instruction::Jgz(x, y) if registers.value(x) > 0 => {
+                let y = registers.value(y);
+                if y == 0 {
+                    panic!("Jump can't be 0!")
+                }
+                return Flow::Jump(y);
+            }
+            Instruction::Jgz(..) => {}
+            Instruction::Snd(_) | Instruction::Rcv(_) => unreachable!("{:?} depends on the part", self),
         }
-
-        self.register.get_mut(&c).unwrap()
+        Flow::Next
     }
+}
 
-    fn set(&mut self, src: &InsVal, value: &InsVal) {
-        let c = src.get_char().unwrap();
-
-        let value = match value {
-            InsVal::Integer(v) => *v,
-            InsVal::Char(_) => { self.get(value) }
-        };
+/// How part A reads the instructions: `snd` plays a sound and `rcv` recovers the last one played,
+/// unless its register is 0
+#[derive(Debug, Default)]
+struct Sound {
+    last_frequency: i64,
+}
 
+impl machine::Instruction<Sound> for Instruction {
+    type Register = char;
 
-        self.register.insert(c, value);
+    fn execute(&self, registers: &mut Registers, sound: &mut Sound) -> Flow {
+        match self {
+            Instruction::Snd(x) => {
+                sound.last_frequency = registers.value(x);
+                Flow::Next
+            }
+            Instruction::Rcv(x) if registers.get(x) != 0 => Flow::Halt,
+            Instruction::Rcv(_) => Flow::Next,
+            _ => self.execute_common(registers),
+        }
     }
 }
 
+#[aoc(year = 2017, day = 18, part = 1)]
 pub fn solve_a() {
     let instructions = parse_instructions(&read_content(18));
     let ans = get_first_recovered_frequency(&instructions);
@@ -112,187 +95,137 @@ pub fn solve_a() {
     println!("Solution A: {}", ans);
 }
 
-fn get_first_recovered_frequency(instructions: &Vec<Instruction>) -> i64 {
-    let mut i = 0;
-    let mut register = Register::new();
-    let mut last_frequency = 0;
-
-    while i < instructions.len() {
-        match &instructions[i] {
-            Instruction::Snd(x) => {
-                last_frequency = register.get(x);
-                i += 1;
-            }
-            Instruction::Rcv(x) => {
-                if register.get(x) != 0 {
-                    break;
-                } else {
-                    i += 1;
-                }
-            }
-            Instruction::Set(x, v) => {
-                register.set(x, v);
-                i += 1;
-            }
-            Instruction::Mul(x, y) => {
-                let y = register.get(y);
-                let v = register.get_mut(x);
-                *v *= y;
-                i += 1;
-            }
-            Instruction::Add(x, y) => {
-                let y = register.get(y);
-                let v = register.get_mut(x);
-                *v += y;
-                i += 1;
-            }
-            Instruction::Mod(x, y) => {
-                let y = register.get(y);
-                let v = register.get_mut(x);
-                *v %= y;
-                i += 1;
-            }
-            Instruction::Jgz(x, y) => {
-                if register.get(x) <= 0 {
-                    i += 1;
-                } else {
-                    let y = register.get(y);
-                    if y == 0 {
-                        panic!("Jump can't be 0!")
-                    } else if y < 0 {
-                        if let Some(next_i) = i.checked_sub(y.abs() as usize) {
-                            i = next_i;
-                        } else {
-                            break;
-                        }
-                    } else {
-                        // y > 0
-                        i += y as usize;
-                    }
-                }
-            }
-        }
-    };
-
-    last_frequency
+fn get_first_recovered_frequency(instructions: &[Instruction]) -> i64 {
+    let mut machine = Machine::<_, Sound>::new(instructions);
+    machine.run();
+    machine.state().last_frequency
 }
 
+#[aoc(year = 2017, day = 18, part = 2)]
 pub fn solve_b() {
     let instructions = parse_instructions(&read_content(18));
-    let ans = get_num_times_sent(instructions);
+    let ans = get_num_times_sent(&instructions);
     println!("Solution B: {}", ans);
 }
 
-
-fn get_num_times_sent(instructions: Vec<Instruction>) -> usize {
-    let mut pa = Program::new(0, &instructions);
-    let mut pb = Program::new(1, &instructions);
+/// Runs programs 0 and 1 in turn, each until it waits for a value, and hands over what they sent
+/// until neither has anything left to receive. Returns how many values program 1 sent.
+fn get_num_times_sent(instructions: &[Instruction]) -> usize {
+    let mut pa = new_program(0, instructions);
+    let mut pb = new_program(1, instructions);
 
     loop {
-        pa.run(&mut pb);
-        pb.run(&mut pa);
+        pa.run();
+        pb.state_mut().queue.extend(pa.state_mut().sent.drain(..));
+        pb.run();
+        pa.state_mut().queue.extend(pb.state_mut().sent.drain(..));
 
-        if pa.queue.is_empty() && pb.queue.is_empty() {
-            return pb.send_count;
+        if pa.state().queue.is_empty() && pb.state().queue.is_empty() {
+            return pb.state().send_count;
         }
     }
 }
 
-
-struct Program<'a> {
-    register: Register,
+/// How part B reads the instructions: `snd` sends a value to the other program and `rcv` waits
+/// for one to arrive from it
+#[derive(Debug, Default)]
+struct Duet {
     queue: VecDeque<i64>,
-    i: usize,
-    instructions: &'a Vec<Instruction>,
+    sent: Vec<i64>,
     send_count: usize,
 }
 
-impl<'a> Program<'a> {
-    fn new(id: i64, instructions: &'a Vec<Instruction>) -> Self {
-        let mut register = Register::new();
-        register.set(&InsVal::Char('p'), &InsVal::Integer(id));
-        let queue = VecDeque::new();
+impl machine::Instruction<Duet> for Instruction {
+    type Register = char;
 
-        Program { register, queue, instructions, i: 0, send_count: 0 }
+    fn execute(&self, registers: &mut Registers, duet: &mut Duet) -> Flow {
+        match self {
+            Instruction::Snd(x) => {
+                duet.sent.push(registers.value(x));
+                duet.send_count += 1;
+                Flow::Next
+            }
+            Instruction::Rcv(x) => match duet.queue.pop_front() {
+                Some(v) => {
+                    registers.set(*x, v);
+                    Flow::Next
+                }
+                None => Flow::Wait,
+            },
+            _ => self.execute_common(registers),
+        }
     }
+}
 
-    fn add_to_queue(&mut self, value: i64) {
-        self.queue.push_back(value);
-    }
+fn new_program(id: i64, instructions: &[Instruction]) -> Machine<'_, Instruction, Duet> {
+    let mut program = Machine::new(instructions);
+    program.registers_mut().set('p', id);
+    program
+}
 
-    /// Runs the loop till receive step when it times out
-    fn run(&mut self, other: &mut Program) {
-        while self.i < self.instructions.len() {
-            match &self.instructions[self.i] {
-                Instruction::Snd(x) => {
-                    let v = self.register.get(x);
+/// How the threaded runner, an alternative to the one above, reads the instructions: `snd` sends a value to the next program around
+/// the network and `rcv` blocks until one arrives, halting the program if none ever will
+struct Wire {
+    link: Link<i64>,
+    send_count: usize,
+}
 
-                    other.add_to_queue(v);
-                    self.send_count += 1;
-                    self.i += 1;
-                }
-                Instruction::Rcv(x) => {
-                    if let Some(v) = self.queue.pop_front() {
-                        self.register.set(x, &InsVal::Integer(v));
-                        self.i += 1;
-                    } else {
-                        return;
-                    }
-                }
-                Instruction::Set(x, v) => {
-                    self.register.set(x, v);
-                    self.i += 1;
-                }
-                Instruction::Mul(x, y) => {
-                    let y = self.register.get(y);
-                    let v = self.register.get_mut(x);
-                    *v *= y;
-                    self.i += 1;
-                }
-                Instruction::Add(x, y) => {
-                    let y = self.register.get(y);
-                    let v = self.register.get_mut(x);
-                    *v += y;
-                    self.i += 1;
-                }
-                Instruction::Mod(x, y) => {
-                    let y = self.register.get(y);
-                    let v = self.register.get_mut(x);
-                    *v %= y;
-                    self.i += 1;
-                }
-                Instruction::Jgz(x, y) => {
-                    if self.register.get(x) <= 0 {
-                        self.i += 1;
-                    } else {
-                        let y = self.register.get(y);
-                        if y == 0 {
-                            panic!("Jump can't be 0!")
-                        } else if y < 0 {
-                            if let Some(next_i) = self.i.checked_sub(y.abs() as usize) {
-                                self.i = next_i;
-                            } else {
-                                return;
-                            }
-                        } else {
-                            // y > 0
-                            self.i += y as usize;
-                        }
-                    }
-                }
+impl machine::Instruction<Wire> for Instruction {
+    type Register = char;
+
+    fn execute(&self, registers: &mut Registers, wire: &mut Wire) -> Flow {
+        match self {
+            Instruction::Snd(x) => {
+                wire.link.send((wire.link.id() + 1) % wire.link.size(), registers.value(x));
+                wire.send_count += 1;
+                Flow::Next
             }
-        };
+            Instruction::Rcv(x) => match wire.link.receive() {
+                Some(v) => {
+                    registers.set(*x, v);
+                    Flow::Next
+                }
+                None => Flow::Halt,
+            },
+            _ => self.execute_common(registers),
+        }
     }
 }
 
+/// Runs `programs` copies of the instructions on their own threads, each with its id in register
+/// `p` and sending to the next one around, until they all finish or wait on each other. Returns how
+/// many values each one sent.
+fn get_num_times_sent_threaded(instructions: &[Instruction], programs: usize) -> Vec<usize> {
+    run_network(programs, |link| {
+        let id = link.id() as i64;
+        let mut program = Machine::with_state(instructions, Wire { link, send_count: 0 });
+        program.registers_mut().set('p', id);
+        program.run();
+        program.state().send_count
+    })
+}
+
+/// Runs the puzzle input as a ring of any number of programs on their own threads and prints how
+/// many values each one sent
+#[aoc(year = 2017, day = 18, tool = "duet")]
+pub fn duet(args: &[String]) -> Result<(), String> {
+    let programs = match args.first() {
+        Some(arg) => arg.parse::<usize>().map_err(|e| format!("Could not parse number of programs {}: {}", arg, e))?,
+        None => 2,
+    };
+
+    let instructions = parse_instructions(&read_content(18));
+    for (id, sent) in get_num_times_sent_threaded(&instructions, programs).iter().enumerate() {
+        println!("Program {} sent {} values", id, sent);
+    }
+    Ok(())
+}
 
 #[cfg(test)]
 mod tests {
-    use std::sync::{Arc, RwLock};
-    use std::sync::mpsc::{channel, Receiver, Sender};
-    use std::thread;
+    use crate::inputs::read_content;
 
-    use super::{get_first_recovered_frequency, get_num_times_sent, Instruction, InsVal, parse_instructions, Register};
+    use super::{get_first_recovered_frequency, get_num_times_sent, get_num_times_sent_threaded, parse_instructions};
 
     #[test]
     fn test_get_first_recovered_frequency() {
@@ -321,184 +254,36 @@ rcv b
 rcv c
 rcv d");
 
-        let ans = get_num_times_sent(instructions);
-        assert_eq!(ans, 3);
+        assert_eq!(get_num_times_sent(&instructions), 3);
+        assert_eq!(get_num_times_sent_threaded(&instructions, 2), vec![3, 3]);
     }
 
-    struct Program {
-        sender: Sender<i64>,
-        receiver: Receiver<i64>,
-        instructions: Arc<Vec<Instruction>>,
-        register: Register,
-        send_count: usize,
-        i: usize,
-
-    }
-
-    impl Program {
-        fn new(id: i64, sender: Sender<i64>, receiver: Receiver<i64>, instructions: Arc<Vec<Instruction>>) -> Self {
-            let mut register = Register::new();
-            register.set(&InsVal::Char('p'), &InsVal::Integer(id));
-
-            Program { sender, receiver, instructions, register, send_count: 0, i: 0 }
-        }
-
-        /// Returns true if program can still run but is waiting for inputs, otherwise
-        /// false if program has halted
-        fn run(&mut self) -> bool {
-            while self.i < self.instructions.len() {
-                match &self.instructions[self.i] {
-                    Instruction::Snd(x) => {
-                        let v = self.register.get(x);
-
-                        self.sender.send(v).expect("Could not send to other channel");
-                        self.send_count += 1;
-                        self.i += 1;
-                    }
-                    Instruction::Rcv(x) => {
-                        match self.receiver.try_recv() {
-                            Ok(v) => {
-                                self.register.set(x, &InsVal::Integer(v));
-                                self.i += 1;
-                            }
-                            Err(_) => {
-                                return true;
-                            }
-                        }
-                    }
-                    Instruction::Set(x, v) => {
-                        self.register.set(x, v);
-                        self.i += 1;
-                    }
-                    Instruction::Mul(x, y) => {
-                        let y = self.register.get(y);
-                        let v = self.register.get_mut(x);
-                        *v *= y;
-                        self.i += 1;
-                    }
-                    Instruction::Add(x, y) => {
-                        let y = self.register.get(y);
-                        let v = self.register.get_mut(x);
-                        *v += y;
-                        self.i += 1;
-                    }
-                    Instruction::Mod(x, y) => {
-                        let y = self.register.get(y);
-                        let v = self.register.get_mut(x);
-                        *v %= y;
-                        self.i += 1;
-                    }
-                    Instruction::Jgz(x, y) => {
-                        if self.register.get(x) <= 0 {
-                            self.i += 1;
-                        } else {
-                            let y = self.register.get(y);
-                            if y == 0 {
-                                panic!("Jump can't be 0!")
-                            } else if y < 0 {
-                                if let Some(next_i) = self.i.checked_sub(y.abs() as usize) {
-                                    self.i = next_i;
-                                } else {
-                                    return false;
-                                }
-                            } else {
-                                // y > 0
-                                self.i += y as usize;
-                            }
-                        }
-                    }
-                }
-            };
-            false
-        }
+    #[test]
+    fn test_runners_agree() {
+        let instructions = parse_instructions(&read_content(18));
+        assert_eq!(get_num_times_sent(&instructions), 7366);
+        assert_eq!(get_num_times_sent_threaded(&instructions, 2)[1], 7366);
     }
 
     #[test]
-    fn test_get_num_times_sent_parallel() {
-        // parallel version of solve B
-        
-        let instructions = Arc::new(parse_instructions("set i 31
-set a 1
-mul p 17
-jgz p p
-mul a 2
-add i -1
-jgz i -2
-add a -1
-set i 127
-set p 316
-mul p 8505
-mod p a
-mul p 129749
-add p 12345
-mod p a
-set b p
-mod b 10000
-snd b
-add i -1
-jgz i -9
-jgz a 3
-rcv b
-jgz b -1
-set f 0
-set i 126
+    fn test_get_num_times_sent_threaded() {
+        // program 0 starts a token going around the ring and everyone passes it on one higher,
+        // until it reaches 20 and nobody has anything left to send
+        let instructions = parse_instructions("jgz p 2
+snd 0
 rcv a
-rcv b
-set p a
-mul p -1
-add p b
-jgz p 4
-snd a
-set a b
-jgz 1 3
-snd b
-set f 1
-add i -1
-jgz i -11
+set b a
+mul b -1
+add b 20
+jgz b 2
+jgz 1 -5
+add a 1
 snd a
-jgz f -16
-jgz a -19"));
-        let (sa, ra) = channel();
-        let (sb, rb) = channel();
-        let wa = Arc::new(RwLock::new(false));
-        let wb = Arc::new(RwLock::new(false));
-
-        let mut pa = Program::new(0, sb, ra, instructions.clone());
-        let mut pb = Program::new(1, sa, rb, instructions.clone());
-
-        let thread_handles = Vec::from([
-            {
-                let wa = wa.clone();
-                let wb = wb.clone();
-
-                thread::spawn(move || {
-                    while pa.run() {
-                        *wa.write().unwrap() = true;
-                        if *wa.read().unwrap() && *wb.read().unwrap() { break; } else { *wa.write().unwrap() = false; }
-                    }
-                    *wa.write().unwrap() = true;
-                    pa.send_count
-                })
-            },
-            {
-                thread::spawn(move || {
-                    while pb.run() {
-                        *wb.write().unwrap() = true;
-                        if *wa.read().unwrap() && *wb.read().unwrap() { break; } else { *wb.write().unwrap() = false; }
-                    }
-                    *wb.write().unwrap() = true;
-                    pb.send_count
-                })
-            }
-        ]);
-
-        // only need the last value
-        let ans = thread_handles
-            .into_iter()
-            .map(|th| th.join().unwrap())
-            .last()
-            .unwrap();
+jgz 1 -8");
 
-        assert_eq!(ans, 7366);
+        assert_eq!(get_num_times_sent_threaded(&instructions, 2), vec![11, 10]);
+        assert_eq!(get_num_times_sent_threaded(&instructions, 3), vec![7, 7, 7]);
+        assert_eq!(get_num_times_sent_threaded(&instructions, 4), vec![6, 5, 5, 5]);
+        assert_eq!(get_num_times_sent_threaded(&instructions, 1), vec![21]);
     }
-}
\ No newline at end of file
+}
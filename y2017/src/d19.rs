@@ -1,5 +1,7 @@
 use std::collections::{HashMap, HashSet};
 
+use aoc_macros::aoc;
+
 use crate::inputs::read_content;
 
 #[derive(Eq, PartialEq, Hash, Debug, Copy, Clone)]
@@ -104,6 +106,7 @@ fn run_through_maze() -> (Vec<char>, usize) {
     (order, count)
 }
 
+#[aoc(year = 2017, day = 19, part = 1)]
 pub fn solve_a() {
     let (order, _) = run_through_maze();
     let ans = order.iter().collect::<String>();
@@ -112,6 +115,7 @@ pub fn solve_a() {
     println!("Solution A: {}", ans);
 }
 
+#[aoc(year = 2017, day = 19, part = 2)]
 pub fn solve_b() {
     let (_, ans) = run_through_maze();
     
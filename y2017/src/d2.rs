@@ -1,3 +1,5 @@
+use aoc_macros::aoc;
+
 const PUZZLE_INPUT: &str = "1640	590	93	958	73	1263	1405	1363	737	712	1501	390	68	1554	959	79
 4209	128	131	2379	2568	2784	2133	145	3618	1274	3875	158	1506	3455	1621	3799
 206	1951	2502	2697	2997	74	76	78	1534	81	2775	2059	3026	77	2600	3067
@@ -26,6 +28,7 @@ fn parse_input(input: &str) -> Vec<Vec<i32>> {
         .collect()
 }
 
+#[aoc(year = 2017, day = 2, part = 1)]
 pub fn solve_a() {
     let inputs = parse_input(PUZZLE_INPUT);
 
@@ -40,6 +43,7 @@ pub fn solve_a() {
     println!("Solution A: {}", ans);
 }
 
+#[aoc(year = 2017, day = 2, part = 2)]
 pub fn solve_b() {
     let inputs = parse_input(PUZZLE_INPUT);
 
@@ -1,10 +1,11 @@
 use std::collections::HashMap;
 
-use regex::{Captures, Regex};
+use aoc_macros::{aoc, AocParse};
 
 use crate::inputs::read_content;
 
-#[derive(Eq, PartialEq)]
+#[derive(AocParse, Eq, PartialEq)]
+#[aoc(pattern = "{x},{y},{z}")]
 struct Vector {
     x: i64,
     y: i64,
@@ -19,25 +20,15 @@ impl Vector {
         self.z += other.z;
     }
 
-    fn from_captures(cap: &Captures, prefix: char) -> Self {
-        let get_value = |c: char| -> i64{
-            let name = [prefix, c].iter().collect::<String>();
-            cap.name(&name)
-                .unwrap()
-                .as_str()
-                .parse()
-                .expect(format!("Could not parse: {}", name).as_str())
-        };
-
-        Vector { x: get_value('x'), y: get_value('y'), z: get_value('z') }
-    }
-
     fn key(&self) -> [i64; 3] {
         [self.x, self.y, self.z]
     }
 }
 
+#[derive(AocParse)]
+#[aoc(pattern = "p=<{p}>, v=<{v}>, a=<{a}>")]
 struct Particle {
+    #[aoc(skip)]
     id: usize,
     p: Vector,
     v: Vector,
@@ -45,10 +36,6 @@ struct Particle {
 }
 
 impl Particle {
-    fn new(id: usize, position: Vector, velocity: Vector, acceleration: Vector) -> Self {
-        Particle { id, p: position, v: velocity, a: acceleration }
-    }
-
     fn update(&mut self) {
         self.v.add(&self.a);
         self.p.add(&self.v);
@@ -60,24 +47,17 @@ impl Particle {
 }
 
 fn get_particles() -> Vec<Particle> {
-    let re = Regex::new(r"p=<(?<px>-?\d+),(?<py>-?\d+),(?<pz>-?\d+)>, v=<(?<vx>-?\d+),(?<vy>-?\d+),(?<vz>-?\d+)>, a=<(?<ax>-?\d+),(?<ay>-?\d+),(?<az>-?\d+)>")
-        .expect("Could not form regex particle line regex");
-
     read_content(20).lines()
         .enumerate()
         .map(|(id, line)| {
-            let cap = re.captures(line).expect(format!("Could not parse line: {}", line).as_str());
-
-            Particle::new(
-                id,
-                Vector::from_captures(&cap, 'p'),
-                Vector::from_captures(&cap, 'v'),
-                Vector::from_captures(&cap, 'a'),
-            )
+            let particle = line.parse::<Particle>().unwrap_or_else(|e| panic!("{}", e));
+
+            Particle { id, ..particle }
         })
         .collect()
 }
 
+#[aoc(year = 2017, day = 20, part = 1)]
 pub fn solve_a() {
     let mut particles = get_particles();
     let ans = find_closest_particle(&mut particles);
@@ -105,6 +85,7 @@ fn find_closest_particle(particles: &mut Vec<Particle>) -> usize {
     min_id
 }
 
+#[aoc(year = 2017, day = 20, part = 2)]
 pub fn solve_b() {
     let particles = get_particles();
     let ans = count_remaining_particles_after_collision(particles);
@@ -2,6 +2,7 @@ use std::collections::{HashMap, HashSet};
 use std::fmt::{Debug, Display, Formatter};
 use std::hash::{Hash, Hasher};
 
+use aoc_macros::aoc;
 use itertools::{Itertools, izip};
 
 use crate::inputs::read_content;
@@ -249,6 +250,7 @@ impl Rulebook {
 }
 
 
+#[aoc(year = 2017, day = 21, part = 1)]
 pub fn solve_a() {
     let rulebook = Rulebook::new(&read_content(21));
 
@@ -262,6 +264,7 @@ pub fn solve_a() {
     println!("Solution A: {}", ans);
 }
 
+#[aoc(year = 2017, day = 21, part = 2)]
 pub fn solve_b() {
     let rulebook = Rulebook::new(&read_content(21));
 
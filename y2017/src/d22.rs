@@ -1,43 +1,20 @@
-use std::collections::HashMap;
+use aoc_macros::{aoc, AocTile};
+use aoc_utils::{Grid, Point2, SparseGrid};
 
 use crate::inputs::read_content;
 
-#[derive(Clone, Hash, Eq, PartialEq, Debug)]
-struct Point {
-    x: i64,
-    y: i64,
-}
-
-impl Point {
-    fn move_point(&mut self, face: &Facing) {
-        match face {
-            Facing::Up => { self.y -= 1; }
-            Facing::Down => { self.y += 1; }
-            Facing::Left => { self.x -= 1; }
-            Facing::Right => { self.x += 1 }
-        }
-    }
-}
-
-#[derive(Debug)]
+#[derive(Debug, Clone, AocTile)]
 enum GridState {
+    #[tile('.')]
     Clean,
+    #[tile('#')]
     Infected,
+    #[tile('W')]
     Weakened,
+    #[tile('F')]
     Flagged,
 }
 
-
-impl GridState {
-    fn new(c: char) -> Self {
-        match c {
-            '.' => GridState::Clean,
-            '#' => GridState::Infected,
-            _ => panic!("Invalid GridState character: {}", c)
-        }
-    }
-}
-
 #[derive(Debug)]
 enum Facing {
     Up,
@@ -46,11 +23,22 @@ enum Facing {
     Right,
 }
 
+impl Facing {
+    fn step(&self) -> Point2 {
+        match self {
+            Facing::Up => Point2::new([0, -1]),
+            Facing::Down => Point2::new([0, 1]),
+            Facing::Left => Point2::new([-1, 0]),
+            Facing::Right => Point2::new([1, 0]),
+        }
+    }
+}
+
 trait Virus {
     fn new() -> Self;
-    fn point(&self) -> Point;
+    fn point(&self) -> Point2;
 
-    fn set_point(&mut self, point: Point);
+    fn set_point(&mut self, point: Point2);
 
     fn single_step(&mut self, state: &mut GridState) -> usize;
     fn turn(&mut self, turn: Turn);
@@ -66,21 +54,21 @@ enum Turn {
 }
 
 struct VirusA {
-    p: Point,
+    p: Point2,
     face: Facing,
 }
 
 
 impl Virus for VirusA {
     fn new() -> Self {
-        VirusA { p: Point { x: 0, y: 0 }, face: Facing::Up }
+        VirusA { p: Point2::ORIGIN, face: Facing::Up }
     }
 
-    fn point(&self) -> Point {
-        self.p.clone()
+    fn point(&self) -> Point2 {
+        self.p
     }
 
-    fn set_point(&mut self, point: Point) {
+    fn set_point(&mut self, point: Point2) {
         self.p = point;
     }
 
@@ -124,25 +112,25 @@ impl Virus for VirusA {
     }
 
     fn forward(&mut self) {
-        self.p.move_point(&self.face);
+        self.p += self.face.step();
     }
 }
 
 struct VirusB {
-    p: Point,
+    p: Point2,
     face: Facing,
 }
 
 impl Virus for VirusB {
     fn new() -> Self {
-        VirusB { p: Point { x: 0, y: 0 }, face: Facing::Up }
+        VirusB { p: Point2::ORIGIN, face: Facing::Up }
     }
 
-    fn point(&self) -> Point {
-        self.p.clone()
+    fn point(&self) -> Point2 {
+        self.p
     }
 
-    fn set_point(&mut self, point: Point) {
+    fn set_point(&mut self, point: Point2) {
         self.p = point;
     }
 
@@ -201,37 +189,30 @@ impl Virus for VirusB {
     }
 
     fn forward(&mut self) {
-        self.p.move_point(&self.face);
+        self.p += self.face.step();
     }
 }
 
 struct InfiniteGrid<V: Virus> {
-    grid: HashMap<Point, GridState>,
+    grid: SparseGrid<GridState>,
     virus: V,
     infections: usize,
 }
 
 impl<V: Virus> InfiniteGrid<V> {
     fn new(input: &str, virus: V) -> Self {
-        let mut grid = HashMap::new();
-        let input = input.trim();
-        let n = input.lines().count() as i64;
+        let grid: Grid<GridState> = input.trim().parse().unwrap_or_else(|e| panic!("{}", e));
 
         let mut virus = virus;
-        virus.set_point(Point { x: n / 2, y: n / 2 });
+        virus.set_point(Point2::new([grid.width() as i64 / 2, grid.height() as i64 / 2]));
 
-        for (y, line) in input.lines().enumerate() {
-            for (x, c) in line.chars().enumerate() {
-                grid.insert(Point { x: x as i64, y: y as i64 }, GridState::new(c));
-            }
-        }
+        let grid = SparseGrid::from_grid(&grid, GridState::Clean);
 
         InfiniteGrid { virus, grid, infections: 0 }
     }
 
     fn single_step(&mut self) {
-        let state = self.grid.entry(self.virus.point())
-            .or_insert(GridState::Clean);
+        let state = self.grid.get_mut(self.virus.point());
 
         self.infections += self.virus.single_step(state);
     }
@@ -244,6 +225,7 @@ impl<V: Virus> InfiniteGrid<V> {
 }
 
 
+#[aoc(year = 2017, day = 22, part = 1)]
 pub fn solve_a() {
     let mut grid = InfiniteGrid::new(&read_content(22), VirusA::new());
     grid.run(10000);
@@ -251,6 +233,7 @@ pub fn solve_a() {
     println!("Solution A: {}", grid.infections);
 }
 
+#[aoc(year = 2017, day = 22, part = 2)]
 pub fn solve_b() {
     let mut grid = InfiniteGrid::new(&read_content(22), VirusB::new());
     grid.run(10_000_000);
@@ -261,47 +244,7 @@ pub fn solve_b() {
 
 #[cfg(test)]
 mod tests {
-    use super::{GridState, InfiniteGrid, Point, Virus, VirusA, VirusB};
-
-    impl GridState {
-        fn to_char(&self) -> char {
-            match self {
-                GridState::Clean => '.',
-                GridState::Infected => '#',
-                GridState::Flagged => 'F',
-                GridState::Weakened => 'W'
-            }
-        }
-    }
-
-    impl<V: Virus> InfiniteGrid<V> {
-        fn print_grid(&self) {
-            let mut min_x = 0;
-            let mut max_x = 0;
-            let mut min_y = 0;
-            let mut max_y = 0;
-
-            for &Point { x, y } in self.grid.keys() {
-                if x < min_x { min_x = x };
-                if x > max_x { max_x = x };
-                if y < min_y { min_y = y };
-                if y > max_y { max_y = y };
-            }
-
-            let mut output = Vec::new();
-            for y in min_y..=max_y {
-                let mut row = Vec::new();
-                for x in min_x..=max_x {
-                    row.push(match self.grid.get(&Point { x, y }) {
-                        Some(s) => s.to_char(),
-                        None => '.'
-                    });
-                }
-                output.push(row.iter().collect::<String>());
-            }
-            println!("{}\n", output.join("\n"));
-        }
-    }
+    use super::{InfiniteGrid, Virus, VirusA, VirusB};
 
     #[test]
     fn test_virus_a() {
@@ -317,7 +260,7 @@ mod tests {
             grid.run(n);
             
             if n < 100 {
-                grid.print_grid();
+                println!("{}\n", grid.grid);
             }
             assert_eq!(grid.infections, exp);
         }
@@ -1,41 +1,32 @@
-use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::ops::{Range, RangeInclusive};
 
+use aoc_macros::aoc;
+use aoc_utils::machine::{self, Flow, Machine, Registers, Value};
 use itertools::Itertools;
 
 use crate::inputs::read_content;
 
-enum Arg {
-    Char(char),
-    Integer(i64),
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Instruction {
+    Set(char, Value),
+    Sub(char, Value),
+    Mul(char, Value),
+    Jnz(Value, Value),
 }
 
-impl Arg {
-    fn new(x: &str) -> Self {
-        if x.len() == 1 {
-            let v = x.chars().next().unwrap();
-            if v.is_alphabetic() {
-                return Arg::Char(v);
-            }
-        }
-
-        Arg::Integer(x.parse().unwrap())
-    }
-
-    fn get_value(&self, register: &HashMap<char, i64>) -> i64 {
+impl Display for Instruction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
-            Arg::Char(c) => *register.get(c).unwrap(),
-            Arg::Integer(v) => *v
+            Instruction::Set(c, arg) => write!(f, "set {} {}", c, arg),
+            Instruction::Sub(c, arg) => write!(f, "sub {} {}", c, arg),
+            Instruction::Mul(c, arg) => write!(f, "mul {} {}", c, arg),
+            Instruction::Jnz(a1, a2) => write!(f, "jnz {} {}", a1, a2),
         }
     }
 }
 
-enum Instruction {
-    Set(char, Arg),
-    Sub(char, Arg),
-    Mul(char, Arg),
-    Jnz(Arg, Arg),
-}
-
 fn form_instructions(input: String) -> Vec<Instruction> {
     fn get_char(x: &str) -> char {
         assert_eq!(x.len(), 1);
@@ -50,100 +41,343 @@ fn form_instructions(input: String) -> Vec<Instruction> {
                 .unwrap();
 
 
+            let value = |x: &str| x.parse::<Value>().unwrap_or_else(|e| panic!("{}", e));
             match cmd {
-                "set" => Instruction::Set(get_char(a1), Arg::new(a2)),
-                "sub" => Instruction::Sub(get_char(a1), Arg::new(a2)),
-                "mul" => Instruction::Mul(get_char(a1), Arg::new(a2)),
-                "jnz" => Instruction::Jnz(Arg::new(a1), Arg::new(a2)),
+                "set" => Instruction::Set(get_char(a1), value(a2)),
+                "sub" => Instruction::Sub(get_char(a1), value(a2)),
+                "mul" => Instruction::Mul(get_char(a1), value(a2)),
+                "jnz" => Instruction::Jnz(value(a1), value(a2)),
                 _ => panic!("Invalid command: {}", cmd)
             }
         })
         .collect()
 }
 
-fn run_instructions(register: &mut HashMap<char, i64>,
-                    instructions: &Vec<Instruction>) -> usize {
-    let mut i = 0;
+/// The state counts the `mul` instructions run
+impl machine::Instruction<usize> for Instruction {
+    type Register = char;
 
-    let mut count = 0;
-    while i < instructions.len() {
-        let ins = &instructions[i];
-        match ins {
-            Instruction::Set(c, arg) |
-            Instruction::Sub(c, arg) |
+    fn execute(&self, registers: &mut Registers, mul_count: &mut usize) -> Flow {
+        match self {
+            Instruction::Set(c, arg) => registers.set(*c, registers.value(arg)),
+            Instruction::Sub(c, arg) => *registers.get_mut(*c) -= registers.value(arg),
             Instruction::Mul(c, arg) => {
-                let value = arg.get_value(&register);
-                let err = format!("Expect a char but got '{}'. {:?}", c, register);
-                let src = register.get_mut(c).expect(&err);
-
-                match ins {
-                    Instruction::Set(_, _) => { *src = value; }
-                    Instruction::Sub(_, _) => { *src -= value; }
-                    Instruction::Mul(_, _) => {
-                        *src *= value;
-                        count += 1;
-                    }
-                    Instruction::Jnz(_, _) => {}
-                }
-
-                i += 1;
-            }
-            Instruction::Jnz(a1, a2) => {
-                if a1.get_value(&register) == 0 {
-                    i += 1;
-                } else {
-                    let v = a2.get_value(&register);
-                    if v < 0 {
-                        let v = v.abs() as usize;
-                        i -= v;
-                    } else {
-                        i += v as usize;
-                    }
-                }
+                *registers.get_mut(*c) *= registers.value(arg);
+                *mul_count += 1;
             }
+            Instruction::Jnz(a1, a2) if registers.value(a1) != 0 => return Flow::Jump(registers.value(a2)),
+            Instruction::Jnz(..) => {}
         }
+        Flow::Next
     }
+}
 
-    count
+/// Runs the instructions till they jump out, returning how many times `mul` ran
+fn run_instructions(instructions: &[Instruction]) -> usize {
+    let mut machine = Machine::new(instructions);
+    machine.run();
+    *machine.state()
 }
 
+#[aoc(year = 2017, day = 23, part = 1)]
 pub fn solve_a() {
     let instructions = form_instructions(read_content(23));
+    let ans = run_instructions(&instructions);
+    println!("Solution A: {}", ans);
+}
 
-    let mut register = HashMap::new();
-    for c in 'a'..='h' {
-        register.insert(c, 0);
+/// Where the instruction at `i` jumps to, if it jumps by a literal offset
+fn jump_target(i: usize, instruction: &Instruction) -> Option<i64> {
+    match instruction {
+        Instruction::Jnz(_, Value::Literal(offset)) => Some(i as i64 + offset),
+        _ => None,
     }
+}
 
-    let ans = run_instructions(&mut register, &instructions);
-    println!("Solution A: {}", ans);
+/// Splits the program into basic blocks, which are only ever entered at the top and left at the
+/// bottom. A jump by a register could land anywhere, so those programs aren't split any further.
+fn basic_blocks(program: &[Instruction]) -> Vec<Range<usize>> {
+    let mut leaders = vec![false; program.len() + 1];
+    leaders[0] = true;
+    leaders[program.len()] = true;
+    for (i, instruction) in program.iter().enumerate() {
+        if let Instruction::Jnz(..) = instruction {
+            leaders[i + 1] = true;
+        }
+        if let Some(target) = jump_target(i, instruction).filter(|t| (0..program.len() as i64).contains(t)) {
+            leaders[target as usize] = true;
+        }
+    }
+
+    leaders.iter()
+           .positions(|&leader| leader)
+           .tuple_windows()
+           .map(|(start, end)| start..end)
+           .collect()
 }
 
-pub fn solve_b() {
-    let mut b = 81 * 100 + 100_000;
-    let c = b + 17_000;
-    let d = 2;
-    let mut g = -1;  // any number
-    let mut h = 0;
-    
-    while g != 0 {
-        let mut f = 1;
-    
-        for i in d.. {
-            if i * i >= b { break; }
-            if b % i == 0 {
-                f = 0;
-                break;
+/// The loops closed by jumping back a literal offset, innermost first
+fn find_loops(program: &[Instruction]) -> Vec<RangeInclusive<usize>> {
+    program.iter()
+           .enumerate()
+           .filter_map(|(i, instruction)| match jump_target(i, instruction) {
+               Some(target) if (0..i as i64).contains(&target) => Some(target as usize..=i),
+               _ => None,
+           })
+           .sorted_by_key(|body| body.end() - body.start())
+           .collect()
+}
+
+/// What a recognised loop works out in one go. Each only stands in for its loop when the loop
+/// would run as expected, e.g. counting up to a bound it will actually reach.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Macro {
+    /// `target -= value * counter` and `counter = 0`, from subtracting `value` once per count
+    Multiply { target: char, value: Value, counter: char },
+    /// `flag = 0` if `d * x == b` for any `x` from `e` up to `b`, leaving `e = b` and `temp = 0`
+    HasFactor { d: char, e: char, b: char, flag: char, temp: char },
+    /// `flag = 0` if `x * y == b` for any `x` from `d` up to `b` and `y` from `e_start` up to
+    /// `b`, leaving `d = e = b` and `temp = 0`
+    HasFactorPair { d: char, e: char, e_start: i64, b: char, flag: char, temp: char },
+}
+
+impl Macro {
+    /// Runs the macro, or returns false without touching anything if it can't stand in for its
+    /// loop with these registers
+    fn execute(&self, registers: &mut Registers) -> bool {
+        match *self {
+            Macro::Multiply { target, value, counter } => {
+                let count = registers.get(&counter);
+                if count <= 0 {
+                    return false;
+                }
+                *registers.get_mut(target) -= registers.value(&value) * count;
+                registers.set(counter, 0);
+            }
+            Macro::HasFactor { d, e, b, flag, temp } => {
+                let (dv, ev, bv) = (registers.get(&d), registers.get(&e), registers.get(&b));
+                if dv < 1 || ev < 1 || ev >= bv {
+                    return false;
+                }
+                if has_factor_pair(bv, dv..dv + 1, ev..bv) {
+                    registers.set(flag, 0);
+                }
+                registers.set(e, bv);
+                registers.set(temp, 0);
+            }
+            Macro::HasFactorPair { d, e, e_start, b, flag, temp } => {
+                let (dv, bv) = (registers.get(&d), registers.get(&b));
+                if dv < 1 || dv >= bv || e_start < 1 || e_start >= bv {
+                    return false;
+                }
+                if has_factor_pair(bv, dv..bv, e_start..bv) {
+                    registers.set(flag, 0);
+                }
+                registers.set(d, bv);
+                registers.set(e, bv);
+                registers.set(temp, 0);
             }
         }
-    
-        if f == 0 {
-            h += 1;
+        true
+    }
+}
+
+impl Display for Macro {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Macro::Multiply { target, value, counter } =>
+                write!(f, "{} -= {} * {}; {} = 0", target, value, counter, counter),
+            Macro::HasFactor { d, e, b, flag, temp } =>
+                write!(f, "if {} * x == {} for some x in {}..{} {{ {} = 0 }}; {} = {}; {} = 0", d, b, e, b, flag, e, b, temp),
+            Macro::HasFactorPair { d, e, e_start, b, flag, temp } =>
+                write!(f, "if x * y == {} for some x in {}..{}, y in {}..{} {{ {} = 0 }}; {} = {} = {}; {} = 0",
+                       b, d, b, e_start, b, flag, d, e, b, temp),
         }
-    
-        g = b - c;
-        b += 17;
     }
+}
 
-    println!("Solution B: {}", h);
+/// Whether `b = x * y` for some `x` in `xs` and `y` in `ys`, all positive
+fn has_factor_pair(b: i64, xs: Range<i64>, ys: Range<i64>) -> bool {
+    (1..).take_while(|x| x * x <= b)
+         .filter(|x| b % x == 0)
+         .any(|x| (xs.contains(&x) && ys.contains(&(b / x))) || (xs.contains(&(b / x)) && ys.contains(&x)))
+}
+
+/// The optimised program. A recognised loop is fused into its first instruction, which jumps past
+/// the loop and leaves the rest of it in place for anything that jumps into the middle.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Op {
+    Ins(Instruction),
+    /// A loop of `len` instructions, which runs `fallback` instead when the macro doesn't apply
+    Fused { op: Macro, len: usize, fallback: Box<Op> },
+}
+
+impl machine::Instruction<usize> for Op {
+    type Register = char;
+
+    /// Fused loops don't count the `mul` instructions they skip
+    fn execute(&self, registers: &mut Registers, mul_count: &mut usize) -> Flow {
+        match self {
+            Op::Ins(instruction) => instruction.execute(registers, mul_count),
+            Op::Fused { op, len, .. } if op.execute(registers) => Flow::Jump(*len as i64),
+            Op::Fused { fallback, .. } => fallback.execute(registers, mul_count),
+        }
+    }
+}
+
+/// Matches a loop body against the idioms the optimiser knows
+fn recognise(body: &[Op]) -> Option<Macro> {
+    use Instruction::*;
+    use Op::Ins;
+    use Value::*;
+
+    match body {
+        // counter -= 1 and target -= value, in either order, until the counter is 0
+        [Ins(Sub(target, value)), Ins(Sub(counter, Literal(1))), Ins(Jnz(Register(c), _))] |
+        [Ins(Sub(counter, Literal(1))), Ins(Sub(target, value)), Ins(Jnz(Register(c), _))]
+        if c == counter && target != counter && ![Register(*target), Register(*counter)].contains(value) => {
+            Some(Macro::Multiply { target: *target, value: *value, counter: *counter })
+        }
+
+        // do { if d * e == b { flag = 0 } e += 1 } while e != b
+        [Ins(Set(g1, Register(d))), Ins(Mul(g2, Register(e1))), Ins(Sub(g3, Register(b1))), Ins(Jnz(Register(g4), Literal(2))),
+         Ins(Set(flag, Literal(0))), Ins(Sub(e2, Literal(-1))), Ins(Set(g5, Register(e3))), Ins(Sub(g6, Register(b2))), Ins(Jnz(Register(g7), _))]
+        if [g2, g3, g4, g5, g6, g7].iter().all(|g| g == &g1) && e1 == e2 && e1 == e3 && b1 == b2
+            && [g1, d, e1, b1, flag].into_iter().all_unique() => {
+            Some(Macro::HasFactor { d: *d, e: *e1, b: *b1, flag: *flag, temp: *g1 })
+        }
+
+        // do { e = e_start; <HasFactor>; d += 1 } while d != b
+        [Ins(Set(e, Literal(e_start))), Op::Fused { op: Macro::HasFactor { d, e: e1, b, flag, temp }, len, .. }, ..,
+         Ins(Sub(d1, Literal(-1))), Ins(Set(g1, Register(d2))), Ins(Sub(g2, Register(b1))), Ins(Jnz(Register(g3), _))]
+        if body.len() == len + 5 && e == e1 && d == d1 && d == d2 && b == b1 && [g1, g2, g3].iter().all(|g| g == &temp) => {
+            Some(Macro::HasFactorPair { d: *d, e: *e, e_start: *e_start, b: *b, flag: *flag, temp: *temp })
+        }
+
+        _ => None,
+    }
+}
+
+/// Fuses every loop the optimiser recognises, innermost first so that outer loops can be
+/// recognised in terms of the inner ones
+fn optimise(program: &[Instruction]) -> Vec<Op> {
+    let mut ops = program.iter().map(|&instruction| Op::Ins(instruction)).collect::<Vec<_>>();
+    for body in find_loops(program) {
+        if let Some(op) = recognise(&ops[body.clone()]) {
+            let start = *body.start();
+            let fallback = Box::new(ops[start].clone());
+            ops[start] = Op::Fused { op, len: body.count(), fallback };
+        }
+    }
+    ops
+}
+
+/// Prints the optimised program a basic block at a time, with fused loops in place of the
+/// instructions they stand in for
+fn decompile_listing(program: &[Instruction]) -> String {
+    let ops = optimise(program);
+    let mut lines = Vec::new();
+    let mut next = 0;
+    for block in basic_blocks(program) {
+        if block.start < next {
+            continue;
+        }
+        lines.push(format!("L{}:", block.start));
+        for i in block.clone() {
+            if i < next {
+                continue;
+            }
+            match &ops[i] {
+                Op::Ins(instruction) => {
+                    let target = jump_target(i, instruction).map(|t| format!("  -> L{}", t)).unwrap_or_default();
+                    lines.push(format!("    {:<24}{}", instruction.to_string(), target).trim_end().to_string());
+                    next = i + 1;
+                }
+                Op::Fused { op, len, .. } => {
+                    lines.push(format!("    {}  (lines {}..={})", op, i, i + len - 1));
+                    next = i + len;
+                }
+            }
+        }
+    }
+    lines.join("\n")
+}
+
+/// Prints the puzzle input, or the program in the given file, with the loops the optimiser
+/// recognises replaced by what they work out
+#[aoc(year = 2017, day = 23, tool = "decompile")]
+pub fn decompile(args: &[String]) -> Result<(), String> {
+    let input = match args.first() {
+        Some(path) => fs::read_to_string(path).map_err(|e| format!("Could not read {}: {}", path, e))?,
+        None => read_content(23),
+    };
+    println!("{}", decompile_listing(&form_instructions(input)));
+    Ok(())
+}
+
+#[aoc(year = 2017, day = 23, part = 2)]
+pub fn solve_b() {
+    let ops = optimise(&form_instructions(read_content(23)));
+    let mut machine = Machine::new(&ops);
+    machine.registers_mut().set('a', 1);
+    machine.run();
+
+    println!("Solution B: {}", machine.registers().get(&'h'));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_basic_blocks_and_loops() {
+        let program = form_instructions("set c 3
+sub a -2
+sub c 1
+jnz c -2
+jnz a 2
+set b 1".to_string());
+
+        assert_eq!(basic_blocks(&program), vec![0..1, 1..4, 4..5, 5..6]);
+        assert_eq!(find_loops(&program), vec![1..=3]);
+        assert_eq!(find_loops(&form_instructions(read_content(23))), vec![11..=19, 10..=23, 8..=31]);
+    }
+
+    #[test]
+    fn test_multiply() {
+        let program = form_instructions("set c 5
+set b 3
+sub a b
+sub c 1
+jnz c -2".to_string());
+        let ops = optimise(&program);
+        assert!(matches!(ops[2], Op::Fused { op: Macro::Multiply { target: 'a', value: Value::Register('b'), counter: 'c' }, len: 3, .. }));
+
+        let mut machine = Machine::new(&ops);
+        machine.run();
+        assert_eq!((machine.registers().get(&'a'), machine.registers().get(&'c')), (-15, 0));
+        assert_eq!(machine.steps(), 3);
+    }
+
+    #[test]
+    fn test_optimise() {
+        let program = form_instructions(read_content(23));
+        let ops = optimise(&program);
+        assert!(matches!(ops[10], Op::Fused { op: Macro::HasFactorPair { e_start: 2, .. }, len: 14, .. }));
+
+        // with a = 0 the program checks b alone, slowly enough to compare for small b (but b = 2
+        // counts up from 2 until it overflows)
+        for b in 3..50 {
+            let program = form_instructions(read_content(23).replace("set b 81", &format!("set b {}", b)));
+            let ops = optimise(&program);
+
+            let mut slow = Machine::new(&program);
+            slow.run();
+            let mut fast = Machine::new(&ops);
+            fast.run();
+
+            assert_eq!(fast.registers(), slow.registers(), "b = {}", b);
+            assert!(fast.steps() < 50);
+        }
+    }
 }
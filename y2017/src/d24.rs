@@ -1,6 +1,7 @@
 use std::collections::{HashMap, HashSet, VecDeque};
 use std::hash::{Hash, Hasher};
 
+use aoc_macros::aoc;
 use once_cell::sync::OnceCell;
 
 use crate::inputs::read_content;
@@ -121,12 +122,14 @@ fn find_strongest_bridge(input: &str) -> (usize, usize) {
 // cache results
 static SOLUTION: OnceCell<(usize, usize)> = OnceCell::new();
 
+#[aoc(year = 2017, day = 24, part = 1)]
 pub fn solve_a() {
     let (ans, _) = *SOLUTION.get_or_init(|| find_strongest_bridge(&read_content(24)));
 
     println!("Solution A: {}", ans);
 }
 
+#[aoc(year = 2017, day = 24, part = 2)]
 pub fn solve_b() {
     let (_, ans) = *SOLUTION.get_or_init(|| find_strongest_bridge(&read_content(24)));
     println!("Solution B: {}", ans);
@@ -1,8 +1,199 @@
-use std::collections::HashMap;
+use std::str::FromStr;
 
-/**
-Begin in state A.
-Perform a diagnostic checksum after 12368930 steps.
+use aoc_macros::aoc;
+use regex::Regex;
+
+use crate::inputs::read_content;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Move {
+    Left,
+    Right,
+}
+
+/// What the machine does when it reads a value in some state
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Action {
+    write: bool,
+    step: Move,
+    next: usize,
+}
+
+/// A parsed blueprint. States are numbered in the order they are described, and every state has an
+/// action for reading a 0 and for reading a 1.
+#[derive(Debug, PartialEq, Eq)]
+struct Blueprint {
+    names: Vec<String>,
+    start: usize,
+    steps: usize,
+    actions: Vec<[Action; 2]>,
+}
+
+impl FromStr for Blueprint {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let header_re = Regex::new(r"^Begin in state (\w+)\.\s+Perform a diagnostic checksum after (\d+) steps?\.").unwrap();
+        let state_re = Regex::new(r"^In state (\w+):").unwrap();
+        let action_re = Regex::new(r"If the current value is ([01]):\s+- Write the value ([01])\.\s+- Move one slot to the (left|right)\.\s+- Continue with state (\w+)\.").unwrap();
+
+        let s = s.replace("\r\n", "\n");
+        let mut blocks = s.trim().split("\n\n");
+
+        let header = blocks.next().unwrap_or_default();
+        let caps = header_re.captures(header).ok_or(format!("Could not parse header: {}", header))?;
+        let start = caps[1].to_string();
+        let steps = caps[2].parse::<usize>().map_err(|e| format!("Could not parse steps: {}", e))?;
+
+        // the actions name the next state, which may not have been described yet
+        let mut states = Vec::new();
+        for block in blocks {
+            let name = state_re.captures(block.trim())
+                               .ok_or(format!("Could not parse state: {}", block))?[1]
+                .to_string();
+
+            let mut actions = [None, None];
+            for caps in action_re.captures_iter(block) {
+                let current = (&caps[1] == "1") as usize;
+                if actions[current].is_some() {
+                    return Err(format!("state {} reads {} twice", name, current));
+                }
+                let step = if &caps[3] == "left" { Move::Left } else { Move::Right };
+                actions[current] = Some((&caps[2] == "1", step, caps[4].to_string()));
+            }
+
+            match actions {
+                [Some(zero), Some(one)] => states.push((name, [zero, one])),
+                _ => return Err(format!("state {} needs an action for both 0 and 1", name)),
+            }
+        }
+
+        let names = states.iter().map(|(name, _)| name.clone()).collect::<Vec<_>>();
+        let index = |name: &str| names.iter()
+                                      .position(|n| n == name)
+                                      .ok_or(format!("state {} is never described", name));
+
+        let actions = states.iter()
+                            .map(|(_, actions)| {
+                                let [zero, one] = actions.clone().map(|(write, step, next)| {
+                                    index(&next).map(|next| Action { write, step, next })
+                                });
+                                Ok([zero?, one?])
+                            })
+                            .collect::<Result<Vec<_>, String>>()?;
+
+        Ok(Blueprint { start: index(&start)?, names, steps, actions })
+    }
+}
+
+/// An unbounded tape of bits that starts out all 0. Cells right of the start (and the start itself)
+/// are packed into `right` and cells left of it into `left`, so it grows in either direction.
+#[derive(Debug, Default)]
+struct Tape {
+    left: Vec<u64>,
+    right: Vec<u64>,
+}
+
+impl Tape {
+    fn locate(position: i64) -> (bool, usize, u64) {
+        let (is_left, offset) = if position < 0 {
+            (true, (-position - 1) as usize)
+        } else {
+            (false, position as usize)
+        };
+        (is_left, offset / 64, 1 << (offset % 64))
+    }
+
+    fn get(&self, position: i64) -> bool {
+        let (is_left, word, bit) = Tape::locate(position);
+        let words = if is_left { &self.left } else { &self.right };
+        words.get(word).is_some_and(|w| w & bit != 0)
+    }
+
+    fn set(&mut self, position: i64, value: bool) {
+        let (is_left, word, bit) = Tape::locate(position);
+        let words = if is_left { &mut self.left } else { &mut self.right };
+        if word >= words.len() {
+            if !value {
+                return;
+            }
+            words.resize(word + 1, 0);
+        }
+
+        if value {
+            words[word] |= bit;
+        } else {
+            words[word] &= !bit;
+        }
+    }
+
+    /// The number of cells set to 1
+    fn count_ones(&self) -> usize {
+        self.left.iter().chain(self.right.iter()).map(|w| w.count_ones() as usize).sum()
+    }
+}
+
+struct TuringMachine<'a> {
+    blueprint: &'a Blueprint,
+    tape: Tape,
+    cursor: i64,
+    state: usize,
+}
+
+impl<'a> TuringMachine<'a> {
+    fn new(blueprint: &'a Blueprint) -> Self {
+        TuringMachine { blueprint, tape: Tape::default(), cursor: 0, state: blueprint.start }
+    }
+
+    fn step(&mut self) {
+        let current = self.tape.get(self.cursor) as usize;
+        let action = self.blueprint.actions[self.state][current];
+
+        self.tape.set(self.cursor, action.write);
+        self.cursor += match action.step {
+            Move::Left => -1,
+            Move::Right => 1,
+        };
+        self.state = action.next;
+    }
+
+    fn run(&mut self, steps: usize) {
+        for _ in 0..steps {
+            self.step();
+        }
+    }
+
+    fn checksum(&self) -> usize {
+        self.tape.count_ones()
+    }
+}
+
+/// Runs the machine for as many steps as the blueprint asks and returns the checksum
+fn diagnostic_checksum(blueprint: &Blueprint) -> usize {
+    let mut machine = TuringMachine::new(blueprint);
+    machine.run(blueprint.steps);
+    machine.checksum()
+}
+
+#[aoc(year = 2017, day = 25, part = 1)]
+pub fn solve_a() {
+    let blueprint = read_content(25).parse::<Blueprint>().unwrap_or_else(|e| panic!("{}", e));
+    let ans = diagnostic_checksum(&blueprint);
+
+    println!("Solution A: {}", ans);
+}
+
+#[aoc(year = 2017, day = 25, part = 2)]
+pub fn solve_b() {
+    println!("Completed AOC 2017");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "Begin in state A.
+Perform a diagnostic checksum after 6 steps.
 
 In state A:
   If the current value is 0:
@@ -11,128 +202,56 @@ In state A:
     - Continue with state B.
   If the current value is 1:
     - Write the value 0.
-    - Move one slot to the right.
-    - Continue with state C.
+    - Move one slot to the left.
+    - Continue with state B.
 
 In state B:
   If the current value is 0:
-    - Write the value 0.
+    - Write the value 1.
     - Move one slot to the left.
     - Continue with state A.
-  If the current value is 1:
-    - Write the value 0.
-    - Move one slot to the right.
-    - Continue with state D.
-
-In state C:
-  If the current value is 0:
-    - Write the value 1.
-    - Move one slot to the right.
-    - Continue with state D.
   If the current value is 1:
     - Write the value 1.
     - Move one slot to the right.
     - Continue with state A.
+";
 
-In state D:
-  If the current value is 0:
-    - Write the value 1.
-    - Move one slot to the left.
-    - Continue with state E.
-  If the current value is 1:
-    - Write the value 0.
-    - Move one slot to the left.
-    - Continue with state D.
+    #[test]
+    fn test_parse_blueprint() {
+        let blueprint = EXAMPLE.parse::<Blueprint>().unwrap();
 
-In state E:
-  If the current value is 0:
-    - Write the value 1.
-    - Move one slot to the right.
-    - Continue with state F.
-  If the current value is 1:
-    - Write the value 1.
-    - Move one slot to the left.
-    - Continue with state B.
+        assert_eq!(blueprint.names, vec!["A", "B"]);
+        assert_eq!((blueprint.start, blueprint.steps), (0, 6));
+        assert_eq!(blueprint.actions[1], [
+            Action { write: true, step: Move::Left, next: 0 },
+            Action { write: true, step: Move::Right, next: 0 },
+        ]);
 
-In state F:
-  If the current value is 0:
-    - Write the value 1.
-    - Move one slot to the right.
-    - Continue with state A.
-  If the current value is 1:
-    - Write the value 1.
-    - Move one slot to the right.
-    - Continue with state E.
- **/
+        assert!(EXAMPLE.replace("state B.", "state C.").parse::<Blueprint>().is_err());
+        assert!(EXAMPLE.replace("value is 1", "value is 0").parse::<Blueprint>().is_err());
+        assert!(EXAMPLE.replace("Begin", "Start").parse::<Blueprint>().is_err());
+    }
 
-enum State { A, B, C, D, E, F }
+    #[test]
+    fn test_diagnostic_checksum() {
+        let blueprint = EXAMPLE.parse::<Blueprint>().unwrap();
 
-pub fn solve_a() {
-    let mut register: HashMap<i64, usize> = HashMap::new();
-    let mut state = State::A;
-    let mut index = 0;
-
-    for _ in 0..12_368_930 {
-        let v = register.entry(index).or_insert(0);
-
-        match state {
-            State::A => {
-                if *v == 0 {
-                    *v = 1;
-                    state = State::B;
-                } else {
-                    *v = 0;
-                    state = State::C;
-                }
-                index += 1;
-            }
-            State::B => {
-                if *v == 0 {
-                    index -= 1;
-                    state = State::A;
-                } else {
-                    index += 1;
-                    state = State::D;
-                }
-                *v = 0;
-            }
-            State::C => {
-                state = if *v == 0 { State::D } else { State::A };
-                index += 1;
-                *v = 1;
-            }
-            State::D => {
-                if *v == 0 {
-                    *v = 1;
-                    state = State::E;
-                } else {
-                    *v = 0;
-                    state = State::D;
-                }
-                index -= 1;
-            }
-            State::E => {
-                if *v == 0 {
-                    index += 1;
-                    state = State::F;
-                } else {
-                    index -= 1;
-                    state = State::B;
-                }
-                *v = 1;
-            }
-            State::F => {
-                state = if *v == 0 { State::A } else { State::E };
-                index += 1;
-                *v = 1;
-            }
-        }
+        assert_eq!(diagnostic_checksum(&blueprint), 3);
     }
 
-    let ans = register.values().filter(|&&v| v == 1).count();
-    println!("Solution A: {}", ans);
-}
+    #[test]
+    fn test_tape() {
+        let mut tape = Tape::default();
+        for position in [-200, -65, -64, -1, 0, 63, 64, 130] {
+            assert!(!tape.get(position));
+            tape.set(position, true);
+            assert!(tape.get(position));
+        }
+        assert_eq!(tape.count_ones(), 8);
 
-pub fn solve_b() {
-    println!("Completed AOC 2017");
-}
\ No newline at end of file
+        tape.set(-64, false);
+        tape.set(1000, false);
+        assert!(!tape.get(-64) && tape.get(-65));
+        assert_eq!(tape.count_ones(), 7);
+    }
+}
@@ -1,8 +1,11 @@
 use std::cmp::max;
 use std::collections::HashMap;
 
+use aoc_macros::aoc;
+
 const PUZZLE_INPUT: usize = 368078;
 
+#[aoc(year = 2017, day = 3, part = 1)]
 pub fn solve_a() {
     let mut level: usize = 1;
     while level.pow(2) < PUZZLE_INPUT {
@@ -52,6 +55,7 @@ impl Point {
 }
 
 
+#[aoc(year = 2017, day = 3, part = 2)]
 pub fn solve_b() {
     let mut points = HashMap::from([(Point(0, 0), 1_usize)]);
 
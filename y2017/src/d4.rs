@@ -1,3 +1,4 @@
+use aoc_macros::aoc;
 use itertools::Itertools;
 
 const PUZZLE_INPUT: &str = "nyot babgr babgr kqtu kqtu kzshonp ylyk psqk
@@ -514,6 +515,7 @@ dflx gdtb jyoj jyoj dflx aqhycgi xffnn
 inc mpys mzqmcwx vryz ibqrzc pmsy fat rojpxwy rcbqzi gjef";
 
 
+#[aoc(year = 2017, day = 4, part = 1)]
 pub fn solve_a() {
     let ans = PUZZLE_INPUT.lines()
         .filter(|line| {
@@ -524,6 +526,7 @@ pub fn solve_a() {
     println!("Solution A: {}", ans);
 }
 
+#[aoc(year = 2017, day = 4, part = 2)]
 pub fn solve_b() {
     let ans = PUZZLE_INPUT.lines()
         .filter(|line| {
@@ -1,3 +1,5 @@
+use aoc_macros::aoc;
+
 use super::inputs::read_content;
 
 fn get_jumps() -> Vec<i32> {
@@ -7,6 +9,7 @@ fn get_jumps() -> Vec<i32> {
         .collect()
 }
 
+#[aoc(year = 2017, day = 5, part = 1)]
 pub fn solve_a() {
     let mut jumps = get_jumps();
 
@@ -27,6 +30,7 @@ pub fn solve_a() {
     println!("Solution A: {}", num)
 }
 
+#[aoc(year = 2017, day = 5, part = 2)]
 pub fn solve_b() {
     let mut jumps = get_jumps();
 
@@ -1,6 +1,5 @@
-use std::collections::{HashMap, HashSet};
-
-use itertools::Itertools;
+use aoc_macros::aoc;
+use aoc_utils::cycle::{self, Cycle};
 
 fn get_inputs() -> Vec<usize> {
     "10	3	15	10	5	15	5	15	9	2	5	8	5	2	3	6"
@@ -9,10 +8,6 @@ fn get_inputs() -> Vec<usize> {
         .collect()
 }
 
-fn hash_inputs(inputs: &Vec<usize>) -> String {
-    inputs.iter().map(|x| x.to_string()).join(".")
-}
-
 fn get_max_index_and_value(inputs: &Vec<usize>) -> (usize, usize) {
     inputs.iter()
         .enumerate()
@@ -37,41 +32,28 @@ fn redistribute_blocks(inputs: &mut Vec<usize>, idx: usize, blocks: usize) {
     }
 }
 
-pub fn solve_a() {
-    let mut inputs = get_inputs();
-    let mut seen = HashSet::from([hash_inputs(&inputs)]);
+fn reallocate(inputs: &[usize]) -> Vec<usize> {
+    let mut next = inputs.to_vec();
+    let (idx, blocks) = get_max_index_and_value(&next);
+    redistribute_blocks(&mut next, idx, blocks);
 
-    let mut cycles = 0;
-    loop {
-        cycles += 1;
+    next
+}
 
-        let (idx, blocks) = get_max_index_and_value(&inputs);
-        redistribute_blocks(&mut inputs, idx, blocks);
+fn find_reallocation_cycle() -> Cycle {
+    cycle::find_cycle(get_inputs(), |inputs| reallocate(inputs))
+}
 
-        if !seen.insert(hash_inputs(&inputs)) {
-            break;
-        }
-    };
+#[aoc(year = 2017, day = 6, part = 1)]
+pub fn solve_a() {
+    let ans = find_reallocation_cycle().first_repeat();
 
-    println!("Solution A: {}", cycles);
+    println!("Solution A: {}", ans);
 }
 
-
+#[aoc(year = 2017, day = 6, part = 2)]
 pub fn solve_b() {
-    let mut inputs = get_inputs();
-    let mut seen = HashMap::from([(hash_inputs(&inputs), 0)]);
-
-    let mut cycle = 0;
-    let ans = loop {
-        cycle += 1;
-
-        let (idx, blocks) = get_max_index_and_value(&inputs);
-        redistribute_blocks(&mut inputs, idx, blocks);
-
-        if let Some(prev_cycle) = seen.insert(hash_inputs(&inputs), cycle) {
-            break cycle - prev_cycle;
-        }
-    };
+    let ans = find_reallocation_cycle().length;
 
     println!("Solution B: {}", ans);
-}
\ No newline at end of file
+}
@@ -3,10 +3,12 @@ use std::collections::HashMap;
 use std::hash::{Hash, Hasher};
 use std::rc::Rc;
 
+use aoc_macros::aoc;
 use regex::Regex;
 
 use crate::inputs::read_content;
 
+#[aoc(year = 2017, day = 7, part = 1)]
 pub fn solve_a() {
     let top_node = form_graph(&read_content(7));
 
@@ -150,6 +152,7 @@ fn form_graph(input: &str) -> Rc<RefCell<Node>> {
     panic!("All nodes have parents!")
 }
 
+#[aoc(year = 2017, day = 7, part = 2)]
 pub fn solve_b() {
     let root = form_graph(&read_content(7));
 
@@ -1,5 +1,5 @@
-use std::collections::HashMap;
-
+use aoc_macros::aoc;
+use aoc_utils::machine::{self, Flow, Machine, Registers};
 use once_cell::sync::OnceCell;
 use regex::Regex;
 
@@ -83,71 +83,66 @@ impl Instruction {
     }
 }
 
-struct Registry {
-    registers: HashMap<String, i64>,
-    highest_value: i64,
-}
-
-impl Registry {
-    fn new() -> Registry {
-        Registry { registers: HashMap::new(), highest_value: 0 }
-    }
-
-    fn run_instruction(&mut self, ins: &Instruction) {
-        let cond_value = self.registers.entry(ins.cond_register.clone()).or_insert(0);
-        let condition_met = match ins.cond_operator {
-            Operator::LessEqual => { *cond_value <= ins.cond_amount }
-            Operator::Less => { *cond_value < ins.cond_amount }
-            Operator::NotEqual => { *cond_value != ins.cond_amount }
-            Operator::Equal => { *cond_value == ins.cond_amount }
-            Operator::Greater => { *cond_value > ins.cond_amount }
-            Operator::GreaterEqual => { *cond_value >= ins.cond_amount }
+/// The state is the highest value any register has held
+impl machine::Instruction<i64> for Instruction {
+    type Register = String;
+
+    fn execute(&self, registers: &mut Registers<String>, highest_value: &mut i64) -> Flow {
+        let cond_value = *registers.get_mut(self.cond_register.clone());
+        let condition_met = match self.cond_operator {
+            Operator::LessEqual => { cond_value <= self.cond_amount }
+            Operator::Less => { cond_value < self.cond_amount }
+            Operator::NotEqual => { cond_value != self.cond_amount }
+            Operator::Equal => { cond_value == self.cond_amount }
+            Operator::Greater => { cond_value > self.cond_amount }
+            Operator::GreaterEqual => { cond_value >= self.cond_amount }
         };
 
         if condition_met {
-            let delta = match ins.command {
-                Command::Inc => { ins.amount }
-                Command::Dec => { -ins.amount }
+            let delta = match self.command {
+                Command::Inc => { self.amount }
+                Command::Dec => { -self.amount }
             };
 
-            let value = self.registers.entry(ins.register.clone()).or_insert(0);
+            let value = registers.get_mut(self.register.clone());
             *value += delta;
 
-            if *value > self.highest_value {
-                self.highest_value = *value;
+            if *value > *highest_value {
+                *highest_value = *value;
             }
         }
-    }
 
-    fn max_value(&self) -> i64 {
-        *self.registers.values().max().unwrap()
+        Flow::Next
     }
 }
 
-fn run_registry() -> Registry {
-    let mut registry = Registry::new();
-
+/// Runs the instructions, returning the registers and the highest value any of them held
+fn run_registry() -> (Registers<String>, i64) {
     let input = read_content(8);
-    for line in input.lines() {
-        if let Some(ins) = Instruction::new(line) {
-            registry.run_instruction(&ins);
-        } else {
-            panic!("Could not parse line as Instruction: {}", line);
-        }
-    }
+    let instructions = input.lines()
+        .map(|line| Instruction::new(line).unwrap_or_else(|| panic!("Could not parse line as Instruction: {}", line)))
+        .collect::<Vec<_>>();
+
+    let mut machine = Machine::new(&instructions);
+    machine.run();
+    machine.into_parts()
+}
 
-    registry
+fn max_value(registers: &Registers<String>) -> i64 {
+    registers.iter().map(|(_, v)| v).max().unwrap()
 }
 
+#[aoc(year = 2017, day = 8, part = 1)]
 pub fn solve_a() {
-    let registry = run_registry();
-    let ans = registry.max_value();
+    let (registers, _) = run_registry();
+    let ans = max_value(&registers);
 
     println!("Solution A: {}", ans);
 }
 
+#[aoc(year = 2017, day = 8, part = 2)]
 pub fn solve_b() {
-    let registry = run_registry();
+    let (_, highest_value) = run_registry();
 
-    println!("Solution B: {}", registry.highest_value);
+    println!("Solution B: {}", highest_value);
 }
\ No newline at end of file
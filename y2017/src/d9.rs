@@ -1,5 +1,8 @@
+use aoc_macros::aoc;
+
 use crate::inputs::read_content;
 
+#[aoc(year = 2017, day = 9, part = 1)]
 pub fn solve_a() {
     let stream = read_content(9);
 
@@ -47,6 +50,7 @@ fn score_stream(stream: &str) -> usize {
     total_score
 }
 
+#[aoc(year = 2017, day = 9, part = 2)]
 pub fn solve_b() {
     let stream = read_content(9);
     let ans = count_garbage(&stream);
@@ -0,0 +1,182 @@
+//! Runs a group of programs that only talk to each other by sending messages, each on its own
+//! thread. Receiving blocks until a message arrives, and the network keeps track of how many
+//! programs are waiting, how many are still running and how many messages are on their way, so it
+//! can tell when nothing will ever happen again. It then wakes every waiting program empty-handed.
+
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+use crossbeam::channel::{self, Receiver, select, Sender};
+
+/// The state of the whole network, only ever changed while holding its lock
+#[derive(Debug)]
+struct Monitor {
+    /// Programs that haven't finished
+    alive: usize,
+    /// Programs blocked in `receive`
+    waiting: usize,
+    /// Messages sent and not yet received
+    in_flight: usize,
+    finished: Vec<bool>,
+    /// Dropped once the network is deadlocked, which wakes every waiting program
+    shutdown: Option<Sender<()>>,
+}
+
+impl Monitor {
+    fn check_deadlock(&mut self) {
+        if self.waiting == self.alive && self.in_flight == 0 {
+            self.shutdown = None;
+        }
+    }
+
+    fn is_deadlocked(&self) -> bool {
+        self.shutdown.is_none()
+    }
+}
+
+/// A program's connection to the rest of the network. It counts as finished once it is dropped.
+pub struct Link<T> {
+    id: usize,
+    senders: Vec<Sender<T>>,
+    receiver: Receiver<T>,
+    shutdown: Receiver<()>,
+    monitor: Arc<Mutex<Monitor>>,
+}
+
+impl<T> Link<T> {
+    pub fn id(&self) -> usize {
+        self.id
+    }
+
+    /// The number of programs in the network
+    pub fn size(&self) -> usize {
+        self.senders.len()
+    }
+
+    /// Sends `message` to program `to`. Messages to a program that has finished, or sent once the
+    /// network is deadlocked, are dropped.
+    pub fn send(&self, to: usize, message: T) {
+        let mut monitor = self.monitor.lock().unwrap();
+        if !monitor.finished[to] && !monitor.is_deadlocked() {
+            monitor.in_flight += 1;
+            self.senders[to].send(message).expect("Could not send to a program that hasn't finished");
+        }
+    }
+
+    /// Waits for the next message, or returns `None` once the network is deadlocked
+    pub fn receive(&self) -> Option<T> {
+        {
+            let mut monitor = self.monitor.lock().unwrap();
+            if monitor.is_deadlocked() {
+                return None;
+            }
+            if let Ok(message) = self.receiver.try_recv() {
+                monitor.in_flight -= 1;
+                return Some(message);
+            }
+            monitor.waiting += 1;
+            monitor.check_deadlock();
+        }
+
+        let message = select! {
+            recv(self.receiver) -> message => message.ok(),
+            recv(self.shutdown) -> _ => None,
+        };
+
+        let mut monitor = self.monitor.lock().unwrap();
+        monitor.waiting -= 1;
+        if message.is_some() {
+            monitor.in_flight -= 1;
+        }
+        message
+    }
+}
+
+impl<T> Drop for Link<T> {
+    fn drop(&mut self) {
+        let mut monitor = self.monitor.lock().unwrap();
+        monitor.finished[self.id] = true;
+        monitor.alive -= 1;
+        monitor.in_flight -= self.receiver.try_iter().count();
+        monitor.check_deadlock();
+    }
+}
+
+/// Runs `program` for each of `programs` programs on its own thread, handing each its link to the
+/// network, and returns what they returned in order
+pub fn run_network<T, R, F>(programs: usize, program: F) -> Vec<R>
+    where T: Send,
+          R: Send,
+          F: Fn(Link<T>) -> R + Sync {
+    let (shutdown_sender, shutdown) = channel::bounded(0);
+    let (senders, receivers): (Vec<_>, Vec<_>) = (0..programs).map(|_| channel::unbounded()).unzip();
+    let monitor = Arc::new(Mutex::new(Monitor {
+        alive: programs,
+        waiting: 0,
+        in_flight: 0,
+        finished: vec![false; programs],
+        shutdown: Some(shutdown_sender),
+    }));
+
+    thread::scope(|scope| {
+        let handles = receivers.into_iter()
+                               .enumerate()
+                               .map(|(id, receiver)| {
+                                   let link = Link { id, senders: senders.clone(), receiver, shutdown: shutdown.clone(), monitor: monitor.clone() };
+                                   let program = &program;
+                                   scope.spawn(move || program(link))
+                               })
+                               .collect::<Vec<_>>();
+
+        handles.into_iter().map(|handle| handle.join().unwrap()).collect()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ring() {
+        // a token goes around the ring, gaining one every time it is passed on, until it gets to
+        // 50 and everyone is left waiting on everyone else
+        let received = run_network(5, |link: Link<usize>| {
+            let next = (link.id() + 1) % link.size();
+            if link.id() == 0 {
+                link.send(next, 0);
+            }
+
+            let mut received = Vec::new();
+            while let Some(token) = link.receive() {
+                received.push(token);
+                if token < 50 {
+                    link.send(next, token + 1);
+                }
+            }
+            received
+        });
+
+        assert_eq!(received[1], (0..=50).step_by(5).collect::<Vec<_>>());
+        assert_eq!(received[0], (4..50).step_by(5).collect::<Vec<_>>());
+        assert_eq!(received.iter().map(|r| r.len()).sum::<usize>(), 51);
+    }
+
+    #[test]
+    fn test_finished_programs() {
+        // program 0 sends to a program that has already finished and then waits for an answer that
+        // will never come
+        let received = run_network(2, |link: Link<i64>| {
+            if link.id() == 1 {
+                return vec![];
+            }
+            while !link.monitor.lock().unwrap().finished[1] {
+                thread::yield_now();
+            }
+            link.send(1, 7);
+            link.receive().into_iter().collect::<Vec<_>>()
+        });
+
+        assert_eq!(received, vec![vec![], vec![]]);
+        assert_eq!(run_network(0, |_: Link<i64>| 1), Vec::<i32>::new());
+    }
+}
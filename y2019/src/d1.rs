@@ -1,5 +1,7 @@
 use std::iter::Iterator;
 
+use aoc_macros::aoc;
+
 fn get_input() -> Vec<i32> {
     "51585
 137484
@@ -115,6 +117,7 @@ fn required_fuel(mass: i32) -> i32 {
     if mass < 9 { 0 } else { mass / 3 - 2 }
 }
 
+#[aoc(year = 2019, day = 1, part = 1)]
 pub fn solve_a() {
     let inp = get_input();
 
@@ -124,6 +127,7 @@ pub fn solve_a() {
     println!("Solution A: {}", ans)
 }
 
+#[aoc(year = 2019, day = 1, part = 2)]
 pub fn solve_b() {
     let inp = get_input();
 
@@ -1,6 +1,8 @@
 use std::collections::{HashMap, HashSet};
 use std::fmt::{Debug, Formatter};
 
+use aoc_macros::aoc;
+use aoc_utils::number::gcd;
 use itertools::Itertools;
 
 const PUZZLE_INPUT: &str = "#..#....#...#.#..#.......##.#.####
@@ -51,15 +53,11 @@ impl Gradient {
         } else if dy == 0 {
             Gradient { dx: dx / dx.abs(), dy }
         } else {
-            let gcd = Gradient::gcd(dx, dy);
+            let gcd = gcd(dx, dy);
             Gradient { dx: dx / gcd, dy: dy / gcd }
         }
     }
 
-    fn gcd(a: i32, b: i32) -> i32 {
-        if b == 0 { a.abs() } else { Gradient::gcd(b, a % b) }
-    }
-
     fn to_degrees(&self) -> f32 {
         match (self.dx, self.dy) {
             (0, -1) => 360.0,
@@ -127,6 +125,7 @@ fn create_map(input: &str) -> Vec<Point> {
     map
 }
 
+#[aoc(year = 2019, day = 10, part = 1)]
 pub fn solve_a() {
     let map = create_map(PUZZLE_INPUT);
     let (_, ans) = num_satellites_viewed_from_best_station(&map);
@@ -161,6 +160,7 @@ fn num_viewable_satellites(map: &Vec<Point>, source: &Point) -> usize {
     seen.len()
 }
 
+#[aoc(year = 2019, day = 10, part = 2)]
 pub fn solve_b() {
     let map = create_map(PUZZLE_INPUT);
     let (source, _) = num_satellites_viewed_from_best_station(&map);
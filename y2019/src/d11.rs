@@ -1,5 +1,8 @@
 use std::collections::{HashMap, VecDeque};
 
+use aoc_macros::aoc;
+use aoc_utils::{ocr, Point2};
+
 use crate::int_code::{CommandMap, decode_op, ParameterMode};
 
 const PUZZLE_INPUT: &str = "3,8,1005,8,336,1106,0,11,0,0,0,104,1,104,0,3,8,102,-1,8,10,1001,10,1,10,4,10,108,1,8,10,4,10,101,0,8,28,1006,0,36,1,2,5,10,1006,0,57,1006,0,68,3,8,102,-1,8,10,1001,10,1,10,4,10,108,0,8,10,4,10,1002,8,1,63,2,6,20,10,1,106,7,10,2,9,0,10,3,8,102,-1,8,10,101,1,10,10,4,10,108,1,8,10,4,10,102,1,8,97,1006,0,71,3,8,1002,8,-1,10,101,1,10,10,4,10,108,1,8,10,4,10,1002,8,1,122,2,105,20,10,3,8,1002,8,-1,10,1001,10,1,10,4,10,108,0,8,10,4,10,101,0,8,148,2,1101,12,10,1006,0,65,2,1001,19,10,3,8,102,-1,8,10,1001,10,1,10,4,10,108,0,8,10,4,10,101,0,8,181,3,8,1002,8,-1,10,1001,10,1,10,4,10,1008,8,0,10,4,10,1002,8,1,204,2,7,14,10,2,1005,20,10,1006,0,19,3,8,102,-1,8,10,101,1,10,10,4,10,108,1,8,10,4,10,102,1,8,236,1006,0,76,1006,0,28,1,1003,10,10,1006,0,72,3,8,1002,8,-1,10,101,1,10,10,4,10,108,0,8,10,4,10,102,1,8,271,1006,0,70,2,107,20,10,1006,0,81,3,8,1002,8,-1,10,1001,10,1,10,4,10,108,1,8,10,4,10,1002,8,1,303,2,3,11,10,2,9,1,10,2,1107,1,10,101,1,9,9,1007,9,913,10,1005,10,15,99,109,658,104,0,104,1,21101,0,387508441896,1,21102,1,353,0,1106,0,457,21101,0,937151013780,1,21101,0,364,0,1105,1,457,3,10,104,0,104,1,3,10,104,0,104,0,3,10,104,0,104,1,3,10,104,0,104,1,3,10,104,0,104,0,3,10,104,0,104,1,21102,179490040923,1,1,21102,411,1,0,1105,1,457,21101,46211964123,0,1,21102,422,1,0,1106,0,457,3,10,104,0,104,0,3,10,104,0,104,0,21101,838324716308,0,1,21101,0,445,0,1106,0,457,21102,1,868410610452,1,21102,1,456,0,1106,0,457,99,109,2,22101,0,-1,1,21101,40,0,2,21101,0,488,3,21101,478,0,0,1106,0,521,109,-2,2105,1,0,0,1,0,0,1,109,2,3,10,204,-1,1001,483,484,499,4,0,1001,483,1,483,108,4,483,10,1006,10,515,1101,0,0,483,109,-2,2105,1,0,0,109,4,2101,0,-1,520,1207,-3,0,10,1006,10,538,21101,0,0,-3,22102,1,-3,1,21202,-2,1,2,21101,0,1,3,21101,557,0,0,1105,1,562,109,-4,2105,1,0,109,5,1207,-3,1,10,1006,10,585,2207,-4,-2,10,1006,10,585,22101,0,-4,-4,1106,0,653,21201,-4,0,1,21201,-3,-1,2,21202,-2,2,3,21102,604,1,0,1106,0,562,21202,1,1,-4,21101,0,1,-1,2207,-4,-2,10,1006,10,623,21102,0,1,-1,22202,-2,-1,-2,2107,0,-3,10,1006,10,645,21202,-1,1,1,21101,0,645,0,106,0,520,21202,-2,-1,-2,22201,-4,-2,-4,109,-5,2105,1,0";
@@ -17,59 +20,25 @@ fn paint(starting_tile: Tile) -> HashMap<(i64, i64), Tile> {
 }
 
 
+#[aoc(year = 2019, day = 11, part = 1)]
 pub fn solve_a() {
     let ans = paint(Tile::Black).len();
     assert_eq!(ans, 2018);
     println!("Solution A: {}", ans);
 }
 
+#[aoc(year = 2019, day = 11, part = 2)]
 pub fn solve_b() {
     let map = paint(Tile::White);
 
-    let mut min_x = i64::MAX;
-    let mut max_x = i64::MIN;
-    let mut min_y = i64::MAX;
-    let mut max_y = i64::MIN;
-
-    for (pos, _) in &map {
-        if pos.0 > max_x {
-            max_x = pos.0;
-        } else if pos.0 < min_x {
-            min_x = pos.0;
-        }
-
-        if pos.1 > max_y {
-            max_y = pos.1;
-        } else if pos.1 < min_y {
-            min_y = pos.1;
-        }
-    }
+    // the robot's y axis points up, so flip it to read the letters
+    let ans = ocr::read_points(map.iter()
+                                  .filter(|(_, tile)| **tile == Tile::White)
+                                  .map(|(&(x, y), _)| Point2::new([x, -y])))
+        .unwrap_or_else(|e| panic!("{}", e));
 
-    // offsets to get positive index
-    let dx = (max_x - min_x + 1) as usize;
-    let dy = (max_y - min_y + 1) as usize;
-
-    let mut drawing: Vec<Vec<char>> = Vec::with_capacity(dy);
-    for _ in 0..dy {
-        drawing.push(Vec::from(['#'].repeat(dx)));
-    }
-
-    for ((x, y), tile) in &map {
-        if *tile == Tile::Black {
-            let x = (*x - min_x) as usize;
-            let y = (*y - min_y) as usize;
-
-            drawing[y][x] = ' ';
-        }
-    }
-
-    drawing.reverse();
-
-    println!("Solution B: APFKRKBR\n");
-    for chars in drawing {
-        let line = String::from_iter(chars);
-        println!("{}", &line[1..line.len() - 3]);
-    }
+    assert_eq!(ans, "APFKRKBR");
+    println!("Solution B: {}", ans);
 }
 
 enum Face {
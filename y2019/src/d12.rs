@@ -1,6 +1,8 @@
 use std::ops;
 
-use regex::Regex;
+use aoc_macros::{aoc, AocParse};
+use aoc_utils::cycle;
+use aoc_utils::number::lcm;
 
 const PUZZLE_INPUT: &str = "<x=-7, y=-1, z=6>
 <x=6, y=-9, z=-9>
@@ -8,7 +10,8 @@ const PUZZLE_INPUT: &str = "<x=-7, y=-1, z=6>
 <x=4, y=-17, z=-12>";
 
 
-#[derive(Debug, Copy, Clone, PartialEq)]
+#[derive(AocParse, Debug, Copy, Clone, PartialEq)]
+#[aoc(pattern = "<x={x}, y={y}, z={z}>")]
 struct Vector {
     x: i64,
     y: i64,
@@ -44,19 +47,6 @@ impl ops::SubAssign for Vector {
 }
 
 impl Vector {
-    fn from_line(line: &str) -> Vector {
-        let re = Regex::new(r"<x=(?<x>[-\s]?\d+), y=(?<y>[-\s]?\d+), z=(?<z>[-\s]?\d+)>").expect("Could not compile regex");
-        let Some(caps) = re.captures(line) else {
-            panic!("Invalid vector line input: {}", line);
-        };
-
-        Vector {
-            x: caps["x"].trim().parse::<i64>().expect(format!("Could not parse line to x integer: {line}").as_str()),
-            y: caps["y"].trim().parse::<i64>().expect(format!("Could not parse line to y integer: {line}").as_str()),
-            z: caps["z"].trim().parse::<i64>().expect(format!("Could not parse line to z integer: {line}").as_str()),
-        }
-    }
-
     #[allow(dead_code)]
     fn from_arr(inp: [i64; 3]) -> Vector {
         Vector { x: inp[0], y: inp[1], z: inp[2] }
@@ -76,7 +66,8 @@ impl Vector {
 }
 
 
-#[derive(Debug, PartialEq)]
+#[derive(AocParse, Debug, PartialEq)]
+#[aoc(pattern = "pos={pos}, vel={vel}")]
 struct Moon {
     pos: Vector,
     vel: Vector,
@@ -84,7 +75,9 @@ struct Moon {
 
 impl Moon {
     fn new(line: &str) -> Moon {
-        Moon { pos: Vector::from_line(line), vel: Vector { x: 0, y: 0, z: 0 } }
+        let pos = line.parse().unwrap_or_else(|e| panic!("{}", e));
+
+        Moon { pos, vel: Vector { x: 0, y: 0, z: 0 } }
     }
 }
 
@@ -114,6 +107,7 @@ fn make_moons(input: &str) -> Vec<Moon> {
 }
 
 
+#[aoc(year = 2019, day = 12, part = 1)]
 pub fn solve_a() {
     let mut moons = make_moons(PUZZLE_INPUT);
     simulate(&mut moons, 1000);
@@ -138,65 +132,34 @@ fn moons_get_axis_values(moons: &Vec<Moon>, axis: char) -> Vec<(i64, i64)> {
     }
 }
 
-pub fn solve_b() {
-    let mut moons = make_moons(PUZZLE_INPUT);
-    let mut steps: Vec<Option<u64>> = vec![None, None, None];
-    let mut counter = 0_u64;
-
-    let start_x = moons_get_axis_values(&moons, 'x');
-    let start_y = moons_get_axis_values(&moons, 'y');
-    let start_z = moons_get_axis_values(&moons, 'z');
-
-    while steps.iter().any(|&x| x.is_none()) {
-        counter += 1;
-        simulate_one_step(&mut moons);
-
-        if let None = steps[0] {
-            if start_x == moons_get_axis_values(&moons, 'x') {
-                steps[0] = Some(counter);
-            }
-        }
-
-        if let None = steps[1] {
-            if start_y == moons_get_axis_values(&moons, 'y') {
-                steps[1] = Some(counter);
-            }
-        }
-
-        if let None = steps[2] {
-            if start_z == moons_get_axis_values(&moons, 'z') {
-                steps[2] = Some(counter);
-            }
-        }
-    }
+/// One step of the simulation along a single axis, which moves independently of the others
+fn simulate_axis(axis: &[(i64, i64)]) -> Vec<(i64, i64)> {
+    axis.iter()
+        .map(|&(pos, vel)| {
+            let vel = vel + axis.iter().map(|&(other, _)| (other - pos).signum()).sum::<i64>();
+            (pos + vel, vel)
+        })
+        .collect()
+}
 
-    let steps = steps.iter().map(|x| x.unwrap()).collect::<Vec<_>>();
+#[aoc(year = 2019, day = 12, part = 2)]
+pub fn solve_b() {
+    let moons = make_moons(PUZZLE_INPUT);
 
-    let mut ans = steps[0];
-    for x in &steps[1..] {
-        ans *= *x / gcd(ans, *x);
+    // the whole system repeats once every axis has gone round its own cycle
+    let mut ans = 1;
+    for axis in ['x', 'y', 'z'] {
+        let length = cycle::brent(moons_get_axis_values(&moons, axis), |axis| simulate_axis(axis)).length as u64;
+        ans = lcm(ans, length);
     }
 
     assert_eq!(ans, 452582583272768);
     println!("Solution B: {}", ans);
 }
 
-fn gcd(a: u64, b: u64) -> u64 {
-    let mut a = a;
-    let mut b = b;
-
-    while b > 0 {
-        (a, b) = (b, a % b)
-    }
-
-    a
-}
-
 #[cfg(test)]
 mod tests {
-    use regex::Regex;
-
-    use crate::d12::{gcd, make_moons, Moon, simulate_one_step, Vector};
+    use crate::d12::{make_moons, Moon, simulate_one_step, Vector};
 
     fn make_test_input() -> Vec<Moon> {
         let input = "<x=-1, y=0, z=2>
@@ -208,14 +171,7 @@ mod tests {
     }
 
     fn make_expected_moons_vector(input: &str) -> Vec<Moon> {
-        let re = Regex::new("pos=(<x=.*, y=.*, z=.*>), vel=(<x=.*, y=.*, z=.*>)").unwrap();
-        input.split('\n').map(|line| {
-            let caps = re.captures(line).expect(format!("Could not parse line: {}", line).as_str());
-            let pos = caps.get(1).expect("Could not get 'pos'").as_str();
-            let vel = caps.get(2).expect("Could not get 'vel'").as_str();
-
-            Moon { pos: Vector::from_line(pos), vel: Vector::from_line(vel) }
-        }).collect::<Vec<_>>()
+        input.split('\n').map(|line| line.parse().unwrap()).collect::<Vec<_>>()
     }
 
     #[test]
@@ -281,15 +237,4 @@ pos=<x= 2, y= 0, z= 4>, vel=<x= 1, y=-1, z=-1>"
             }
         }
     }
-
-    #[test]
-    fn test_gcd() {
-        for (a, b, exp) in [
-            (78, 15, 3),
-            (99, 53, 1),
-            (48, 56, 8),
-        ] {
-            assert_eq!(gcd(a, b), exp);
-        }
-    }
-}
\ No newline at end of file
+}
@@ -1,65 +1,197 @@
+use std::collections::VecDeque;
+use std::time::Duration;
+
+use aoc_macros::{aoc, AocTile};
+use aoc_utils::{Point2, SparseGrid};
+
 use crate::int_code::{CommandMap, decode_op, ParameterMode};
+use crate::terminal::{Key, Terminal};
 
 const PUZZLE_INPUT: &str = "1,380,379,385,1008,2399,462045,381,1005,381,12,99,109,2400,1102,0,1,383,1101,0,0,382,20101,0,382,1,21001,383,0,2,21102,37,1,0,1105,1,578,4,382,4,383,204,1,1001,382,1,382,1007,382,44,381,1005,381,22,1001,383,1,383,1007,383,20,381,1005,381,18,1006,385,69,99,104,-1,104,0,4,386,3,384,1007,384,0,381,1005,381,94,107,0,384,381,1005,381,108,1105,1,161,107,1,392,381,1006,381,161,1102,-1,1,384,1106,0,119,1007,392,42,381,1006,381,161,1101,0,1,384,21002,392,1,1,21101,0,18,2,21102,1,0,3,21101,138,0,0,1105,1,549,1,392,384,392,21002,392,1,1,21102,1,18,2,21102,3,1,3,21101,0,161,0,1106,0,549,1101,0,0,384,20001,388,390,1,20101,0,389,2,21102,1,180,0,1106,0,578,1206,1,213,1208,1,2,381,1006,381,205,20001,388,390,1,21002,389,1,2,21102,1,205,0,1105,1,393,1002,390,-1,390,1102,1,1,384,20101,0,388,1,20001,389,391,2,21102,228,1,0,1105,1,578,1206,1,261,1208,1,2,381,1006,381,253,21002,388,1,1,20001,389,391,2,21102,253,1,0,1105,1,393,1002,391,-1,391,1101,1,0,384,1005,384,161,20001,388,390,1,20001,389,391,2,21101,279,0,0,1106,0,578,1206,1,316,1208,1,2,381,1006,381,304,20001,388,390,1,20001,389,391,2,21102,304,1,0,1106,0,393,1002,390,-1,390,1002,391,-1,391,1102,1,1,384,1005,384,161,21002,388,1,1,21002,389,1,2,21101,0,0,3,21101,0,338,0,1105,1,549,1,388,390,388,1,389,391,389,20101,0,388,1,20101,0,389,2,21102,1,4,3,21102,1,365,0,1106,0,549,1007,389,19,381,1005,381,75,104,-1,104,0,104,0,99,0,1,0,0,0,0,0,0,318,20,15,1,1,22,109,3,22101,0,-2,1,21202,-1,1,2,21102,1,0,3,21102,1,414,0,1106,0,549,21201,-2,0,1,22102,1,-1,2,21101,429,0,0,1106,0,601,2101,0,1,435,1,386,0,386,104,-1,104,0,4,386,1001,387,-1,387,1005,387,451,99,109,-3,2105,1,0,109,8,22202,-7,-6,-3,22201,-3,-5,-3,21202,-4,64,-2,2207,-3,-2,381,1005,381,492,21202,-2,-1,-1,22201,-3,-1,-3,2207,-3,-2,381,1006,381,481,21202,-4,8,-2,2207,-3,-2,381,1005,381,518,21202,-2,-1,-1,22201,-3,-1,-3,2207,-3,-2,381,1006,381,507,2207,-3,-4,381,1005,381,540,21202,-4,-1,-1,22201,-3,-1,-3,2207,-3,-4,381,1006,381,529,22101,0,-3,-7,109,-8,2106,0,0,109,4,1202,-2,44,566,201,-3,566,566,101,639,566,566,1201,-1,0,0,204,-3,204,-2,204,-1,109,-4,2106,0,0,109,3,1202,-1,44,593,201,-2,593,593,101,639,593,593,21002,0,1,-2,109,-3,2105,1,0,109,3,22102,20,-2,1,22201,1,-1,1,21102,443,1,2,21101,114,0,3,21102,1,880,4,21102,1,630,0,1106,0,456,21201,1,1519,-2,109,-3,2105,1,0,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,1,1,0,2,2,0,2,2,2,2,2,0,2,2,2,0,0,0,2,2,2,2,2,2,0,2,2,0,0,2,2,2,0,2,2,2,2,0,0,2,0,2,2,0,1,1,0,2,2,0,2,2,2,2,2,0,2,2,0,2,2,2,0,0,0,2,0,2,2,0,0,2,2,2,0,2,2,2,2,2,0,2,0,0,2,2,0,0,1,1,0,2,2,0,2,2,2,2,2,2,0,2,2,2,2,0,2,2,0,0,2,0,0,0,2,0,0,2,2,2,2,2,0,2,2,0,2,2,2,0,2,0,1,1,0,2,2,2,2,0,0,2,2,2,2,2,2,2,2,0,0,2,2,2,0,2,2,2,0,2,2,0,0,0,2,2,2,2,2,2,0,2,2,2,2,0,1,1,0,0,2,2,2,2,2,2,0,2,0,2,2,0,2,0,2,0,2,0,2,2,2,0,2,0,0,0,2,0,2,2,0,0,2,2,2,2,2,0,2,0,1,1,0,0,2,2,2,2,2,2,2,2,0,2,2,0,0,2,0,2,0,2,0,0,2,2,0,0,2,2,0,0,0,2,0,2,0,2,2,2,0,2,2,0,1,1,0,0,0,0,0,0,2,0,2,0,0,2,2,0,2,2,0,0,2,0,0,2,2,2,2,2,2,2,2,0,2,2,0,0,0,2,2,2,2,2,2,0,1,1,0,0,2,2,2,2,0,0,0,0,2,0,0,2,2,0,2,2,2,2,2,2,0,2,2,2,0,2,2,2,0,2,2,2,2,2,2,0,2,0,2,0,1,1,0,2,0,2,2,2,2,2,0,0,2,2,2,2,2,0,2,2,2,2,2,2,0,2,0,0,0,2,2,0,2,0,0,2,0,2,2,2,2,2,2,0,1,1,0,2,2,2,2,2,0,2,2,2,2,2,2,2,0,2,2,2,2,2,0,2,2,2,2,2,2,0,2,2,2,2,2,2,0,0,0,2,2,0,2,0,1,1,0,0,0,0,2,2,2,2,2,2,2,0,2,2,2,2,2,0,2,0,2,2,0,2,2,0,2,2,2,0,2,2,0,0,2,0,2,0,2,2,0,0,1,1,0,0,2,2,2,2,0,2,0,2,2,0,2,2,2,2,2,2,0,2,0,0,0,0,2,0,2,2,2,2,2,0,0,2,0,2,0,0,0,0,0,0,1,1,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,1,1,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,4,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,1,1,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,1,1,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,1,1,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,3,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,1,1,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,1,56,72,33,24,73,85,29,45,48,12,58,96,32,17,48,17,9,49,78,6,48,98,91,23,28,37,16,54,30,88,69,69,23,59,33,61,89,94,79,64,42,13,82,45,98,57,57,80,6,2,67,86,26,84,80,47,72,38,22,73,75,4,3,47,96,34,52,22,26,90,74,84,37,68,37,73,86,59,27,67,52,22,63,92,41,46,68,93,55,3,6,78,32,82,8,34,41,77,29,92,77,81,17,90,40,50,27,80,82,96,66,21,67,96,69,12,39,65,93,7,91,97,55,95,64,9,91,48,23,44,96,67,38,43,26,70,64,17,47,98,35,54,89,31,67,18,36,42,52,19,71,4,47,21,43,77,6,64,45,94,49,52,54,85,9,78,73,44,56,3,37,15,45,16,78,98,82,27,59,13,26,75,73,18,74,20,63,65,56,55,98,34,10,97,55,70,51,25,9,16,10,79,49,58,13,92,19,25,79,33,48,5,78,86,94,48,39,3,43,90,35,45,56,60,51,92,4,52,64,63,18,70,44,82,70,29,72,53,91,36,75,95,57,61,42,79,98,26,8,73,10,3,69,95,69,39,13,70,90,66,96,97,21,35,38,43,21,79,91,5,92,93,48,25,31,15,39,58,51,68,46,93,10,56,16,5,54,34,54,68,22,97,18,14,96,52,92,62,62,62,43,62,73,41,85,36,81,81,1,41,92,94,78,32,72,15,30,54,86,1,60,28,20,94,15,52,60,68,63,15,45,39,66,65,42,35,28,31,83,59,87,69,83,22,58,45,22,70,86,98,44,13,37,24,67,80,7,67,16,10,88,54,60,76,97,37,63,31,61,91,10,61,97,76,59,40,28,15,45,50,86,61,30,11,85,87,53,10,88,40,69,82,60,57,38,74,35,44,33,98,80,47,3,51,56,12,28,86,26,91,45,10,92,18,63,4,66,47,73,18,57,51,32,79,25,41,61,68,78,34,71,3,33,29,40,25,15,72,88,51,20,76,70,10,20,38,13,27,92,97,60,22,54,73,20,51,27,87,51,41,73,61,1,31,94,11,74,56,34,9,74,31,20,91,63,75,1,54,62,31,30,60,74,67,13,83,65,10,63,38,65,75,94,85,98,53,59,63,42,21,93,13,55,36,76,53,14,30,71,2,84,16,82,87,57,74,57,29,48,14,73,4,22,91,81,94,41,67,27,82,20,4,89,43,92,36,70,29,45,82,65,49,2,63,78,18,13,75,76,50,85,64,37,4,57,41,18,15,65,70,44,85,72,11,36,35,84,4,70,49,47,20,10,80,79,59,89,1,87,5,22,87,31,23,38,35,49,71,33,46,81,64,43,59,46,51,62,33,89,61,66,64,92,23,30,56,17,71,85,18,2,72,2,42,31,13,53,35,17,91,73,73,48,95,20,26,23,10,65,4,40,6,79,49,84,7,15,49,90,45,24,42,76,21,97,3,63,42,30,92,55,38,44,53,67,44,42,36,28,9,17,66,92,44,51,55,57,59,6,50,52,97,21,45,19,17,21,76,86,32,23,56,78,93,97,13,93,87,32,83,89,23,21,63,40,87,83,95,95,74,57,60,82,48,45,18,93,63,74,31,30,43,50,28,69,60,43,81,86,67,64,17,67,27,79,49,92,21,71,59,32,83,29,72,3,62,47,95,76,63,32,53,32,28,75,50,22,37,43,20,10,13,80,80,19,43,55,23,14,70,32,80,4,44,4,40,35,44,55,41,68,80,68,25,27,97,39,30,24,42,52,88,87,36,23,83,58,50,85,60,97,72,97,51,37,83,40,59,52,25,83,8,76,14,20,94,43,45,75,47,12,67,46,56,30,74,1,28,41,42,74,21,36,22,80,69,23,12,62,25,39,77,8,46,56,64,43,34,8,54,85,43,20,84,24,13,64,92,68,7,61,49,46,16,87,54,24,94,70,63,63,33,43,30,29,34,22,23,98,20,90,14,77,27,89,39,13,3,77,47,462045";
 
-pub fn solve_a() {
-    let mut program = IntCodeProgram::from_str(PUZZLE_INPUT);
+#[derive(Debug, Clone, Copy, PartialEq, Eq, AocTile)]
+enum Tile {
+    #[tile(' ')]
+    Empty,
+    #[tile('#')]
+    Wall,
+    #[tile('=')]
+    Block,
+    #[tile('_')]
+    Paddle,
+    #[tile('o')]
+    Ball,
+}
 
-    program.run_to_end();
-    let mut count = 0;
-    for i in (2..program.outputs.len()).step_by(3) {
-        if program.outputs[i] == 2 {
-            count += 1;
+impl Tile {
+    fn from_id(id: i64) -> Tile {
+        match id {
+            0 => Tile::Empty,
+            1 => Tile::Wall,
+            2 => Tile::Block,
+            3 => Tile::Paddle,
+            4 => Tile::Ball,
+            _ => panic!("Invalid tile id: {}", id),
         }
     }
+}
 
-    assert_eq!(count, 318);
-    println!("Solution A: {}", count);
+/// The game and what it has drawn so far. Cloning it makes a save state.
+#[derive(Clone)]
+struct Arcade {
+    program: IntCodeProgram,
+    screen: SparseGrid<Tile>,
+    score: i64,
+    ball: Option<Point2>,
+    paddle: Option<Point2>,
 }
 
+impl Arcade {
+    /// Loads the game, with enough quarters inserted to play it if `free_play` is set
+    fn new(free_play: bool) -> Arcade {
+        let mut program = IntCodeProgram::from_str(PUZZLE_INPUT);
+        if free_play {
+            program.command.insert(0, 2);
+        }
+        Arcade { program, screen: SparseGrid::new(Tile::Empty), score: 0, ball: None, paddle: None }
+    }
 
-pub fn solve_b() {
-    let mut program = IntCodeProgram::from_str(PUZZLE_INPUT);
-    let mut score = -1;
-    program.command.insert(0, 2);
-
-    while !program.finished {
-        program.run();
-
-        if program.outputs.len() == 3 {
-            let x = program.outputs[0];
-            let z = program.outputs[2];
-
-            if x == -1 {
-                score = z;
-            } else {
-                match z {
-                    3 => {
-                        program.paddle = Some(x);
+    /// Runs the game until it wants the joystick, drawing whatever it outputs. Returns false once
+    /// the game is over.
+    fn run(&mut self) -> bool {
+        loop {
+            match self.program.run() {
+                Status::Output if self.program.outputs.len() == 3 => {
+                    let (x, y, z) = (self.program.outputs[0], self.program.outputs[1], self.program.outputs[2]);
+                    self.program.outputs.clear();
+                    if (x, y) == (-1, 0) {
+                        self.score = z;
+                        continue;
                     }
-                    4 => {
-                        program.ball = Some(x);
+
+                    let tile = Tile::from_id(z);
+                    let point = Point2::new([x, y]);
+                    match tile {
+                        Tile::Ball => self.ball = Some(point),
+                        Tile::Paddle => self.paddle = Some(point),
+                        _ => {}
                     }
-                    _ => {}
+                    self.screen.insert(point, tile);
                 }
+                Status::Output => {}
+                Status::Input => return true,
+                Status::Halted => return false,
             }
+        }
+    }
 
-            program.outputs.clear();
+    /// Tilts the joystick left (-1) or right (1), or leaves it in the middle (0), for the next frame
+    fn tilt(&mut self, joystick: i64) {
+        self.program.inputs.push_back(joystick);
+    }
+
+    /// Where to tilt the joystick to keep the paddle under the ball
+    fn autopilot(&self) -> i64 {
+        match (self.ball, self.paddle) {
+            (Some(ball), Some(paddle)) => (ball.x() - paddle.x()).signum(),
+            _ => 0,
         }
     }
 
+    fn blocks(&self) -> usize {
+        self.screen.count(&Tile::Block)
+    }
+}
+
+#[aoc(year = 2019, day = 13, part = 1)]
+pub fn solve_a() {
+    let mut arcade = Arcade::new(false);
+    arcade.run();
+    let count = arcade.blocks();
+
+    assert_eq!(count, 318);
+    println!("Solution A: {}", count);
+}
+
+
+#[aoc(year = 2019, day = 13, part = 2)]
+pub fn solve_b() {
+    let mut arcade = Arcade::new(true);
+    while arcade.run() {
+        arcade.tilt(arcade.autopilot());
+    }
+    let score = arcade.score;
+
     assert_eq!(score, 16309);
     println!("Solution B: {}", score);
 }
 
+/// Plays the game in the terminal. The arrow keys (or `a` and `d`) tilt the joystick and any other
+/// key leaves it in the middle for a frame. `p` switches the autopilot on and off, `k` keeps a save
+/// state, `r` restores it and `q` quits. The autopilot plays a frame every 30ms, or as many
+/// milliseconds as the first argument says.
+#[aoc(year = 2019, day = 13, tool = "arcade")]
+pub fn play(args: &[String]) -> Result<(), String> {
+    let frame_time = match args.first() {
+        Some(arg) => arg.parse::<u64>().map_err(|e| format!("Could not parse frame time {}: {}", arg, e))?,
+        None => 30,
+    };
+
+    let terminal = Terminal::open()?;
+    let mut arcade = Arcade::new(true);
+    let mut saved: Option<Arcade> = None;
+    let mut autopilot = false;
+
+    loop {
+        let running = arcade.run();
+        let status = match (running, autopilot) {
+            (false, _) => "GAME OVER",
+            (true, true) => "AUTOPILOT",
+            (true, false) => "",
+        };
+        terminal.draw(&format!("{}\nScore: {:<8} Blocks: {:<5} {}\n[arrows/a/d] move  [p] autopilot  [k] save  [r] restore  [q] quit",
+                               arcade.screen, arcade.score, arcade.blocks(), status))?;
+
+        let key = if running && autopilot {
+            terminal.poll(Duration::from_millis(frame_time))?
+        } else {
+            Some(terminal.key()?)
+        };
+
+        match key {
+            Some(Key::Char('q') | Key::Esc) => break,
+            Some(Key::Char('p')) => autopilot = !autopilot,
+            Some(Key::Char('k')) if running => saved = Some(arcade.clone()),
+            Some(Key::Char('r')) => if let Some(state) = &saved {
+                arcade = state.clone();
+            },
+            _ if !running => break,
+            _ if autopilot => arcade.tilt(arcade.autopilot()),
+            Some(Key::Left | Key::Char('a')) => arcade.tilt(-1),
+            Some(Key::Right | Key::Char('d')) => arcade.tilt(1),
+            _ => arcade.tilt(0),
+        }
+    }
+
+    Ok(())
+}
+
+/// Why the program stopped running
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Status {
+    Output,
+    Input,
+    Halted,
+}
+
+#[derive(Clone)]
 struct IntCodeProgram {
     command: CommandMap,
+    inputs: VecDeque<i64>,
     outputs: Vec<i64>,
     finished: bool,
     ptr: usize,
     offset: i64,
-    paddle: Option<i64>,
-    ball: Option<i64>,
 }
 
 impl IntCodeProgram {
@@ -71,24 +203,16 @@ impl IntCodeProgram {
 
         IntCodeProgram {
             command,
+            inputs: VecDeque::new(),
             outputs: Vec::new(),
             finished: false,
             ptr: 0,
             offset: 0,
-            paddle: None,
-            ball: None,
         }
     }
 
-    /// Runs till HALTED op code.
-    pub fn run_to_end(&mut self) {
-        while !self.finished {
-            self.run();
-        }
-    }
-
-    /// Runs and stops either at OUTPUT or HALTED op code.
-    pub fn run(&mut self) {
+    /// Runs and stops at OUTPUT, at INPUT when there is no input left or at HALTED op code.
+    pub fn run(&mut self) -> Status {
         loop {
             let (op, p1, p2, p3) = decode_op(*self.command.get(&self.ptr).unwrap());
 
@@ -107,13 +231,8 @@ impl IntCodeProgram {
                     self.ptr += 4;
                 }
                 3 => {
-                    let value = match (self.ball, self.paddle) {
-                        (Some(ball), Some(paddle)) => {
-                            if ball > paddle { 1 } else if ball < paddle { -1 } else { 0 }
-                        }
-                        (_, _) => {
-                            panic!("Ball or paddle location unspecified")
-                        }
+                    let Some(value) = self.inputs.pop_front() else {
+                        return Status::Input;
                     };
 
                     self.save_value(p1, self.ptr + 1, value);
@@ -123,7 +242,7 @@ impl IntCodeProgram {
                     let value = self.get_value(p1, self.ptr + 1);
                     self.outputs.push(value);
                     self.ptr += 2;
-                    break;
+                    return Status::Output;
                 }
                 5 => {
                     if self.get_value(p1, self.ptr + 1) != 0 {
@@ -162,7 +281,7 @@ impl IntCodeProgram {
 
                 99 => {
                     self.finished = true;
-                    break;
+                    return Status::Halted;
                 }
                 _ => panic!("Invalid op code {op}")
             }
@@ -203,3 +322,29 @@ impl IntCodeProgram {
         };
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_save_state() {
+        let mut arcade = Arcade::new(true);
+        for _ in 0..200 {
+            arcade.run();
+            arcade.tilt(arcade.autopilot());
+        }
+
+        // the saved game carries on exactly like the one it was saved from
+        let mut saved = arcade.clone();
+        for _ in 0..200 {
+            for arcade in [&mut arcade, &mut saved] {
+                arcade.run();
+                arcade.tilt(arcade.autopilot());
+            }
+        }
+        assert_eq!(saved.screen, arcade.screen);
+        assert_eq!(saved.score, arcade.score);
+        assert!(arcade.blocks() < 318);
+    }
+}
@@ -1,5 +1,6 @@
 use std::collections::{HashMap, VecDeque};
 
+use aoc_macros::aoc;
 use regex::Regex;
 
 const PUZZLE_INPUT: &str = "2 LFPRM, 4 GPNQ => 2 VGZVD
@@ -182,6 +183,7 @@ impl FuelCalculator {
 }
 
 
+#[aoc(year = 2019, day = 14, part = 1)]
 pub fn solve_a() {
     let calculator = FuelCalculator::new(PUZZLE_INPUT);
 
@@ -191,6 +193,7 @@ pub fn solve_a() {
 }
 
 
+#[aoc(year = 2019, day = 14, part = 2)]
 pub fn solve_b() {
     let num_ores = 1_000_000_000_000_i64;
 
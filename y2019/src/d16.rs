@@ -1,5 +1,6 @@
 use std::collections::{HashMap, VecDeque};
 
+use aoc_macros::aoc;
 use itertools::Itertools;
 
 const PUZZLE_INPUT: &str = "59728776137831964407973962002190906766322659303479564518502254685706025795824872901465838782474078135479504351754597318603898249365886373257507600323820091333924823533976723324070520961217627430323336204524247721593859226704485849491418129908885940064664115882392043975997862502832791753443475733972832341211432322108298512512553114533929906718683734211778737511609226184538973092804715035096933160826733751936056316586618837326144846607181591957802127283758478256860673616576061374687104534470102346796536051507583471850382678959394486801952841777641763547422116981527264877636892414006855332078225310912793451227305425976335026620670455240087933409";
@@ -10,6 +11,7 @@ fn read_numbers(input: &str) -> Vec<i32> {
         .collect::<Vec<_>>()
 }
 
+#[aoc(year = 2019, day = 16, part = 1)]
 pub fn solve_a() {
     let mut nums = read_numbers(PUZZLE_INPUT);
     let ans = format!("{:.8}", convolve(&mut nums, 100));
@@ -56,6 +58,7 @@ fn get_constant_row(row_num: usize, length: usize) -> VecDeque<i32> {
     vec
 }
 
+#[aoc(year = 2019, day = 16, part = 2)]
 pub fn solve_b() {
     let ans = solve_big(PUZZLE_INPUT);
     assert_eq!(ans, "84024125");
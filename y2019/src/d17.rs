@@ -1,10 +1,16 @@
-use std::collections::HashMap;
+use std::collections::HashSet;
+use std::fmt::{Display, Formatter};
+use std::fs;
+
+use aoc_macros::aoc;
 
 use crate::d15::IntCodeProgram;
+use crate::terminal::{Key, Terminal};
 
 const PUZZLE_INPUT: &str = "1,330,331,332,109,3546,1101,0,1182,15,1101,1481,0,24,1001,0,0,570,1006,570,36,102,1,571,0,1001,570,-1,570,1001,24,1,24,1105,1,18,1008,571,0,571,1001,15,1,15,1008,15,1481,570,1006,570,14,21102,58,1,0,1106,0,786,1006,332,62,99,21101,0,333,1,21101,0,73,0,1106,0,579,1101,0,0,572,1101,0,0,573,3,574,101,1,573,573,1007,574,65,570,1005,570,151,107,67,574,570,1005,570,151,1001,574,-64,574,1002,574,-1,574,1001,572,1,572,1007,572,11,570,1006,570,165,101,1182,572,127,1002,574,1,0,3,574,101,1,573,573,1008,574,10,570,1005,570,189,1008,574,44,570,1006,570,158,1105,1,81,21102,1,340,1,1106,0,177,21102,1,477,1,1106,0,177,21101,0,514,1,21102,1,176,0,1105,1,579,99,21102,1,184,0,1106,0,579,4,574,104,10,99,1007,573,22,570,1006,570,165,102,1,572,1182,21102,375,1,1,21101,211,0,0,1106,0,579,21101,1182,11,1,21101,0,222,0,1106,0,979,21102,388,1,1,21102,1,233,0,1106,0,579,21101,1182,22,1,21102,1,244,0,1106,0,979,21101,0,401,1,21102,255,1,0,1106,0,579,21101,1182,33,1,21102,266,1,0,1105,1,979,21102,414,1,1,21102,1,277,0,1105,1,579,3,575,1008,575,89,570,1008,575,121,575,1,575,570,575,3,574,1008,574,10,570,1006,570,291,104,10,21102,1,1182,1,21102,1,313,0,1105,1,622,1005,575,327,1102,1,1,575,21101,0,327,0,1106,0,786,4,438,99,0,1,1,6,77,97,105,110,58,10,33,10,69,120,112,101,99,116,101,100,32,102,117,110,99,116,105,111,110,32,110,97,109,101,32,98,117,116,32,103,111,116,58,32,0,12,70,117,110,99,116,105,111,110,32,65,58,10,12,70,117,110,99,116,105,111,110,32,66,58,10,12,70,117,110,99,116,105,111,110,32,67,58,10,23,67,111,110,116,105,110,117,111,117,115,32,118,105,100,101,111,32,102,101,101,100,63,10,0,37,10,69,120,112,101,99,116,101,100,32,82,44,32,76,44,32,111,114,32,100,105,115,116,97,110,99,101,32,98,117,116,32,103,111,116,58,32,36,10,69,120,112,101,99,116,101,100,32,99,111,109,109,97,32,111,114,32,110,101,119,108,105,110,101,32,98,117,116,32,103,111,116,58,32,43,10,68,101,102,105,110,105,116,105,111,110,115,32,109,97,121,32,98,101,32,97,116,32,109,111,115,116,32,50,48,32,99,104,97,114,97,99,116,101,114,115,33,10,94,62,118,60,0,1,0,-1,-1,0,1,0,0,0,0,0,0,1,12,18,0,109,4,2102,1,-3,587,20101,0,0,-1,22101,1,-3,-3,21101,0,0,-2,2208,-2,-1,570,1005,570,617,2201,-3,-2,609,4,0,21201,-2,1,-2,1106,0,597,109,-4,2106,0,0,109,5,2102,1,-4,630,20102,1,0,-2,22101,1,-4,-4,21101,0,0,-3,2208,-3,-2,570,1005,570,781,2201,-4,-3,653,20102,1,0,-1,1208,-1,-4,570,1005,570,709,1208,-1,-5,570,1005,570,734,1207,-1,0,570,1005,570,759,1206,-1,774,1001,578,562,684,1,0,576,576,1001,578,566,692,1,0,577,577,21101,0,702,0,1105,1,786,21201,-1,-1,-1,1106,0,676,1001,578,1,578,1008,578,4,570,1006,570,724,1001,578,-4,578,21102,731,1,0,1105,1,786,1106,0,774,1001,578,-1,578,1008,578,-1,570,1006,570,749,1001,578,4,578,21102,1,756,0,1105,1,786,1105,1,774,21202,-1,-11,1,22101,1182,1,1,21101,0,774,0,1106,0,622,21201,-3,1,-3,1106,0,640,109,-5,2106,0,0,109,7,1005,575,802,21001,576,0,-6,20102,1,577,-5,1106,0,814,21102,1,0,-1,21102,0,1,-5,21102,0,1,-6,20208,-6,576,-2,208,-5,577,570,22002,570,-2,-2,21202,-5,59,-3,22201,-6,-3,-3,22101,1481,-3,-3,2101,0,-3,843,1005,0,863,21202,-2,42,-4,22101,46,-4,-4,1206,-2,924,21102,1,1,-1,1105,1,924,1205,-2,873,21102,35,1,-4,1105,1,924,2101,0,-3,878,1008,0,1,570,1006,570,916,1001,374,1,374,1202,-3,1,895,1101,0,2,0,2101,0,-3,902,1001,438,0,438,2202,-6,-5,570,1,570,374,570,1,570,438,438,1001,578,558,921,21002,0,1,-4,1006,575,959,204,-4,22101,1,-6,-6,1208,-6,59,570,1006,570,814,104,10,22101,1,-5,-5,1208,-5,35,570,1006,570,810,104,10,1206,-1,974,99,1206,-1,974,1101,0,1,575,21102,973,1,0,1105,1,786,99,109,-7,2105,1,0,109,6,21101,0,0,-4,21102,0,1,-3,203,-2,22101,1,-3,-3,21208,-2,82,-1,1205,-1,1030,21208,-2,76,-1,1205,-1,1037,21207,-2,48,-1,1205,-1,1124,22107,57,-2,-1,1205,-1,1124,21201,-2,-48,-2,1106,0,1041,21102,1,-4,-2,1106,0,1041,21101,0,-5,-2,21201,-4,1,-4,21207,-4,11,-1,1206,-1,1138,2201,-5,-4,1059,1202,-2,1,0,203,-2,22101,1,-3,-3,21207,-2,48,-1,1205,-1,1107,22107,57,-2,-1,1205,-1,1107,21201,-2,-48,-2,2201,-5,-4,1090,20102,10,0,-1,22201,-2,-1,-2,2201,-5,-4,1103,1202,-2,1,0,1105,1,1060,21208,-2,10,-1,1205,-1,1162,21208,-2,44,-1,1206,-1,1131,1105,1,989,21101,0,439,1,1106,0,1150,21102,477,1,1,1106,0,1150,21101,0,514,1,21102,1,1149,0,1105,1,579,99,21101,0,1157,0,1106,0,579,204,-2,104,10,99,21207,-3,22,-1,1206,-1,1138,2101,0,-5,1176,1201,-4,0,0,109,-6,2105,1,0,6,13,27,13,6,1,11,1,27,1,11,1,6,1,11,1,27,1,11,1,6,1,11,1,27,1,11,1,6,1,11,1,27,1,11,1,6,1,11,1,27,1,11,1,6,1,11,1,1,9,9,11,9,1,6,1,11,1,1,1,7,1,9,1,7,1,1,1,9,1,6,1,11,13,7,1,7,1,1,1,9,1,6,1,13,1,7,1,1,1,7,1,7,1,1,1,9,1,6,1,13,1,7,1,1,1,5,11,1,1,9,1,6,1,13,1,7,1,1,1,5,1,1,1,9,1,9,1,6,11,3,1,7,1,1,1,5,1,1,1,9,1,1,9,16,1,3,1,7,1,1,1,5,1,1,1,9,1,1,1,24,1,3,1,7,13,7,1,1,1,24,1,3,1,9,1,5,1,1,1,1,1,7,1,1,1,24,1,3,1,9,9,1,1,7,11,16,1,3,1,15,1,3,1,9,1,7,1,12,9,15,1,3,1,9,1,7,1,16,1,19,1,3,1,9,1,7,1,16,1,19,11,3,1,7,1,16,1,23,1,5,1,3,1,7,1,8,9,23,11,7,1,8,1,37,1,11,1,8,1,37,1,11,1,8,1,37,1,11,1,8,1,37,1,11,1,8,1,37,1,11,1,8,1,37,13,8,1,58,1,58,1,58,1,58,1,50,9,50";
 
 
+#[aoc(year = 2019, day = 17, part = 1)]
 pub fn solve_a() {
     let map = get_map(PUZZLE_INPUT);
 
@@ -13,7 +19,7 @@ pub fn solve_a() {
         let row = &map[i];
 
         'inner: for j in 1..=(row.len() - 2) {
-            if row[j] == '#' {
+            if is_scaffold(row[j]) {
                 for (ii, jj) in [
                     (i - 1, j),
                     (i + 1, j),
@@ -21,7 +27,7 @@ pub fn solve_a() {
                     (i, j + 1),
                 ] {
                     let next_row = &map[ii];
-                    if jj > next_row.len() || next_row[jj] != '#' {
+                    if jj > next_row.len() || !is_scaffold(next_row[jj]) {
                         continue 'inner;
                     }
                 }
@@ -45,18 +51,16 @@ fn get_map(input: &str) -> Vec<Vec<char>> {
     let mut map: Vec<Vec<char>> = vec![];
 
     let mut last = vec![];
-    for v in program.get_outputs() {
+    for &v in program.get_outputs().iter() {
         match v {
-            46 => { last.push('.'); }
             10 => {
                 map.push(last);
                 last = vec![];
             }
             _ => {
-                // also 35
-                last.push('#');
+                // '#' for scaffold, '.' for space and '^', 'v', '<' or '>' for the robot
+                last.push(v as u8 as char);
             }
-            // _ => { panic!("Invalid output code: {}", v) }
         }
     }
 
@@ -67,33 +71,206 @@ fn get_map(input: &str) -> Vec<Vec<char>> {
     map
 }
 
+fn is_scaffold(c: char) -> bool {
+    matches!(c, '#' | '^' | 'v' | '<' | '>')
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Facing {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Facing {
+    fn from_char(c: char) -> Option<Facing> {
+        match c {
+            '^' => Some(Facing::Up),
+            '>' => Some(Facing::Right),
+            'v' => Some(Facing::Down),
+            '<' => Some(Facing::Left),
+            _ => None,
+        }
+    }
+
+    fn as_char(&self) -> char {
+        match self {
+            Facing::Up => '^',
+            Facing::Right => '>',
+            Facing::Down => 'v',
+            Facing::Left => '<',
+        }
+    }
+
+    fn turn(&self, turn: Move) -> Facing {
+        match (self, turn) {
+            (Facing::Up, Move::Left) | (Facing::Down, Move::Right) => Facing::Left,
+            (Facing::Up, Move::Right) | (Facing::Down, Move::Left) => Facing::Right,
+            (Facing::Left, Move::Left) | (Facing::Right, Move::Right) => Facing::Down,
+            (Facing::Left, Move::Right) | (Facing::Right, Move::Left) => Facing::Up,
+            (_, Move::Forward(_)) => *self,
+        }
+    }
+
+    /// The (row, column) step one tile ahead
+    fn delta(&self) -> (i64, i64) {
+        match self {
+            Facing::Up => (-1, 0),
+            Facing::Right => (0, 1),
+            Facing::Down => (1, 0),
+            Facing::Left => (0, -1),
+        }
+    }
+}
+
+/// A movement the robot understands, written the way it reads them
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Move {
+    Left,
+    Right,
+    Forward(usize),
+}
+
+impl Display for Move {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Move::Left => write!(f, "L"),
+            Move::Right => write!(f, "R"),
+            Move::Forward(n) => write!(f, "{}", n),
+        }
+    }
+}
+
+/// The moves separated by commas, e.g. `R,8,L,10`
+fn format_moves(moves: &[Move]) -> String {
+    moves.iter().map(|m| m.to_string()).collect::<Vec<_>>().join(",")
+}
+
+/// The vacuum robot on its scaffold, remembering where it went and how it got there
+struct Robot {
+    map: Vec<Vec<char>>,
+    position: (i64, i64),
+    facing: Facing,
+    visited: HashSet<(i64, i64)>,
+    moves: Vec<Move>,
+}
+
+impl Robot {
+    fn new(mut map: Vec<Vec<char>>) -> Result<Robot, String> {
+        let (position, facing) = map.iter()
+                                    .enumerate()
+                                    .find_map(|(i, row)| row.iter().enumerate().find_map(|(j, &c)| {
+                                        Facing::from_char(c).map(|facing| ((i as i64, j as i64), facing))
+                                    }))
+                                    .ok_or("The robot is not on the map".to_string())?;
+        map[position.0 as usize][position.1 as usize] = '#';
+
+        Ok(Robot { map, position, facing, visited: HashSet::from([position]), moves: vec![] })
+    }
+
+    fn is_scaffold(&self, (i, j): (i64, i64)) -> bool {
+        i >= 0 && j >= 0 && self.map.get(i as usize).and_then(|row| row.get(j as usize)) == Some(&'#')
+    }
+
+    fn ahead(&self) -> (i64, i64) {
+        let (di, dj) = self.facing.delta();
+        (self.position.0 + di, self.position.1 + dj)
+    }
+
+    /// Whether there is scaffold to move on to when facing `facing`
+    fn can_face(&self, facing: Facing) -> bool {
+        let (di, dj) = facing.delta();
+        self.is_scaffold((self.position.0 + di, self.position.1 + dj))
+    }
+
+    fn turn(&mut self, turn: Move) {
+        self.facing = self.facing.turn(turn);
+        self.moves.push(turn);
+    }
+
+    /// Moves one tile ahead, unless that would take the robot off the scaffold
+    fn forward(&mut self) -> bool {
+        let next = self.ahead();
+        if !self.is_scaffold(next) {
+            return false;
+        }
+
+        self.position = next;
+        self.visited.insert(next);
+        match self.moves.last_mut() {
+            Some(Move::Forward(n)) => *n += 1,
+            _ => self.moves.push(Move::Forward(1)),
+        }
+        true
+    }
+
+    fn scaffold_count(&self) -> usize {
+        self.map.iter().flatten().filter(|&&c| c == '#').count()
+    }
+
+    /// Draws the map with the visited scaffold as `o`
+    fn render(&self) -> String {
+        self.map.iter()
+            .enumerate()
+            .map(|(i, row)| row.iter()
+                .enumerate()
+                .map(|(j, &c)| match (i as i64, j as i64) {
+                    p if p == self.position => self.facing.as_char(),
+                    p if self.visited.contains(&p) => 'o',
+                    _ => c,
+                })
+                .collect::<String>())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+/// Drives the robot over the scaffold in the terminal. The left and right arrow keys (or `a` and
+/// `d`) turn it and the up arrow (or `w`) moves it one tile forward, while the moves it has made are
+/// written out the way the movement routines take them. `q` quits. The map and the moves are
+/// written to the file named by the first argument, if there is one, on the way out.
+#[aoc(year = 2019, day = 17, tool = "drive")]
+pub fn drive(args: &[String]) -> Result<(), String> {
+    let mut robot = Robot::new(get_map(PUZZLE_INPUT))?;
+    let scaffold = robot.scaffold_count();
+
+    let terminal = Terminal::open()?;
+    loop {
+        let status = if robot.visited.len() == scaffold { "ALL SCAFFOLD VISITED" } else { "" };
+        terminal.draw(&format!("{}\nVisited: {}/{} {}\nMoves: {}\n[left/right/a/d] turn  [up/w] forward  [q] quit",
+                               robot.render(), robot.visited.len(), scaffold, status, format_moves(&robot.moves)))?;
+
+        match terminal.key()? {
+            Key::Char('q') | Key::Esc => break,
+            Key::Left | Key::Char('a') => robot.turn(Move::Left),
+            Key::Right | Key::Char('d') => robot.turn(Move::Right),
+            Key::Up | Key::Char('w') => {
+                robot.forward();
+            }
+            _ => {}
+        }
+    }
+
+    drop(terminal);
+    if let Some(path) = args.first() {
+        fs::write(path, format!("{}\n{}\n", robot.render(), format_moves(&robot.moves)))
+            .map_err(|e| format!("Could not write map to {}: {}", path, e))?;
+        println!("Map written to {}", path);
+    }
+    Ok(())
+}
+
+#[aoc(year = 2019, day = 17, part = 2)]
 pub fn solve_b() {
-    /*
-        Solution:
-        R,8,L,12,R,8,R,8,L,12,R,8,L,10,L,10,R,8,L,12,L,12,L,10,R,10,L,10,L,10,R,8,L,12,L,12,L,10,R,10,L,10,L,10,R,8,R,8,L,12,R,8,L,12,L,12,L,10,R,10,R,8,L,12,R,8
-
-        R,8,L,12,R,8,R,8,L,12,R,8,L,10,L,10,R,8,L,12,L,12,L,10,R,10,L,10,L,10,R,8,L,12,L,12,L,10,R,10,L,10,L,10,R,8,R,8,L,12,R,8,L,12,L,12,L,10,R,10,R,8,L,12,R,8
-        L,10,L,10,R,8,L,12,L,12,L,10,R,10,L,10,L,10,R,8,L,12,L,12,L,10,R,10,L,10,L,10,R,8,R,8,L,12,R,8,L,12,L,12,L,10,R,10,R,8,L,12,R,8
-        L,12,L,12,L,10,R,10,L,10,L,10,R,8,L,12,L,12,L,10,R,10,L,10,L,10,R,8,R,8,L,12,R,8,L,12,L,12,L,10,R,10,R,8,L,12,R,8
-        L,10,L,10,R,8,L,12,L,12,L,10,R,10,L,10,L,10,R,8,R,8,L,12,R,8,L,12,L,12,L,10,R,10,R,8,L,12,R,8
-        L,12,L,12,L,10,R,10,L,10,L,10,R,8,R,8,L,12,R,8,L,12,L,12,L,10,R,10,R,8,L,12,R,8
-        L,10,L,10,R,8,R,8,L,12,R,8,L,12,L,12,L,10,R,10,R,8,L,12,R,8
-        R,8,L,12,R,8,L,12,L,12,L,10,R,10,R,8,L,12,R,8
-        L,12,L,12,L,10,R,10,R,8,L,12,R,8
-        R,8,L,12,R,8
-
-        main = A,A,B,C,B,C,B,A,C,A
-        A = R,8,L,12,R,8
-        B = L,10,L,10,R,8
-        C = L,12,L,12,L,10,R,10
-    */
+    let path = extract_path(get_map(PUZZLE_INPUT)).unwrap_or_else(|e| panic!("{}", e));
+    let routines = compress(&path, FUNCTIONS, 20).unwrap_or_else(|| panic!("Could not split {} into routines", format_moves(&path)));
+    assert_eq!(routines.expand(), path);
+
     let input = PUZZLE_INPUT.to_string().replacen("1", "2", 1);
     let mut program = IntCodeProgram::from_str(&input);
-
-    for mut raw_input in check_solution() {
-        raw_input.push_str("\n");
-
-        let inp = raw_input.chars().map(|c| c as i64).collect::<Vec<_>>();
+    for line in routines.lines().iter().chain(&["n".to_string()]) {
+        let inp = format!("{}\n", line).chars().map(|c| c as i64).collect::<Vec<_>>();
         program.append_inputs(&inp);
     }
 
@@ -107,34 +284,165 @@ pub fn solve_b() {
     println!("Solution B: {}", ans);
 }
 
-fn check_solution() -> [String; 5] {
-    let directions = "R,8,L,12,R,8,R,8,L,12,R,8,L,10,L,10,R,8,L,12,L,12,L,10,R,10,L,10,L,10,R,8,L,12,L,12,L,10,R,10,L,10,L,10,R,8,R,8,L,12,R,8,L,12,L,12,L,10,R,10,R,8,L,12,R,8";
-    let main = "A,A,B,C,B,C,B,A,C,A";
+/// Walks the robot from its start to the end of the scaffold, going straight on at every crossing
+/// and turning only where the scaffold does. Fails if that leaves some of the scaffold unvisited.
+fn extract_path(map: Vec<Vec<char>>) -> Result<Vec<Move>, String> {
+    let mut robot = Robot::new(map)?;
+    loop {
+        if robot.forward() {
+            continue;
+        }
+        match [Move::Left, Move::Right].into_iter().find(|&turn| robot.can_face(robot.facing.turn(turn))) {
+            Some(turn) => robot.turn(turn),
+            None => break,
+        }
+    }
 
-    let map = HashMap::from([
-        ("A", "R,8,L,12,R,8"),
-        ("B", "L,10,L,10,R,8"),
-        ("C", "L,12,L,12,L,10,R,10"),
-    ]);
+    if robot.visited.len() != robot.scaffold_count() {
+        return Err(format!("The path {} misses some of the scaffold", format_moves(&robot.moves)));
+    }
+    Ok(robot.moves)
+}
+
+/// How many movement functions the robot asks for
+const FUNCTIONS: usize = 3;
+
+/// A path split into movement functions and a main routine of the indices of the functions to call
+#[derive(Debug, PartialEq, Eq)]
+struct Routines {
+    main: Vec<usize>,
+    functions: Vec<Vec<Move>>,
+}
+
+impl Routines {
+    fn expand(&self) -> Vec<Move> {
+        self.main.iter().flat_map(|&f| self.functions[f].iter().copied()).collect()
+    }
+
+    /// The main routine followed by the functions, as the robot asks for them. The robot always
+    /// asks for `FUNCTIONS` functions, so any that weren't needed are left empty.
+    fn lines(&self) -> Vec<String> {
+        let main = self.main.iter().map(|&f| ((b'A' + f as u8) as char).to_string()).collect::<Vec<_>>().join(",");
+        let mut lines = vec![main];
+        lines.extend(self.functions.iter().map(|moves| format_moves(moves)));
+        lines.resize(lines.len().max(FUNCTIONS + 1), String::new());
+        lines
+    }
+}
+
+/// Splits `path` into at most `functions` movement functions and a main routine calling them, with
+/// every routine at most `limit` characters long. Functions are tried shortest first and the first
+/// split that fits is returned.
+fn compress(path: &[Move], functions: usize, limit: usize) -> Option<Routines> {
+    fn search(path: &[Move], max_functions: usize, limit: usize, routines: &mut Routines) -> bool {
+        if path.is_empty() {
+            return true;
+        }
+        // each call takes a letter and a comma
+        if (routines.main.len() + 1) * 2 - 1 > limit {
+            return false;
+        }
 
-    let result = main.split(",")
-        .fold(String::new(), |acc, letter| {
-            let new = map[letter].to_string();
-            if acc.is_empty() {
-                new
-            } else {
-                format!("{},{}", acc, new)
+        for f in 0..routines.functions.len() {
+            if path.starts_with(&routines.functions[f]) {
+                routines.main.push(f);
+                if search(&path[routines.functions[f].len()..], max_functions, limit, routines) {
+                    return true;
+                }
+                routines.main.pop();
             }
-        });
+        }
 
-    assert_eq!(result.as_str(), directions);
+        if routines.functions.len() < max_functions {
+            let f = routines.functions.len();
+            for len in 1..=path.len() {
+                if format_moves(&path[..len]).len() > limit {
+                    break;
+                }
+                routines.functions.push(path[..len].to_vec());
+                routines.main.push(f);
+                if search(&path[len..], max_functions, limit, routines) {
+                    return true;
+                }
+                routines.main.pop();
+                routines.functions.pop();
+            }
+        }
+        false
+    }
 
-    [
-        main.to_string(),
-        map["A"].to_string(),
-        map["B"].to_string(),
-        map["C"].to_string(),
-        "\n\n".to_string(),  // final one to set end of stage
-    ]
+    let mut routines = Routines { main: vec![], functions: vec![] };
+    search(path, functions, limit, &mut routines).then_some(routines)
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "#######...#####
+#.....#...#...#
+#.....#...#...#
+......#...#...#
+......#...###.#
+......#.....#.#
+^########...#.#
+......#.#...#.#
+......#########
+........#...#..
+....#########..
+....#...#......
+....#...#......
+....#...#......
+....#####......";
+
+    fn example_map() -> Vec<Vec<char>> {
+        EXAMPLE.lines().map(|line| line.chars().collect()).collect()
+    }
+
+    #[test]
+    fn test_drive() {
+        let mut robot = Robot::new(example_map()).unwrap();
+
+        assert!(!robot.forward());
+        robot.turn(Move::Right);
+        for _ in 0..8 {
+            assert!(robot.forward());
+        }
+        robot.turn(Move::Right);
+        while robot.forward() {}
+
+        assert_eq!(format_moves(&robot.moves), "R,8,R,8");
+        assert_eq!(robot.position, (14, 8));
+        assert_eq!(robot.visited.len(), 17);
+        assert_eq!(robot.render().lines().nth(6), Some("ooooooooo...#.#"));
+    }
+
+    #[test]
+    fn test_extract_path() {
+        let path = extract_path(example_map()).unwrap();
+        assert_eq!(format_moves(&path), "R,8,R,8,R,4,R,4,R,8,L,6,L,2,R,4,R,4,R,8,R,8,R,8,L,6,L,2");
+
+        // scaffold the path never gets to
+        let mut map = example_map();
+        map[12][14] = '#';
+        assert!(extract_path(map).is_err());
+    }
+
+    #[test]
+    fn test_compress() {
+        let path = extract_path(example_map()).unwrap();
+        let routines = compress(&path, FUNCTIONS, 20).unwrap();
+        assert_eq!(routines.expand(), path);
+        assert_eq!(routines.lines(), vec!["A,A,B,B,C,B,B,A,A,C", "R,8", "R,4", "R,8,L,6,L,2"]);
+
+        let routines = compress(&path[..4], 1, 20).unwrap();
+        assert_eq!(routines.lines(), vec!["A,A", "R,8", "", ""]);
+
+        let routines = compress(&path, 3, 12).unwrap();
+        assert_eq!(routines.expand(), path);
+        assert!(routines.lines().iter().all(|line| line.len() <= 12));
+
+        assert_eq!(compress(&path, 2, 20), None);
+        assert_eq!(compress(&path, 3, 6), None);
+    }
+}
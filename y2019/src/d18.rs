@@ -1,5 +1,7 @@
-use std::cmp::Ordering;
-use std::collections::{BinaryHeap, BTreeSet, HashMap, HashSet, VecDeque};
+use std::collections::{BTreeSet, HashMap};
+
+use aoc_macros::aoc;
+use aoc_utils::search;
 
 type Graph = HashMap<char, HashMap<char, usize>>;
 
@@ -116,6 +118,7 @@ enum Tile {
     Node(char),
 }
 
+#[aoc(year = 2019, day = 18, part = 1)]
 pub fn solve_a() {
     let graph = parse_map_to_graph(PUZZLE_INPUT);
 
@@ -163,185 +166,95 @@ fn parse_grid_to_graph(grid: HashMap<Point, Tile>) -> Graph {
 }
 
 fn get_neighbour_nodes(grid: &HashMap<Point, Tile>, initial: Point) -> HashMap<char, usize> {
-    let mut nbr = HashMap::new();
-    let mut visited = HashSet::from([initial]);
-    let mut queue = VecDeque::from([(initial, 0)]);
-
-    while let Some((pt, step)) = queue.pop_front() {
-        for next in pt.neighbours() {
-            match grid.get(&next) {
-                Some(tile) => {
-                    match tile {
-                        Tile::Floor => {
-                            if !visited.contains(&next) {
-                                queue.push_back((next, step + 1))
-                            }
-                        }
-                        Tile::Node(c) => {
-                            nbr.insert(*c, step + 1);
-                        }
-                        _ => {}
-                    }
-                }
-                None => {}
-            }
-            visited.insert(next);
+    // walk along floor tiles, stopping at the first node on each path
+    let mut corridors = |&pt: &Point| -> Vec<(Point, usize)> {
+        if pt != initial && matches!(grid.get(&pt), Some(Tile::Node(_))) {
+            return vec![];
         }
-    }
 
-    nbr
-}
+        pt.neighbours()
+          .into_iter()
+          .filter(|next| matches!(grid.get(next), Some(Tile::Floor | Tile::Node(_))))
+          .map(|next| (next, 1))
+          .collect()
+    };
 
-#[derive(PartialEq, Eq)]
-struct SearchState {
-    steps: usize,
-    node: char,
-    keys: BTreeSet<char>,
+    search::bfs_distances(&mut corridors, initial)
+        .into_iter()
+        .filter_map(|(pt, steps)| match grid.get(&pt) {
+            Some(Tile::Node(c)) if pt != initial => Some((*c, steps)),
+            _ => None,
+        })
+        .collect()
 }
 
-impl Ord for SearchState {
-    fn cmp(&self, other: &Self) -> Ordering {
-        other.steps.cmp(&self.steps)
-            .then(self.keys.len().cmp(&other.keys.len()))
-    }
-}
+/// The robots' positions and the keys collected so far
+type SearchState = (Vec<char>, BTreeSet<char>);
 
-impl PartialOrd for SearchState {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.cmp(other))
-    }
-}
+/// The keys reachable from a node with a set of keys collected, and how far away they are
+type ReachableKeys = HashMap<(char, BTreeSet<char>), Vec<(char, usize)>>;
 
-impl SearchState {
-    fn cache_key(&self) -> (char, BTreeSet<char>) {
-        (self.node, self.keys.clone())
-    }
+fn search(graph: Graph, node: char) -> Option<usize> {
+    search_keys(&graph, vec![node])
 }
 
-
-fn search(graph: Graph, node: char) -> Option<usize> {
+/// Finds the fewest steps needed for the robots to collect every key, moving one robot at a time
+fn search_keys(graph: &Graph, robots: Vec<char>) -> Option<usize> {
     let num_keys = graph.iter().filter(|(c, _)| c.is_ascii_lowercase()).count();
 
-    // best distance given position (node) and types of keys collected (set of keys)
-    let mut distances: HashMap<(char, BTreeSet<char>), usize> = HashMap::new();
-
-    let mut queue = BinaryHeap::from([SearchState {
-        steps: 0,
-        node,
-        keys: BTreeSet::new(),
-    }]);
-
-    let mut cache: HashMap<(char, BTreeSet<char>), Vec<(char, usize)>> = HashMap::new();
-
-    while let Some(current) = queue.pop() {
-        if current.keys.len() == num_keys {
-            return Some(current.steps);
-        }
-
-        let cache_key = current.cache_key();
-        if let Some(&best_num_steps) = distances.get(&cache_key) {
-            if current.steps > best_num_steps {
-                continue;
-            }
-        }
+    let mut cache = ReachableKeys::new();
 
-        // gets a vector of keys and the respective distance from the current node
-        let entry = cache.entry(cache_key)
-            .or_insert_with(|| get_reachable_keys(&graph, &current.keys, current.node));
+    let mut moves = |(robots, keys): &SearchState| -> Vec<(SearchState, usize)> {
+        let mut next_states = Vec::new();
 
-        for &mut (next_node, cost) in entry {
-            let mut next_keys = current.keys.clone();
-            next_keys.insert(next_node);
-            let next_step = current.steps + cost;
+        for (robot_num, &robot_loc) in robots.iter().enumerate() {
+            let reachable = cache.entry((robot_loc, keys.clone()))
+                                 .or_insert_with(|| get_reachable_keys(graph, keys, robot_loc));
 
-            // recorded node distance, if does not exist, use MAX
-            let node_distance = distances.entry((next_node, next_keys.clone())).or_insert(usize::MAX);
+            for &(next_node, cost) in reachable.iter() {
+                // the robot moves to the key and picks it up, the other robots stay where they are
+                let mut next_keys = keys.clone();
+                next_keys.insert(next_node);
 
-            if next_step < *node_distance {
-                *node_distance = next_step; // update distance
+                let mut next_robots = robots.clone();
+                next_robots[robot_num] = next_node;
 
-                queue.push(SearchState {
-                    steps: next_step,
-                    node: next_node,
-                    keys: next_keys,
-                });
+                next_states.push(((next_robots, next_keys), cost));
             }
         }
-    }
-
-    None
-}
-
-#[derive(PartialEq, Eq)]
-struct DjikstraState {
-    cost: usize,
-    node: char,
-}
 
-// make it comparable for the max-heap implementation
-// since we want the items that have travelled the least to be on the top of the heap
-// we need to "invert" the cost, so if other > self, self will be "Greater" and go on top
-impl Ord for DjikstraState {
-    fn cmp(&self, other: &Self) -> Ordering {
-        other.cost.cmp(&self.cost)
-            .then(self.node.cmp(&other.node))
-    }
-}
+        next_states
+    };
 
-impl PartialOrd for DjikstraState {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.cmp(other))
-    }
+    search::dijkstra(&mut moves, (robots, BTreeSet::new()), |(_, keys)| keys.len() == num_keys)
+        .map(|path| path.cost)
 }
 
 // Searches for all keys that can be reached from current node. Uses the Djikstra algorithm
 fn get_reachable_keys(graph: &Graph, keys: &BTreeSet<char>, start: char) -> Vec<(char, usize)> {
-    let mut dist = graph.keys().map(|c| (*c, usize::MAX)).collect::<HashMap<char, usize>>();
-
-    *dist.get_mut(&start).unwrap() = 0;
-    let mut heap = BinaryHeap::from([
-        DjikstraState { cost: 0, node: start }
-    ]);
+    let is_new_key = |node: char| node.is_ascii_lowercase() && !keys.contains(&node);
 
-    // keys that can be reached
-    let mut reach = HashSet::new();
-
-    while let Some(DjikstraState { cost, node }) = heap.pop() {
-        if node.is_ascii_lowercase() && !keys.contains(&node) {
-            // can reach this key
-            reach.insert(node);
-            continue;
+    let mut edges = |&node: &char| -> Vec<(char, usize)> {
+        if node != start && is_new_key(node) {
+            // stop at the key, the robot picks it up before going further
+            return vec![];
         }
 
-        if cost > dist[&node] {
-            // already found a better solution, skip this search route
-            continue;
-        }
-
-        for (&next_node, &next_cost) in graph.get(&node).unwrap() {
-            if next_node.is_ascii_uppercase() && !keys.contains(&next_node.to_ascii_lowercase()) {
-                // don't have key to door, can't pass
-                continue;
-            }
-
-            let next_cost = cost + next_cost;
-            if next_cost < dist[&next_node] {
-                // update the distance map, set the next node cost to this lower cost
-                dist.insert(next_node, next_cost);
-
-                // add the state back into the heap to search again
-                heap.push(DjikstraState {
-                    cost: next_cost,
-                    node: next_node,
-                });
-            }
-        }
-    }
+        graph[&node].iter()
+                    // don't have key to door, can't pass
+                    .filter(|(next, _)| !next.is_ascii_uppercase() || keys.contains(&next.to_ascii_lowercase()))
+                    .map(|(&next, &cost)| (next, cost))
+                    .collect()
+    };
 
-    reach.iter().map(|node| (*node, dist[&node])).collect()
+    search::dijkstra_all(&mut edges, start)
+        .into_iter()
+        .filter(|&(node, _)| node != start && is_new_key(node))
+        .collect()
 }
 
 
+#[aoc(year = 2019, day = 18, part = 2)]
 pub fn solve_b() {
     let graph = parse_map_to_graph(&modify_input(PUZZLE_INPUT));
 
@@ -375,103 +288,8 @@ fn modify_input(input: &str) -> String {
 }
 
 
-#[derive(PartialEq, Eq)]
-struct QuadrantSearchState {
-    steps: usize,
-    robots: [char; 4],
-    keys: BTreeSet<char>,
-}
-
-impl Ord for QuadrantSearchState {
-    fn cmp(&self, other: &Self) -> Ordering {
-        other.steps.cmp(&self.steps)
-            .then(self.keys.len().cmp(&other.keys.len()))
-    }
-}
-
-impl PartialOrd for QuadrantSearchState {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.cmp(other))
-    }
-}
-
-impl QuadrantSearchState {
-    fn cache_key(&self) -> ([char; 4], BTreeSet<char>) {
-        (self.robots, self.keys.clone())
-    }
-}
-
-
 fn quadrant_search(graph: &Graph) -> Option<usize> {
-    let num_keys = graph.iter().filter(|(c, _)| c.is_ascii_lowercase()).count();
-
-    let robots = ['@', '$', '%', '*'];
-
-    // best distance given position (node) and types of keys collected (set of keys)
-    let mut distances: HashMap<([char; 4], BTreeSet<char>), usize> = HashMap::from([
-        ((robots.clone(), BTreeSet::new()), 0)
-    ]);
-
-    let mut queue = BinaryHeap::from([
-        QuadrantSearchState {
-            steps: 0,
-            robots,
-            keys: BTreeSet::new(),
-        }
-    ]);
-
-
-    let mut cache: HashMap<(char, BTreeSet<char>), Vec<(char, usize)>> = HashMap::new();
-
-    while let Some(current) = queue.pop() {
-        if current.keys.len() == num_keys {
-            return Some(current.steps);
-        }
-
-        if let Some(&best_num_steps) = distances.get(&current.cache_key()) {
-            if current.steps > best_num_steps {
-                continue;
-            }
-        }
-
-        for (robot_num, &robot_loc) in current.robots.iter().enumerate() {
-            // get every reachable nodes for current robot
-            let entry = cache.entry((robot_loc, current.keys.clone()))
-                .or_insert_with(|| get_reachable_keys(&graph, &current.keys, robot_loc));
-
-            for &mut (next_node, cost) in entry {
-                // for each reachable node for the robot, set the robot's next location to this node
-                // and update its keys
-                let mut next_keys = current.keys.clone();
-                next_keys.insert(next_node);
-
-                // this lets the robot update its own position while keeping the other robot's position fixed
-                let mut next_robots = current.robots.clone();
-                next_robots[robot_num] = next_node;
-
-                // next distance cost
-                let next_steps = current.steps + cost;
-
-                // check if we have seen the next state
-                let distance = distances
-                    .entry((next_robots.clone(), next_keys.clone()))
-                    .or_insert(usize::MAX);
-
-                // if we haven't seen the next state or the next state we saved previously is not as good
-                // as the current one we found, we search out the next state
-                if next_steps < *distance {
-                    *distance = next_steps;
-                    queue.push(QuadrantSearchState {
-                        steps: next_steps,
-                        robots: next_robots,
-                        keys: next_keys,
-                    });
-                }
-            }
-        }
-    }
-
-    None
+    search_keys(graph, vec!['@', '$', '%', '*'])
 }
 
 
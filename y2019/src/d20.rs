@@ -1,6 +1,9 @@
 use std::cmp::max;
 use std::collections::{HashMap, HashSet, VecDeque};
 
+use aoc_macros::aoc;
+use aoc_utils::search;
+
 const PUZZLE_INPUT: &str = "
                                          V         C   O           F   O     A   K     H
                                          Q         U   B           P   C     A   M     Y
@@ -126,6 +129,7 @@ ZZ......#.............#.....#.#.#.#
                                                X   V           C     O     M       P
                                                E   A           T     E     V       A                                             ";
 
+#[aoc(year = 2019, day = 20, part = 1)]
 pub fn solve_a() {
     let map = form_map(PUZZLE_INPUT);
     let ans = bfs(&map);
@@ -135,40 +139,21 @@ pub fn solve_a() {
 
 fn bfs(map: &HashMap<Point, Tile>) -> Option<usize> {
     let start = *map.iter().find(|&(_, t)| *t == Tile::Start).unwrap().0;
-    let mut queue: VecDeque<(Point, usize)> = VecDeque::from([(start, 0)]);
-    let mut distances: HashMap<Point, usize> = HashMap::from([(start, 0)]);
-
-    while let Some((curr, n_steps)) = queue.pop_front() {
-        if let Some(dist) = distances.get_mut(&curr) {
-            if n_steps > *dist {
-                continue;
-            } else {
-                *dist = n_steps;
-            }
-        } else {
-            distances.insert(curr, n_steps);
-        }
 
-        for nbr in curr.neighbours() {
-            if let Some(tile) = map.get(&nbr) {
-                match tile {
-                    Tile::Floor => {
-                        queue.push_back((nbr, n_steps + 1));
-                    }
-                    Tile::Portal { loc: next_pt, code: _, is_edge: _ } => {
-                        // moving to that tile and then teleporting takes 2 steps
-                        queue.push_back((*next_pt, n_steps + 2))
-                    }
-                    Tile::Wall | Tile::Start => { continue; }
-                    Tile::End => {
-                        return Some(n_steps + 1);
-                    }
-                }
-            }
-        }
+    let mut moves = |curr: &Point| -> Vec<(Point, usize)> {
+        curr.neighbours()
+            .into_iter()
+            .filter_map(|nbr| match map.get(&nbr) {
+                Some(Tile::Floor | Tile::End) => Some((nbr, 1)),
+                // moving to that tile and then teleporting takes 2 steps
+                Some(Tile::Portal { loc: next_pt, .. }) => Some((*next_pt, 2)),
+                _ => None,
+            })
+            .collect()
     };
 
-    None
+    search::dijkstra(&mut moves, start, |pt| map.get(pt) == Some(&Tile::End))
+        .map(|path| path.cost)
 }
 
 #[derive(Debug, PartialEq, Eq, Clone)]
@@ -293,6 +278,7 @@ fn get_portal_code(grid: &Vec<Vec<char>>, pt: Point) -> Option<(String, Point)>
     None
 }
 
+#[aoc(year = 2019, day = 20, part = 2)]
 pub fn solve_b() {
     let map = form_map(PUZZLE_INPUT);
     let ans = bfs_with_levels(&map).unwrap();
@@ -308,66 +294,34 @@ struct SearchState {
 
 fn bfs_with_levels(map: &HashMap<Point, Tile>) -> Option<usize> {
     let start = *map.iter().find(|&(_, t)| *t == Tile::Start).unwrap().0;
-
     let initial_state = SearchState {
         loc: start,
         level: 0,
     };
 
-    let mut queue = VecDeque::from([(initial_state.clone(), 0, Point(usize::MAX, usize::MAX))]);
-    let mut distances: HashMap<SearchState, usize> = HashMap::from([(initial_state.clone(), 0)]);
-
-    while let Some((state, n_steps, last_pt)) = queue.pop_front() {
-        if let Some(dist) = distances.get_mut(&state) {
-            if n_steps > *dist {
-                continue;
-            } else {
-                *dist = n_steps;
-            }
-        } else {
-            distances.insert(state.clone(), n_steps);
-        }
-
-        let SearchState { loc, level } = state;
-
-        for nbr in loc.neighbours() {
-            if nbr == last_pt {
-                continue;
-            }
-
-            if let Some(tile) = map.get(&nbr) {
-                match tile {
-                    Tile::End if level == 0 => {
-                        // ending condition
-                        return Some(n_steps + 1);
-                    }
-                    Tile::Floor => {
-                        queue.push_back((SearchState {
-                            loc: nbr,
-                            level,
-                        }, n_steps + 1, loc));
-                    }
-                    Tile::Portal { code: _, is_edge, loc: next_pt } => {
-                        if level == 0 && *is_edge {
-                            continue;
-                        }
-
-                        // go down when at inner box, go up when at outer edges
-                        let next_level = if *is_edge { level - 1 } else { level + 1 };
-
-                        // moving to that tile and then teleporting takes 2 steps
-                        queue.push_back((SearchState {
-                            loc: *next_pt,
-                            level: next_level,
-                        }, n_steps + 2, loc));
-                    }
-                    _ => { continue; }
-                }
-            }
-        }
+    let mut moves = |&SearchState { loc, level }: &SearchState| -> Vec<(SearchState, usize)> {
+        loc.neighbours()
+           .into_iter()
+           .filter_map(|nbr| match map.get(&nbr) {
+               Some(Tile::Floor) => Some((SearchState { loc: nbr, level }, 1)),
+               // the exit only works on the outermost level
+               Some(Tile::End) if level == 0 => Some((SearchState { loc: nbr, level }, 1)),
+               // the outer portals are walls on the outermost level
+               Some(Tile::Portal { is_edge: true, .. }) if level == 0 => None,
+               Some(Tile::Portal { is_edge, loc: next_pt, .. }) => {
+                   // go down when at inner box, go up when at outer edges
+                   let next_level = if *is_edge { level - 1 } else { level + 1 };
+
+                   // moving to that tile and then teleporting takes 2 steps
+                   Some((SearchState { loc: *next_pt, level: next_level }, 2))
+               }
+               _ => None,
+           })
+           .collect()
     };
 
-    None
+    search::dijkstra(&mut moves, initial_state, |state| map.get(&state.loc) == Some(&Tile::End))
+        .map(|path| path.cost)
 }
 
 
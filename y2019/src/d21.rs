@@ -1,13 +1,13 @@
+use aoc_macros::aoc;
+
 use crate::d15::IntCodeProgram;
+use crate::springscript::{Formula, Hull, MAX_INSTRUCTIONS, Mode, parse_failure, Script, search_formula, search_hulls};
 
 const PUZZLE_INPUT: &str = "109,2050,21101,966,0,1,21101,0,13,0,1106,0,1378,21101,0,20,0,1105,1,1337,21102,27,1,0,1105,1,1279,1208,1,65,748,1005,748,73,1208,1,79,748,1005,748,110,1208,1,78,748,1005,748,132,1208,1,87,748,1005,748,169,1208,1,82,748,1005,748,239,21102,1,1041,1,21102,1,73,0,1105,1,1421,21102,1,78,1,21102,1041,1,2,21102,88,1,0,1106,0,1301,21102,1,68,1,21101,0,1041,2,21101,103,0,0,1105,1,1301,1101,0,1,750,1105,1,298,21101,0,82,1,21101,0,1041,2,21101,125,0,0,1105,1,1301,1102,2,1,750,1106,0,298,21102,1,79,1,21101,0,1041,2,21102,147,1,0,1105,1,1301,21102,1,84,1,21101,0,1041,2,21101,0,162,0,1106,0,1301,1101,0,3,750,1105,1,298,21101,0,65,1,21102,1,1041,2,21101,184,0,0,1105,1,1301,21101,0,76,1,21101,0,1041,2,21101,199,0,0,1105,1,1301,21101,0,75,1,21102,1041,1,2,21101,214,0,0,1105,1,1301,21101,0,221,0,1106,0,1337,21102,1,10,1,21101,1041,0,2,21102,1,236,0,1105,1,1301,1106,0,553,21102,1,85,1,21101,0,1041,2,21102,254,1,0,1105,1,1301,21101,0,78,1,21102,1041,1,2,21102,269,1,0,1106,0,1301,21101,276,0,0,1105,1,1337,21101,0,10,1,21102,1041,1,2,21101,291,0,0,1106,0,1301,1101,0,1,755,1105,1,553,21101,0,32,1,21101,1041,0,2,21101,0,313,0,1106,0,1301,21102,320,1,0,1106,0,1337,21101,327,0,0,1105,1,1279,2102,1,1,749,21101,65,0,2,21101,0,73,3,21101,346,0,0,1106,0,1889,1206,1,367,1007,749,69,748,1005,748,360,1101,1,0,756,1001,749,-64,751,1105,1,406,1008,749,74,748,1006,748,381,1101,0,-1,751,1105,1,406,1008,749,84,748,1006,748,395,1101,-2,0,751,1106,0,406,21102,1,1100,1,21102,406,1,0,1106,0,1421,21101,0,32,1,21102,1100,1,2,21101,0,421,0,1106,0,1301,21101,428,0,0,1105,1,1337,21101,435,0,0,1105,1,1279,1202,1,1,749,1008,749,74,748,1006,748,453,1101,0,-1,752,1105,1,478,1008,749,84,748,1006,748,467,1102,-2,1,752,1106,0,478,21101,1168,0,1,21102,1,478,0,1105,1,1421,21101,0,485,0,1106,0,1337,21101,0,10,1,21101,1168,0,2,21101,500,0,0,1105,1,1301,1007,920,15,748,1005,748,518,21101,1209,0,1,21102,518,1,0,1106,0,1421,1002,920,3,529,1001,529,921,529,1001,750,0,0,1001,529,1,537,1001,751,0,0,1001,537,1,545,1001,752,0,0,1001,920,1,920,1106,0,13,1005,755,577,1006,756,570,21102,1,1100,1,21102,570,1,0,1105,1,1421,21101,987,0,1,1106,0,581,21101,1001,0,1,21102,588,1,0,1105,1,1378,1101,0,758,594,101,0,0,753,1006,753,654,21002,753,1,1,21102,1,610,0,1105,1,667,21101,0,0,1,21102,621,1,0,1106,0,1463,1205,1,647,21101,0,1015,1,21101,0,635,0,1105,1,1378,21101,1,0,1,21102,1,646,0,1106,0,1463,99,1001,594,1,594,1106,0,592,1006,755,664,1102,1,0,755,1106,0,647,4,754,99,109,2,1102,726,1,757,21201,-1,0,1,21101,9,0,2,21102,697,1,3,21101,0,692,0,1105,1,1913,109,-2,2106,0,0,109,2,1002,757,1,706,1201,-1,0,0,1001,757,1,757,109,-2,2105,1,0,1,1,1,1,1,1,1,1,1,1,0,0,0,0,0,0,0,0,0,1,1,1,1,1,1,1,1,1,1,1,1,1,0,0,0,0,0,0,0,0,0,0,255,63,159,223,127,191,95,0,252,126,199,169,71,110,243,184,249,251,170,246,68,62,84,136,216,227,201,101,108,231,183,157,143,196,232,179,245,174,38,158,115,138,103,56,204,141,123,113,229,124,79,47,118,188,107,86,215,247,93,153,238,55,154,109,60,119,221,92,175,117,70,106,173,46,76,228,100,102,155,120,219,114,87,125,59,54,198,218,168,58,152,121,49,241,178,172,253,111,78,197,50,230,77,226,190,57,236,98,167,214,203,140,186,163,182,254,51,206,244,94,222,220,233,116,187,177,185,212,235,248,181,242,237,39,85,122,137,200,217,162,213,234,53,207,139,61,99,142,189,156,69,239,205,34,35,202,43,171,42,250,166,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,20,73,110,112,117,116,32,105,110,115,116,114,117,99,116,105,111,110,115,58,10,13,10,87,97,108,107,105,110,103,46,46,46,10,10,13,10,82,117,110,110,105,110,103,46,46,46,10,10,25,10,68,105,100,110,39,116,32,109,97,107,101,32,105,116,32,97,99,114,111,115,115,58,10,10,58,73,110,118,97,108,105,100,32,111,112,101,114,97,116,105,111,110,59,32,101,120,112,101,99,116,101,100,32,115,111,109,101,116,104,105,110,103,32,108,105,107,101,32,65,78,68,44,32,79,82,44,32,111,114,32,78,79,84,67,73,110,118,97,108,105,100,32,102,105,114,115,116,32,97,114,103,117,109,101,110,116,59,32,101,120,112,101,99,116,101,100,32,115,111,109,101,116,104,105,110,103,32,108,105,107,101,32,65,44,32,66,44,32,67,44,32,68,44,32,74,44,32,111,114,32,84,40,73,110,118,97,108,105,100,32,115,101,99,111,110,100,32,97,114,103,117,109,101,110,116,59,32,101,120,112,101,99,116,101,100,32,74,32,111,114,32,84,52,79,117,116,32,111,102,32,109,101,109,111,114,121,59,32,97,116,32,109,111,115,116,32,49,53,32,105,110,115,116,114,117,99,116,105,111,110,115,32,99,97,110,32,98,101,32,115,116,111,114,101,100,0,109,1,1005,1262,1270,3,1262,21002,1262,1,0,109,-1,2105,1,0,109,1,21102,1,1288,0,1105,1,1263,20102,1,1262,0,1102,0,1,1262,109,-1,2105,1,0,109,5,21101,1310,0,0,1105,1,1279,22101,0,1,-2,22208,-2,-4,-1,1205,-1,1332,22102,1,-3,1,21102,1,1332,0,1105,1,1421,109,-5,2105,1,0,109,2,21101,0,1346,0,1106,0,1263,21208,1,32,-1,1205,-1,1363,21208,1,9,-1,1205,-1,1363,1105,1,1373,21102,1,1370,0,1105,1,1279,1106,0,1339,109,-2,2105,1,0,109,5,2102,1,-4,1386,20101,0,0,-2,22101,1,-4,-4,21101,0,0,-3,22208,-3,-2,-1,1205,-1,1416,2201,-4,-3,1408,4,0,21201,-3,1,-3,1106,0,1396,109,-5,2106,0,0,109,2,104,10,21202,-1,1,1,21102,1,1436,0,1105,1,1378,104,10,99,109,-2,2106,0,0,109,3,20002,594,753,-1,22202,-1,-2,-1,201,-1,754,754,109,-3,2105,1,0,109,10,21102,5,1,-5,21101,0,1,-4,21102,0,1,-3,1206,-9,1555,21102,1,3,-6,21102,5,1,-7,22208,-7,-5,-8,1206,-8,1507,22208,-6,-4,-8,1206,-8,1507,104,64,1106,0,1529,1205,-6,1527,1201,-7,716,1515,21002,0,-11,-8,21201,-8,46,-8,204,-8,1106,0,1529,104,46,21201,-7,1,-7,21207,-7,22,-8,1205,-8,1488,104,10,21201,-6,-1,-6,21207,-6,0,-8,1206,-8,1484,104,10,21207,-4,1,-8,1206,-8,1569,21101,0,0,-9,1106,0,1689,21208,-5,21,-8,1206,-8,1583,21101,0,1,-9,1106,0,1689,1201,-5,716,1588,21002,0,1,-2,21208,-4,1,-1,22202,-2,-1,-1,1205,-2,1613,22101,0,-5,1,21102,1613,1,0,1105,1,1444,1206,-1,1634,22101,0,-5,1,21101,0,1627,0,1106,0,1694,1206,1,1634,21101,2,0,-3,22107,1,-4,-8,22201,-1,-8,-8,1206,-8,1649,21201,-5,1,-5,1206,-3,1663,21201,-3,-1,-3,21201,-4,1,-4,1105,1,1667,21201,-4,-1,-4,21208,-4,0,-1,1201,-5,716,1676,22002,0,-1,-1,1206,-1,1686,21102,1,1,-4,1106,0,1477,109,-10,2106,0,0,109,11,21102,0,1,-6,21101,0,0,-8,21102,1,0,-7,20208,-6,920,-9,1205,-9,1880,21202,-6,3,-9,1201,-9,921,1725,20102,1,0,-5,1001,1725,1,1732,21002,0,1,-4,21202,-4,1,1,21101,0,1,2,21102,9,1,3,21102,1,1754,0,1105,1,1889,1206,1,1772,2201,-10,-4,1767,1001,1767,716,1767,20101,0,0,-3,1105,1,1790,21208,-4,-1,-9,1206,-9,1786,22102,1,-8,-3,1106,0,1790,21201,-7,0,-3,1001,1732,1,1795,21002,0,1,-2,21208,-2,-1,-9,1206,-9,1812,22101,0,-8,-1,1106,0,1816,21202,-7,1,-1,21208,-5,1,-9,1205,-9,1837,21208,-5,2,-9,1205,-9,1844,21208,-3,0,-1,1105,1,1855,22202,-3,-1,-1,1105,1,1855,22201,-3,-1,-1,22107,0,-1,-1,1105,1,1855,21208,-2,-1,-9,1206,-9,1869,22101,0,-1,-8,1106,0,1873,22101,0,-1,-7,21201,-6,1,-6,1105,1,1708,21201,-8,0,-10,109,-11,2106,0,0,109,7,22207,-6,-5,-3,22207,-4,-6,-2,22201,-3,-2,-1,21208,-1,0,-6,109,-7,2106,0,0,0,109,5,2101,0,-2,1912,21207,-4,0,-1,1206,-1,1930,21101,0,0,-4,22102,1,-4,1,22102,1,-3,2,21102,1,1,3,21102,1,1949,0,1105,1,1954,109,-5,2106,0,0,109,6,21207,-4,1,-1,1206,-1,1977,22207,-5,-3,-1,1206,-1,1977,21201,-5,0,-5,1105,1,2045,21202,-5,1,1,21201,-4,-1,2,21202,-3,2,3,21102,1996,1,0,1105,1,1954,21202,1,1,-5,21102,1,1,-2,22207,-5,-3,-1,1206,-1,2015,21102,1,0,-2,22202,-3,-2,-3,22107,0,-4,-1,1206,-1,2037,22101,0,-2,1,21101,0,2037,0,106,0,1912,21202,-3,-1,-3,22201,-5,-3,-5,109,-6,2105,1,0";
 
-pub fn solve_a() {
-    // goal is to write a script less than 15 lines long where
-    // 1) if A, B, C, D are all true, robot walks and cause no damage to hull
-    // 2) if (A is empty) or (B and C are empty and D is not), jump
-
-    let script = "NOT B J
+/// Jumps if there is a hole right ahead, or one further on with ground to land on at D, i.e.
+/// `!A | (!B | !C) & D`
+const WALK_SCRIPT: &str = "NOT B J
 NOT C T
 OR T J
 AND D J
@@ -16,37 +16,133 @@ OR T J
 WALK
 ";
 
-    let mut program = IntCodeProgram::from_str(PUZZLE_INPUT);
-    let ans = run_bot(&mut program, script).unwrap();
+/// As walking, but only jumps early if there is ground at H to jump again from, i.e.
+/// `!A | (!B | !C) & D & H`
+const RUN_SCRIPT: &str = "NOT B J
+NOT C T
+OR T J
+AND D J
+AND H J
+NOT A T
+OR T J
+RUN
+";
+
+#[aoc(year = 2019, day = 21, part = 1)]
+pub fn solve_a() {
+    let ans = run_script(WALK_SCRIPT);
     println!("Solution A: {}", ans);
 }
 
-fn run_bot(program: &mut IntCodeProgram, script: &str) -> Option<i64> {
-    program.append_inputs(&script.chars().map(|e| e as i64).collect::<Vec<_>>());
+fn run_script(script: &str) -> i64 {
+    let script = script.parse::<Script>().unwrap_or_else(|e| panic!("{}", e));
+    run_bot(&script).unwrap_or_else(|hull| panic!("{} falls into\n{}", script, hull))
+}
 
+/// Runs the droid with `script`, returning the hull damage it reports or the hull it fell into
+fn run_bot(script: &Script) -> Result<i64, Hull> {
+    let mut program = IntCodeProgram::from_str(PUZZLE_INPUT);
+    program.append_inputs(&script.to_string().chars().map(|e| e as i64).collect::<Vec<_>>());
     while !program.done() {
         program.run();
     }
 
-    let mut output = program.get_outputs().clone();
-    let damage = output.pop();
+    let output = program.get_outputs();
+    match output.last() {
+        Some(&damage) if damage > 127 => Ok(damage),
+        _ => {
+            let report = output.iter().map(|c| *c as u8 as char).collect::<String>();
+            Err(parse_failure(&report).unwrap_or_else(|| panic!("Could not read the droid's report:\n{}", report)))
+        }
+    }
+}
 
-    println!("{}", output.iter().map(|c| *c as u8 as char).collect::<String>());
-    damage
+/// Starts with a script that never jumps and keeps searching for the shortest script that clears
+/// every hull the droid has fallen into so far, until it makes it across. Returns the script that
+/// did and the hull damage it reported.
+fn survey_hull(mode: Mode) -> (Script, i64) {
+    let mut hulls = Vec::new();
+    loop {
+        let script = search_hulls(mode, &hulls)
+            .unwrap_or_else(|| panic!("No script clears all of these hulls:\n{}", hulls.iter().map(|h| h.to_string()).collect::<Vec<_>>().join("\n")));
+
+        match run_bot(&script) {
+            Ok(damage) => return (script, damage),
+            Err(hull) => {
+                // the simulation has to agree with the droid, or the search would keep finding this script
+                assert!(script.cross(&hull).is_err(), "{} falls into\n{}\nbut not in the simulation", script, hull);
+                hulls.push(hull);
+            }
+        }
+    }
 }
 
+#[aoc(year = 2019, day = 21, part = 2)]
 pub fn solve_b() {
-    let script = "NOT B J
-NOT C T
-OR T J
-AND D J
-AND H J
-NOT A T
-OR T J
-RUN
-";
-
-    let mut program = IntCodeProgram::from_str(PUZZLE_INPUT);
-    let ans = run_bot(&mut program, script).unwrap();
+    let ans = run_script(RUN_SCRIPT);
     println!("Solution B: {}", ans);
 }
+
+/// Finds a script by trial and error against the droid rather than from a formula, e.g. `survey run`
+#[aoc(year = 2019, day = 21, tool = "survey")]
+pub fn survey(args: &[String]) -> Result<(), String> {
+    let mode = match args {
+        [mode] => parse_mode(mode)?,
+        _ => return Err("Expected a mode (walk or run)".to_string()),
+    };
+    let (script, damage) = survey_hull(mode);
+    print!("{}", script);
+    println!("Hull damage: {}", damage);
+    Ok(())
+}
+
+fn parse_mode(mode: &str) -> Result<Mode, String> {
+    match mode {
+        "walk" => Ok(Mode::Walk),
+        "run" => Ok(Mode::Run),
+        _ => Err(format!("Unknown mode {}, expected walk or run", mode)),
+    }
+}
+
+/// Prints a shortest script that jumps exactly when a formula over the sensors holds, e.g.
+/// `formula walk "!(A & B & C) & D"`
+#[aoc(year = 2019, day = 21, tool = "formula")]
+pub fn find_formula_script(args: &[String]) -> Result<(), String> {
+    let (mode, formula) = match args {
+        [mode, formula] => (mode, formula),
+        _ => return Err("Expected a mode (walk or run) and a formula".to_string()),
+    };
+    let mode = parse_mode(mode)?;
+    let formula = formula.parse::<Formula>()?;
+    if formula.sensors() > mode.sensors() {
+        // only walking has fewer sensors than a formula can name
+        return Err(format!("The droid has no sensor {} when it walks", (b'A' + formula.sensors() as u8 - 1) as char));
+    }
+
+    let script = search_formula(mode, |readings| formula.eval(readings))
+        .ok_or(format!("No script of at most {} instructions works out {}", MAX_INSTRUCTIONS, args[1]))?;
+    print!("{}", script);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scripts_follow_formulas() {
+        for (script, formula, mode) in [(WALK_SCRIPT, "!A | (!B | !C) & D", Mode::Walk),
+                                      (RUN_SCRIPT, "!A | (!B | !C) & D & H", Mode::Run)] {
+            let script = script.parse::<Script>().unwrap();
+            let formula = formula.parse::<Formula>().unwrap();
+            assert!((0..1u16 << mode.sensors()).all(|readings| script.jumps(readings) == formula.eval(readings)));
+        }
+    }
+
+    #[test]
+    fn test_survey_hull() {
+        let (script, damage) = survey_hull(Mode::Walk);
+        assert_eq!(damage, 19349530);
+        assert_eq!(run_bot(&script), Ok(damage));
+    }
+}
@@ -1,3 +1,5 @@
+use aoc_macros::aoc;
+use aoc_utils::Affine;
 use regex::Regex;
 
 const PUZZLE_INPUT: &str = "deal into new stack
@@ -129,163 +131,99 @@ fn form_shuffle(input: &str) -> Vec<Technique> {
         .collect::<Vec<_>>()
 }
 
-fn shuffle_deck(shuffle: &Vec<Technique>, num_cards: usize) -> Vec<usize> {
-    let mut cards = (0..num_cards).collect::<Vec<_>>();
-
-    for technique in shuffle {
-        match technique {
-            Technique::DealNewStack => {
-                cards.reverse();
-            }
-            Technique::Cut(n) => {
-                if *n == 0 {
-                    continue;
-                }
-
-                let (front, back) = if n.is_negative() {
-                    cards.split_at(cards.len() - (n.abs() as usize))
-                } else {
-                    cards.split_at(*n as usize)
-                };
-                cards = [back, front].concat();
-            }
-            Technique::DealIncrement(v) => {
-                let mut next = cards.clone();
-                let mut p = 0_usize;
+/// The map from the position of a card before `shuffle` to its position after
+fn shuffle_map(shuffle: &[Technique], num_cards: i128) -> Affine {
+    shuffle.iter()
+           .map(|technique| match technique {
+               Technique::DealNewStack => Affine::new(-1, -1, num_cards),
+               Technique::Cut(n) => Affine::new(1, -(*n as i128), num_cards),
+               Technique::DealIncrement(n) => Affine::new(*n as i128, 0, num_cards),
+           })
+           .fold(Affine::identity(num_cards), |map, technique| map.then(&technique))
+}
 
-                for i in 0..num_cards {
-                    next[p] = cards[i];
-                    p = (p + *v) % num_cards
-                }
+/// The cards in order after moving every card with `map`, which has to be a permutation
+fn lay_out_deck(map: &Affine) -> Vec<usize> {
+    let num_cards = *map.modulus() as usize;
 
-                cards = next;
-            }
-        }
+    let mut cards = vec![0; num_cards];
+    for card in 0..num_cards {
+        cards[map.apply(card as i128) as usize] = card;
     }
-
     cards
 }
 
+#[aoc(year = 2019, day = 22, part = 1)]
 pub fn solve_a() {
     let shuffle = form_shuffle(PUZZLE_INPUT);
-
-    let cards = shuffle_deck(&shuffle, 10007);
-    let ans = cards.iter().enumerate().find(|&(_, v)| *v == 2019).unwrap().0;
+    let ans = shuffle_map(&shuffle, 10007).apply(2019);
 
     println!("Solution A: {}", ans)
 }
 
+#[aoc(year = 2019, day = 22, part = 2)]
 pub fn solve_b() {
-    // copied solution from https://www.reddit.com/r/adventofcode/comments/ee0rqi/comment/fbnifwk/
     let shuffle = form_shuffle(PUZZLE_INPUT);
     let num_cards: i128 = 119315717514047;
     let repeats: i128 = 101741582076661;
 
-    let x = 2020;
-    let y = apply_reversed_shuffle(x, &shuffle, num_cards);
-    let z = apply_reversed_shuffle(y, &shuffle, num_cards);
-
-    let a = ((y - z) * modinv(x - y, num_cards)) % num_cards;
-    let b = (y - a * x) % num_cards;
-
-    let one = mod_power(a, repeats, num_cards) * x;
-    let two = mod_power(a, repeats, num_cards) - 1;
-    let three = modinv(a - 1, num_cards);
-
-    let ans = (one + mul_mod(mul_mod(two, three, num_cards), b, num_cards)) % num_cards;
+    // undoing the shuffles takes position 2020 back to the card that ends up there
+    let ans = shuffle_map(&shuffle, num_cards).pow(-repeats).unwrap().apply(2020);
 
     println!("Solution B: {}", ans);
 }
 
-fn apply_reversed_shuffle(x: i128, shuffle: &Vec<Technique>, num_cards: i128) -> i128 {
-    let mut x = x;
-    for technique in shuffle.iter().rev() {
-        match technique {
-            Technique::DealNewStack => {
-                x = rev_card_deal(x, num_cards);
-            }
-            Technique::Cut(v) => {
-                let v = *v as i128;
-                x = rev_card_cut(x, num_cards, v);
-            }
-            Technique::DealIncrement(v) => {
-                let v = *v as i128;
-                x = rev_card_increment(x, num_cards, v);
-            }
-        }
-    };
-    x
-}
-
-
-fn rev_card_deal(x: i128, num_cards: i128) -> i128 {
-    num_cards - x - 1
-}
-
-fn rev_card_cut(x: i128, num_cards: i128, cut: i128) -> i128 {
-    (x + cut + num_cards) % num_cards
-}
-
-fn rev_card_increment(x: i128, num_cards: i128, incr: i128) -> i128 {
-    (modinv(incr, num_cards) * x) % num_cards
-}
+/// Shuffles a deck of any size with the puzzle input, any number of times (negative undoes the
+/// shuffles), and reports where a card ends up, which card ends up at a position or the whole deck
+#[aoc(year = 2019, day = 22, tool = "shuffle")]
+pub fn query_shuffle(args: &[String]) -> Result<(), String> {
+    const USAGE: &str = "Usage: shuffle <cards> <times> (position <card> | card <position> | deck)";
 
-pub fn egcd(a: i128, b: i128) -> (i128, i128, i128) {
-    if a == 0 {
-        (b, 0, 1)
-    } else {
-        let (g, x, y) = egcd(b % a, a);
-        (g, y - (b / a) * x, x)
+    fn parse_arg(args: &[String], i: usize, name: &str) -> Result<i128, String> {
+        let arg = args.get(i).ok_or(USAGE.to_string())?;
+        arg.parse::<i128>().map_err(|e| format!("Could not parse {} {}: {}", name, arg, e))
     }
-}
 
-pub fn modinv(a: i128, m: i128) -> i128 {
-    let (g, x, _) = egcd(a, m);
-    if g != 1 {
-        panic!("modinv does not exist")
-    } else {
-        (x % m + m) % m
+    let num_cards = parse_arg(args, 0, "number of cards")?;
+    let times = parse_arg(args, 1, "number of shuffles")?;
+    if num_cards <= 0 || num_cards > i64::MAX as i128 {
+        return Err(format!("number of cards must be between 1 and {}", i64::MAX));
     }
-}
-
 
-fn mod_power(mut a: i128, mut b: i128, p: i128) -> i128 {
-    let mut res = 1;
-
-    a = a % p;
-    if a == 0 {
-        return 0;
-    }
-    while b > 0 {
-        if b & 1 == 1 {
-            res = (res * a) % p
+    let shuffle = form_shuffle(PUZZLE_INPUT);
+    let map = shuffle_map(&shuffle, num_cards)
+        .pow(times)
+        .ok_or(format!("shuffling {} cards can't be undone", num_cards))?;
+
+    match args.get(2).map(|s| s.as_str()) {
+        Some("position") => {
+            let card = parse_arg(args, 3, "card")?;
+            println!("{}", map.apply(card));
         }
-        b = b >> 1;
-        a = (a * a) % p
+        Some("card") => {
+            let position = parse_arg(args, 3, "position")?;
+            let inverse = map.inverse().ok_or(format!("more than one card ends up at {}", position))?;
+            println!("{}", inverse.apply(position));
+        }
+        Some("deck") => {
+            if num_cards > 100_000 {
+                return Err(format!("{} cards are too many to print", num_cards));
+            }
+            if map.inverse().is_none() {
+                return Err(format!("shuffling {} cards loses some of them", num_cards));
+            }
+            println!("{}", lay_out_deck(&map).iter().map(|c| c.to_string()).collect::<Vec<_>>().join(" "));
+        }
+        _ => return Err(USAGE.to_string()),
     }
-    res
-}
 
-fn mul_mod(mut a: i128, mut b: i128, m: i128) -> i128 {
-    if a >= m {
-        a %= m;
-    }
-    if b >= m {
-        b %= m;
-    }
-    let x = a;
-    let c = x * b / m;
-    let r = (a * b - c * m) % m;
-    if r < 0 {
-        r + m
-    } else {
-        r
-    }
+    Ok(())
 }
 
-
 #[cfg(test)]
 mod tests {
+    use aoc_utils::Affine;
+
     use super::form_shuffle;
 
     #[test]
@@ -322,7 +260,7 @@ cut -1";
 
     #[test]
     fn test_shuffle() {
-        use super::shuffle_deck;
+        use super::{lay_out_deck, shuffle_map};
 
         for (inp, exp) in [
             ("deal with increment 7
@@ -347,9 +285,26 @@ cut -1", "9 2 5 8 1 4 7 0 3 6")
         ] {
             let exp = exp.split(' ').map(|c| c.parse::<usize>().unwrap()).collect::<Vec<_>>();
             let shuffle = form_shuffle(inp);
-            let cards = shuffle_deck(&shuffle, 10);
+            let cards = lay_out_deck(&shuffle_map(&shuffle, 10));
 
             assert_eq!(cards, exp);
         }
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_repeated_shuffle() {
+        use super::{lay_out_deck, shuffle_map};
+
+        let shuffle = form_shuffle("deal with increment 7
+deal into new stack
+cut 3");
+        let map = shuffle_map(&shuffle, 11);
+
+        let mut deck = (0..11).collect::<Vec<_>>();
+        for times in 1..=12 {
+            deck = lay_out_deck(&map).iter().map(|&i| deck[i]).collect();
+            assert_eq!(lay_out_deck(&map.pow(times).unwrap()), deck, "{} shuffles", times);
+        }
+        assert_eq!(map.pow(5).unwrap().then(&map.pow(-5).unwrap()), Affine::identity(11));
+    }
+}
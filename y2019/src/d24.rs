@@ -1,6 +1,9 @@
-use std::collections::{HashMap, HashSet};
+use std::collections::HashMap;
 use std::fmt::{Display, Formatter};
 
+use aoc_macros::aoc;
+use aoc_utils::automaton::{self, Automaton, Topology};
+use aoc_utils::cycle;
 use itertools::Itertools;
 
 const PUZZLE_INPUT: &str = "####.
@@ -9,6 +12,7 @@ const PUZZLE_INPUT: &str = "####.
 .#.#.
 ##.##";
 
+#[derive(Clone)]
 struct Map1D {
     map: HashMap<usize, bool>,
     neighbours: HashMap<usize, Vec<usize>>,
@@ -86,32 +90,29 @@ impl Map1D {
 
 struct Eris {
     map: Map1D,
-    seen: HashSet<u64>,
 }
 
 impl Eris {
     fn new(input: &str) -> Eris {
-        let map = Map1D::new(input);
-        let seen = HashSet::from([map.biodiversity_score()]);
-
-        Eris { map, seen }
+        Eris { map: Map1D::new(input) }
     }
 
     fn get_first_repeated_map(&mut self) -> u64 {
-        loop {
-            self.map.evolve();
+        let evolve = |map: &Map1D| {
+            let mut next = map.clone();
+            next.evolve();
+            next
+        };
 
-            let score = self.map.biodiversity_score();
+        // the score sets one bit per bug, so it tells the maps apart
+        let (_, repeated) = cycle::find_repeat_by_key(self.map.clone(), evolve, Map1D::biodiversity_score);
+        self.map = repeated;
 
-            if self.seen.contains(&score) {
-                return score;
-            } else {
-                self.seen.insert(score);
-            }
-        }
+        self.map.biodiversity_score()
     }
 }
 
+#[aoc(year = 2019, day = 24, part = 1)]
 pub fn solve_a() {
     let mut eris = Eris::new(PUZZLE_INPUT);
     let ans = eris.get_first_repeated_map();
@@ -122,6 +123,7 @@ pub fn solve_a() {
 struct Level(Vec<bool>);
 
 impl Level {
+    #[cfg(test)]
     fn new() -> Level {
         Level(vec![false; 25])
     }
@@ -152,159 +154,6 @@ impl Level {
         self.0[index]
     }
 
-    fn evolve(&self, above: &Level, below: &Level) -> Level {
-        let mut next = Vec::from([false; 25]);
-
-        for (i, &has_bug) in self.0.iter().enumerate() {
-            let num_neighbours = match i {
-                0 => {
-                    // top left corner
-                    vec![
-                        above.get(7),
-                        above.get(11),
-                        self.get(i + 1),
-                        self.get(i + 5),
-                    ]
-                }
-                4 => {
-                    // top right corner
-                    vec![
-                        above.get(7),
-                        above.get(13),
-                        self.get(i - 1),
-                        self.get(i + 5),
-                    ]
-                }
-                20 => {
-                    // bottom left hand corner
-                    vec![
-                        above.get(17),
-                        above.get(11),
-                        self.get(i + 1),
-                        self.get(i - 5),
-                    ]
-                }
-                24 => {
-                    // bottom right hand corner
-                    vec![
-                        above.get(17),
-                        above.get(13),
-                        self.get(i - 1),
-                        self.get(i - 5),
-                    ]
-                }
-                1..=3 => {
-                    // top row
-                    vec![
-                        above.get(7),
-                        self.get(i - 1),
-                        self.get(i + 1),
-                        self.get(i + 5),
-                    ]
-                }
-                21..=23 => {
-                    // bottom row
-                    vec![
-                        above.get(17),
-                        self.get(i - 1),
-                        self.get(i + 1),
-                        self.get(i - 5),
-                    ]
-                }
-                _ if i % 5 == 0 => {
-                    // left column
-                    vec![
-                        above.get(11),
-                        self.get(i + 1),
-                        self.get(i - 5),
-                        self.get(i + 5),
-                    ]
-                }
-                _ if i % 5 == 4 => {
-                    // right column
-                    vec![
-                        above.get(13),
-                        self.get(i - 1),
-                        self.get(i - 5),
-                        self.get(i + 5),
-                    ]
-                }
-                12 => {
-                    // middle cell
-                    vec![false; 4]
-                }
-                7 => {
-                    // top of middle cell
-                    vec![
-                        self.get(i - 1),
-                        self.get(i + 1),
-                        self.get(i - 5),
-                        below.get(0),
-                        below.get(1),
-                        below.get(2),
-                        below.get(3),
-                        below.get(4),
-                    ]
-                }
-                17 => {
-                    // bottom of middle cell
-                    vec![
-                        self.get(i - 1),
-                        self.get(i + 1),
-                        self.get(i + 5),
-                        below.get(20),
-                        below.get(21),
-                        below.get(22),
-                        below.get(23),
-                        below.get(24),
-                    ]
-                }
-                11 => {
-                    // left of middle cell
-                    vec![
-                        self.get(i - 1),
-                        self.get(i + 5),
-                        self.get(i - 5),
-                        below.get(0),
-                        below.get(5),
-                        below.get(10),
-                        below.get(15),
-                        below.get(20),
-                    ]
-                }
-                13 => {
-                    // right of middle cell
-                    vec![
-                        self.get(i + 1),
-                        self.get(i + 5),
-                        self.get(i - 5),
-                        below.get(4),
-                        below.get(9),
-                        below.get(14),
-                        below.get(19),
-                        below.get(24),
-                    ]
-                }
-                _ => {
-                    vec![
-                        self.get(i + 1),
-                        self.get(i - 1),
-                        self.get(i + 5),
-                        self.get(i - 5),
-                    ]
-                }
-            }.into_iter().filter(|v| *v).count();
-
-            next[i] = if has_bug {
-                num_neighbours == 1
-            } else {
-                num_neighbours == 1 || num_neighbours == 2
-            };
-        }
-
-        Level(next)
-    }
-
     fn to_string(&self) -> String {
         (0..25).step_by(5)
             .map(|si| {
@@ -318,10 +167,6 @@ impl Level {
             })
             .join("\n")
     }
-
-    fn num_bugs(&self) -> usize {
-        self.0.iter().filter(|x| **x).count()
-    }
 }
 
 
@@ -331,45 +176,82 @@ impl Display for Level {
     }
 }
 
-struct Map2D {
-    levels: HashMap<i64, Level>,
-}
+/// The tiles of the recursive grids as `(depth, index)`, where the grid at `depth + 1` surrounds
+/// the one at `depth` and the grid at `depth - 1` fills its middle tile
+struct Recursive;
+
+impl Topology for Recursive {
+    type Cell = (i64, usize);
+
+    fn neighbours(&self, &(depth, idx): &(i64, usize)) -> Vec<(i64, usize)> {
+        let (row, col) = ((idx / 5) as i64, (idx % 5) as i64);
+        let mut neighbours = Vec::new();
+
+        for (dr, dc) in [(-1, 0), (1, 0), (0, -1), (0, 1)] {
+            let (r, c) = (row + dr, col + dc);
+
+            if !(0..5).contains(&r) || !(0..5).contains(&c) {
+                // off the edge onto the tile next to the middle of the outer grid
+                neighbours.push((depth + 1, ((2 + dr) * 5 + 2 + dc) as usize));
+            } else if (r, c) == (2, 2) {
+                // into the middle, which touches the whole facing edge of the inner grid
+                for k in 0..5 {
+                    let (r, c) = match (dr, dc) {
+                        (1, 0) => (0, k),
+                        (-1, 0) => (4, k),
+                        (0, 1) => (k, 0),
+                        _ => (k, 4),
+                    };
+                    neighbours.push((depth - 1, r * 5 + c));
+                }
+            } else {
+                neighbours.push((depth, (r * 5 + c) as usize));
+            }
+        }
 
-impl Map2D {
-    fn new(input: &str) -> Map2D {
-        Map2D { levels: HashMap::from([(0, Level::from_input(input))]) }
+        neighbours
     }
 
-    fn evolve_one_step(&mut self) {
-        // add 2 levels per iteration
-        self.levels.insert(self.levels.keys().max().unwrap() + 1, Level::new());
-        self.levels.insert(self.levels.keys().min().unwrap() - 1, Level::new());
+    fn degree(&self) -> usize {
+        // a background tile is on a grid far away, and away from the middle of it
+        4
+    }
+}
 
-        let mut next_levels = HashMap::new();
-        for (&idx, level) in self.levels.iter() {
-            let null_level = Level::new();
-            let above = self.levels.get(&(idx + 1)).unwrap_or(&null_level);
-            let below = self.levels.get(&(idx - 1)).unwrap_or(&null_level);
+struct Map2D {
+    bugs: Automaton<Recursive, bool>,
+}
 
-            let next = level.evolve(above, below);
-            next_levels.insert(idx, next);
-        }
+impl Map2D {
+    fn new(input: &str) -> Map2D {
+        let level = Level::from_input(input);
+        let bugs = (0..25).map(|idx| ((0, idx), level.get(idx)));
 
-        self.levels = next_levels;
+        Map2D { bugs: Automaton::unbounded(Recursive, bugs, false) }
     }
 
     fn evolve(&mut self, n: usize) {
-        for _ in 0..n {
-            self.evolve_one_step();
-        }
+        // a bug survives with exactly one neighbour, and an empty tile gets one with one or two
+        self.bugs.run(n, automaton::life_rule(&[1, 2], &[1]));
     }
 
     fn count_bugs(&self) -> usize {
-        self.levels.values().fold(0, |acc, v| acc + v.num_bugs())
+        self.bugs.count(&true)
+    }
+
+    /// The levels that have any bugs
+    #[cfg(test)]
+    fn levels(&self) -> HashMap<i64, Level> {
+        let mut levels = HashMap::new();
+        for ((depth, idx), _) in self.bugs.cells() {
+            levels.entry(depth).or_insert_with(Level::new).0[idx] = true;
+        }
+        levels
     }
 }
 
 
+#[aoc(year = 2019, day = 24, part = 2)]
 pub fn solve_b() {
     let mut map = Map2D::new(PUZZLE_INPUT);
     map.evolve(200);
@@ -513,8 +395,9 @@ mod tests {
 .#.#.
 ..#..")]);
 
-        for depth in map.levels.keys().sorted() {
-            let level = map.levels.get(depth).unwrap();
+        let levels = map.levels();
+        for depth in levels.keys().sorted() {
+            let level = levels.get(depth).unwrap();
             if !level.is_empty() {
                 let exp = Level::from_input(*expected.get(depth).unwrap()).to_string();
                 assert_eq!(level.to_string(), exp, "Depth: {}", depth);
@@ -1,6 +1,8 @@
 use std::collections::HashMap;
 use std::iter::Iterator;
 
+use aoc_macros::aoc;
+
 #[derive(Debug)]
 enum Direction {
     UP,
@@ -49,6 +51,7 @@ fn get_input(input: &str) -> (Input, Input) {
     (convert(wire1), convert(wire2))
 }
 
+#[aoc(year = 2019, day = 3, part = 1)]
 pub fn solve_a() {
     let (wire1, wire2) = get_input(PUZZLE_INPUT);
     let closest = closest_intersection_distance(&wire1, &wire2);
@@ -117,6 +120,7 @@ fn get_travelled_locations(wire: &Input) -> HashMap<(i32, i32), i32> {
 }
 
 
+#[aoc(year = 2019, day = 3, part = 2)]
 pub fn solve_b() {
     let (wire1, wire2) = get_input(PUZZLE_INPUT);
     let min_steps = minimum_steps_to_intersection(&wire1, &wire2);
@@ -1,6 +1,8 @@
 use std::collections::HashMap;
 use std::ops::Range;
 
+use aoc_macros::aoc;
+
 const RANGE: Range<i32> = 359282..820401;
 
 fn form_digits(num: i32) -> [i32; 6] {
@@ -14,6 +16,7 @@ fn form_digits(num: i32) -> [i32; 6] {
     ]
 }
 
+#[aoc(year = 2019, day = 4, part = 1)]
 pub fn solve_a() {
     let mut count = 0;
     'outer: for num in RANGE {
@@ -38,6 +41,7 @@ pub fn solve_a() {
     println!("Solution A: {}", count);
 }
 
+#[aoc(year = 2019, day = 4, part = 2)]
 pub fn solve_b() {
     let mut count = 0;
     'outer: for num in RANGE {
@@ -1,5 +1,7 @@
 use std::collections::{HashMap, HashSet, VecDeque};
 
+use aoc_macros::aoc;
+
 const PUZZLE_INPUT: &str = "CYJ)BQR
 KX8)YWJ
 45Z)R38
@@ -1741,6 +1743,7 @@ NCT)2KK
 ZD5)LHM
 WK4)DSM";
 
+#[aoc(year = 2019, day = 6, part = 1)]
 pub fn solve_a() {
     let orbits = get_orbits(PUZZLE_INPUT, true);
     let mut queue = VecDeque::from([("COM", 0)]);
@@ -1762,6 +1765,7 @@ pub fn solve_a() {
 }
 
 
+#[aoc(year = 2019, day = 6, part = 2)]
 pub fn solve_b() {
     let orbits = get_orbits(PUZZLE_INPUT, false);
 
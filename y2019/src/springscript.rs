@@ -0,0 +1,599 @@
+//! Springscript, the language the springdroid's jump logic is written in (2019 day 21). A script is
+//! a list of boolean instructions over the droid's ground sensors and its two writable registers,
+//! `T` and `J`, and the droid jumps whenever `J` ends up true. Scripts can be checked against a hull
+//! here without running the droid, and the shortest script for a job can be searched for.
+
+use std::collections::HashSet;
+use std::fmt::{Display, Formatter};
+use std::iter::Peekable;
+use std::str::{Chars, FromStr};
+
+/// The droid only has memory for this many instructions
+pub const MAX_INSTRUCTIONS: usize = 15;
+
+/// How many tiles a jump carries the droid forward
+const JUMP: usize = 4;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    Walk,
+    Run,
+}
+
+impl Mode {
+    /// The number of ground sensors, `A` to `D` when walking and `A` to `I` when running
+    pub fn sensors(&self) -> usize {
+        match self {
+            Mode::Walk => 4,
+            Mode::Run => 9,
+        }
+    }
+}
+
+/// A register, where `Sensor(0)` is `A`, the ground one tile ahead
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Register {
+    Sensor(usize),
+    T,
+    J,
+}
+
+impl Register {
+    fn parse(s: &str) -> Result<Register, String> {
+        match s {
+            "T" => Ok(Register::T),
+            "J" => Ok(Register::J),
+            _ if s.len() == 1 && ("A"..="I").contains(&s) => Ok(Register::Sensor((s.as_bytes()[0] - b'A') as usize)),
+            _ => Err(format!("{} is not a register", s)),
+        }
+    }
+}
+
+impl Display for Register {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Register::Sensor(i) => write!(f, "{}", (b'A' + *i as u8) as char),
+            Register::T => write!(f, "T"),
+            Register::J => write!(f, "J"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Op {
+    And,
+    Or,
+    Not,
+}
+
+/// `op x y`, which stores the result in `y`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Instruction {
+    pub op: Op,
+    pub x: Register,
+    pub y: Register,
+}
+
+impl Instruction {
+    fn apply(&self, x: bool, y: bool) -> bool {
+        match self.op {
+            Op::And => x && y,
+            Op::Or => x || y,
+            Op::Not => !x,
+        }
+    }
+}
+
+impl FromStr for Instruction {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parts = s.split_whitespace().collect::<Vec<_>>();
+        let [op, x, y] = parts[..] else {
+            return Err(format!("expected an operation and two registers: {}", s));
+        };
+
+        let op = match op {
+            "AND" => Op::And,
+            "OR" => Op::Or,
+            "NOT" => Op::Not,
+            _ => return Err(format!("{} is not an operation", op)),
+        };
+        Ok(Instruction { op, x: Register::parse(x)?, y: Register::parse(y)? })
+    }
+}
+
+impl Display for Instruction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let op = match self.op {
+            Op::And => "AND",
+            Op::Or => "OR",
+            Op::Not => "NOT",
+        };
+        write!(f, "{} {} {}", op, self.x, self.y)
+    }
+}
+
+/// A script the droid would accept: short enough, only writing to `T` and `J` and only reading the
+/// sensors it has in its mode
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Script {
+    mode: Mode,
+    instructions: Vec<Instruction>,
+}
+
+impl Script {
+    pub fn new(mode: Mode, instructions: Vec<Instruction>) -> Result<Script, String> {
+        if instructions.len() > MAX_INSTRUCTIONS {
+            return Err(format!("{} instructions is more than the {} that fit", instructions.len(), MAX_INSTRUCTIONS));
+        }
+        for instruction in instructions.iter() {
+            if let Register::Sensor(_) = instruction.y {
+                return Err(format!("{}: sensors can't be written to", instruction));
+            }
+            if let Register::Sensor(i) = instruction.x {
+                if i >= mode.sensors() {
+                    return Err(format!("{}: there are only {} sensors in {:?} mode", instruction, mode.sensors(), mode));
+                }
+            }
+        }
+
+        Ok(Script { mode, instructions })
+    }
+
+    /// Whether the droid jumps with these sensor readings, where bit `i` is set if there is ground
+    /// under sensor `i`
+    pub fn jumps(&self, sensors: u16) -> bool {
+        let (mut t, mut j) = (false, false);
+        for instruction in self.instructions.iter() {
+            let x = match instruction.x {
+                Register::Sensor(i) => sensors >> i & 1 == 1,
+                Register::T => t,
+                Register::J => j,
+            };
+            match instruction.y {
+                Register::T => t = instruction.apply(x, t),
+                _ => j = instruction.apply(x, j),
+            }
+        }
+        j
+    }
+
+    /// Runs the droid across `hull`, returning where it falls in if it does
+    pub fn cross(&self, hull: &Hull) -> Result<(), usize> {
+        hull.cross(|position| self.jumps(hull.sensors(position, self.mode)))
+    }
+}
+
+impl FromStr for Script {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut lines = s.lines().map(|line| line.trim()).filter(|line| !line.is_empty()).collect::<Vec<_>>();
+        let mode = match lines.pop() {
+            Some("WALK") => Mode::Walk,
+            Some("RUN") => Mode::Run,
+            _ => return Err("a script has to end with WALK or RUN".to_string()),
+        };
+
+        let instructions = lines.into_iter().map(|line| line.parse()).collect::<Result<Vec<_>, _>>()?;
+        Script::new(mode, instructions)
+    }
+}
+
+/// The text the droid expects, one instruction per line
+impl Display for Script {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for instruction in self.instructions.iter() {
+            writeln!(f, "{}", instruction)?;
+        }
+        writeln!(f, "{}", if self.mode == Mode::Walk { "WALK" } else { "RUN" })
+    }
+}
+
+/// A stretch of hull, where `true` is ground. The droid starts on the first tile, and there is
+/// ground everywhere past the end.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Hull(Vec<bool>);
+
+impl Hull {
+    fn ground(&self, position: usize) -> bool {
+        self.0.get(position).copied().unwrap_or(true)
+    }
+
+    /// What the sensors read with the droid at `position`
+    fn sensors(&self, position: usize, mode: Mode) -> u16 {
+        (0..mode.sensors()).filter(|&i| self.ground(position + i + 1))
+                           .fold(0, |sensors, i| sensors | 1 << i)
+    }
+
+    /// Runs a droid that decides whether to jump from each position with `jumps`, returning where
+    /// it falls in if it does
+    fn cross<F: Fn(usize) -> bool>(&self, jumps: F) -> Result<(), usize> {
+        let mut position = 0;
+        while position < self.0.len() {
+            position += if jumps(position) { JUMP } else { 1 };
+            if !self.ground(position) {
+                return Err(position);
+            }
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Hull {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.trim()
+         .chars()
+         .map(|c| match c {
+             '#' | '@' => Ok(true),
+             '.' => Ok(false),
+             _ => Err(format!("unknown hull tile {}", c)),
+         })
+         .collect::<Result<Vec<_>, _>>()
+         .map(Hull)
+    }
+}
+
+impl Display for Hull {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0.iter().map(|&ground| if ground { '#' } else { '.' }).collect::<String>())
+    }
+}
+
+/// The hull the droid fell into, from the report it prints when it doesn't make it across
+pub fn parse_failure(report: &str) -> Option<Hull> {
+    let (_, frames) = report.split_once("Didn't make it across:")?;
+
+    // the first frame's bottom row is the hull, the rows above it are air
+    frames.lines().find(|line| line.contains('#')).and_then(|line| line.parse().ok())
+}
+
+/// What a script has to do for the droid to succeed
+enum Goal<'a> {
+    /// Jump exactly for the readings that are `true`
+    Formula(Vec<bool>),
+    /// Cross every hull, given the index of the reading at every position of each
+    Hulls(&'a [Hull], Vec<Vec<usize>>),
+}
+
+/// A boolean formula over the sensors, e.g. `!(A & B & C) & D`. `!` binds tightest, then `&`,
+/// then `|`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Formula {
+    Sensor(usize),
+    Not(Box<Formula>),
+    And(Box<Formula>, Box<Formula>),
+    Or(Box<Formula>, Box<Formula>),
+}
+
+impl Formula {
+    /// The value of the formula, where bit `i` of `readings` is set if sensor `i` sees ground
+    pub fn eval(&self, readings: u16) -> bool {
+        match self {
+            Formula::Sensor(i) => readings >> i & 1 == 1,
+            Formula::Not(x) => !x.eval(readings),
+            Formula::And(x, y) => x.eval(readings) && y.eval(readings),
+            Formula::Or(x, y) => x.eval(readings) || y.eval(readings),
+        }
+    }
+
+    /// The number of sensors the droid needs for this formula
+    pub fn sensors(&self) -> usize {
+        match self {
+            Formula::Sensor(i) => i + 1,
+            Formula::Not(x) => x.sensors(),
+            Formula::And(x, y) | Formula::Or(x, y) => x.sensors().max(y.sensors()),
+        }
+    }
+
+    fn parse_or(tokens: &mut Peekable<Chars>) -> Result<Formula, String> {
+        let mut formula = Formula::parse_and(tokens)?;
+        while tokens.next_if_eq(&'|').is_some() {
+            formula = Formula::Or(Box::new(formula), Box::new(Formula::parse_and(tokens)?));
+        }
+        Ok(formula)
+    }
+
+    fn parse_and(tokens: &mut Peekable<Chars>) -> Result<Formula, String> {
+        let mut formula = Formula::parse_not(tokens)?;
+        while tokens.next_if_eq(&'&').is_some() {
+            formula = Formula::And(Box::new(formula), Box::new(Formula::parse_not(tokens)?));
+        }
+        Ok(formula)
+    }
+
+    fn parse_not(tokens: &mut Peekable<Chars>) -> Result<Formula, String> {
+        match tokens.next() {
+            Some('!') => Ok(Formula::Not(Box::new(Formula::parse_not(tokens)?))),
+            Some('(') => {
+                let formula = Formula::parse_or(tokens)?;
+                tokens.next_if_eq(&')').ok_or("Missing ')'".to_string())?;
+                Ok(formula)
+            }
+            Some(c @ 'A'..='I') => Ok(Formula::Sensor(c as usize - 'A' as usize)),
+            Some(c) => Err(format!("Expected a sensor, '!' or '(' but got '{}'", c)),
+            None => Err("Unexpected end of formula".to_string()),
+        }
+    }
+}
+
+impl FromStr for Formula {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.chars().filter(|c| !c.is_whitespace()).collect::<String>();
+        let mut tokens = s.chars().peekable();
+        let formula = Formula::parse_or(&mut tokens)?;
+        match tokens.next() {
+            Some(c) => Err(format!("Unexpected '{}' in formula {}", c, s)),
+            None => Ok(formula),
+        }
+    }
+}
+
+/// The values of a register for up to `64 * W` sensor readings, one bit per reading
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct Truth<const W: usize>([u64; W]);
+
+impl<const W: usize> Truth<W> {
+    fn new<F: Fn(usize) -> bool>(len: usize, value: F) -> Truth<W> {
+        let mut words = [0; W];
+        for i in (0..len).filter(|&i| value(i)) {
+            words[i / 64] |= 1 << (i % 64);
+        }
+        Truth(words)
+    }
+
+    fn get(&self, i: usize) -> bool {
+        self.0[i / 64] >> (i % 64) & 1 == 1
+    }
+
+    /// `op` on `self` and `y`, where `mask` has the bits of every reading set so that `NOT` leaves
+    /// the unused bits clear
+    fn apply(&self, op: Op, y: &Truth<W>, mask: &Truth<W>) -> Truth<W> {
+        let mut words = [0; W];
+        for (word, ((x, y), mask)) in words.iter_mut().zip(self.0.iter().zip(&y.0).zip(&mask.0)) {
+            *word = match op {
+                Op::And => x & y,
+                Op::Or => x | y,
+                Op::Not => !x & mask,
+            };
+        }
+        Truth(words)
+    }
+}
+
+/// Finds a shortest script for `goal` that only reads the sensors in `usable`, with the narrowest
+/// bit sets that fit the readings, as there can be millions of states to keep
+fn search(mode: Mode, usable: &[usize], readings: &[u16], goal: Goal) -> Option<Script> {
+    match readings.len() {
+        0..=64 => search_with::<1>(mode, usable, readings, goal),
+        65..=128 => search_with::<2>(mode, usable, readings, goal),
+        129..=256 => search_with::<4>(mode, usable, readings, goal),
+        _ => search_with::<8>(mode, usable, readings, goal),
+    }
+}
+
+/// Scripts are told apart only by what `T` and `J` hold for every reading, so this is a breadth
+/// first search over those pairs, a layer at a time. Each state only keeps the move that made it,
+/// and the script is rebuilt by following them back to the start.
+fn search_with<const W: usize>(mode: Mode, usable: &[usize], readings: &[u16], goal: Goal) -> Option<Script> {
+    let mask = Truth::<W>::new(readings.len(), |_| true);
+    let sensors = (0..mode.sensors())
+        .map(|s| Truth::<W>::new(readings.len(), |i| readings[i] >> s & 1 == 1))
+        .collect::<Vec<_>>();
+
+    let registers = usable.iter().map(|&s| Register::Sensor(s)).chain([Register::T, Register::J]).collect::<Vec<_>>();
+    let moves = [Op::And, Op::Or, Op::Not].into_iter()
+        .flat_map(|op| registers.iter().flat_map(move |&x| [Register::T, Register::J].map(|y| Instruction { op, x, y })))
+        .collect::<Vec<_>>();
+
+    let step = |(t, j): (Truth<W>, Truth<W>), instruction: &Instruction| {
+        let x = match instruction.x {
+            Register::Sensor(i) => sensors[i],
+            Register::T => t,
+            Register::J => j,
+        };
+        match instruction.y {
+            Register::T => (x.apply(instruction.op, &t, &mask), j),
+            _ => (t, x.apply(instruction.op, &j, &mask)),
+        }
+    };
+
+    let target = match &goal {
+        Goal::Formula(target) => Truth::new(readings.len(), |i| target[i]),
+        Goal::Hulls(..) => mask,
+    };
+    let accept = |j: &Truth<W>| match &goal {
+        Goal::Formula(_) => *j == target,
+        Goal::Hulls(hulls, indices) => hulls.iter()
+                                            .zip(indices.iter())
+                                            .all(|(hull, index)| hull.cross(|position| j.get(index[position])).is_ok()),
+    };
+
+    // accepting only looks at J, so every value of J is only tested once
+    let mut rejected = HashSet::new();
+    let mut accept = |j: &Truth<W>| !rejected.contains(j) && (accept(j) || !rejected.insert(*j));
+
+    // the move that made every state kept so far, as (index of the state it came from, move)
+    let start = (Truth([0; W]), Truth([0; W]));
+    let mut seen = HashSet::from([start]);
+    let mut parents: Vec<(u32, u8)> = vec![(0, 0)];
+    let mut layer = vec![(start, 0)];
+    let mut found = accept(&start.1).then_some((0, None));
+
+    for _ in 0..MAX_INSTRUCTIONS {
+        if found.is_some() || layer.is_empty() {
+            break;
+        }
+
+        // look for the goal before keeping the next layer, which would be the largest by far
+        found = layer.iter().find_map(|&(state, index)| {
+            moves.iter()
+                 .position(|instruction| instruction.y == Register::J && accept(&step(state, instruction).1))
+                 .map(|m| (index, Some(m)))
+        });
+        if found.is_some() {
+            break;
+        }
+
+        let mut next = Vec::new();
+        for &(state, index) in layer.iter() {
+            for (m, instruction) in moves.iter().enumerate() {
+                let after = step(state, instruction);
+                if seen.insert(after) {
+                    next.push((after, parents.len() as u32));
+                    parents.push((index, m as u8));
+                }
+            }
+        }
+        layer = next;
+    }
+
+    let (mut index, last) = found?;
+    let mut instructions = last.map(|m| moves[m]).into_iter().collect::<Vec<_>>();
+    while index != 0 {
+        let (parent, m) = parents[index as usize];
+        instructions.push(moves[m as usize]);
+        index = parent;
+    }
+    instructions.reverse();
+
+    Script::new(mode, instructions).ok()
+}
+
+/// Finds a shortest script that jumps exactly when `formula` holds for the sensor readings. Only
+/// the sensors the formula depends on are read, which keeps running mode's 512 readings down to the
+/// ones that matter.
+pub fn search_formula<F: Fn(u16) -> bool>(mode: Mode, formula: F) -> Option<Script> {
+    let all = (0..1u16 << mode.sensors()).collect::<Vec<_>>();
+    let usable = (0..mode.sensors())
+        .filter(|&s| all.iter().any(|&r| formula(r) != formula(r ^ 1 << s)))
+        .collect::<Vec<_>>();
+
+    // every combination of the usable sensors, with the others left as holes
+    let readings = all.into_iter()
+                      .filter(|&r| usable.iter().fold(r, |rest, s| rest & !(1 << s)) == 0)
+                      .collect::<Vec<_>>();
+    let target = readings.iter().map(|&r| formula(r)).collect();
+
+    search(mode, &usable, &readings, Goal::Formula(target))
+}
+
+/// Finds a shortest script that gets the droid across every one of `hulls`
+pub fn search_hulls(mode: Mode, hulls: &[Hull]) -> Option<Script> {
+    // only the readings the droid can see somewhere on these hulls matter
+    let mut readings = hulls.iter()
+                            .flat_map(|hull| (0..hull.0.len()).map(|position| hull.sensors(position, mode)))
+                            .collect::<Vec<_>>();
+    readings.sort();
+    readings.dedup();
+
+    let indices = hulls.iter()
+                       .map(|hull| (0..hull.0.len()).map(|position| readings.binary_search(&hull.sensors(position, mode)).unwrap()).collect())
+                       .collect();
+    search(mode, &(0..mode.sensors()).collect::<Vec<_>>(), &readings, Goal::Hulls(hulls, indices))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const REPORT: &str = "Input instructions:
+
+Walking...
+
+
+Didn't make it across:
+
+.................
+.................
+@................
+#####.#..########
+
+.................
+.................
+.................
+#####.#.@########
+";
+
+    #[test]
+    fn test_parse_script() {
+        let text = "NOT A J\nNOT B T\nAND D T\nOR T J\nWALK\n";
+        let script = text.parse::<Script>().unwrap();
+
+        assert_eq!(script.instructions.len(), 4);
+        assert_eq!(script.instructions[1], Instruction { op: Op::Not, x: Register::Sensor(1), y: Register::T });
+        assert_eq!(script.to_string(), text);
+
+        assert!("NOT A J".parse::<Script>().is_err());
+        assert!("NOT A B\nWALK".parse::<Script>().is_err());
+        assert!("NOT E J\nWALK".parse::<Script>().is_err());
+        assert!("NOT E J\nRUN".parse::<Script>().is_ok());
+        assert!("XOR A J\nWALK".parse::<Script>().is_err());
+        assert!("NOT K J\nRUN".parse::<Script>().is_err());
+        assert!(format!("{}WALK", "NOT A J\n".repeat(16)).parse::<Script>().is_err());
+        assert!(format!("{}WALK", "NOT A J\n".repeat(15)).parse::<Script>().is_ok());
+    }
+
+    #[test]
+    fn test_cross() {
+        let hull = parse_failure(REPORT).unwrap();
+        assert_eq!(hull.to_string(), "#####.#..########");
+
+        // jumping at the first hole lands in the second
+        let script = "NOT A J\nWALK".parse::<Script>().unwrap();
+        assert_eq!(script.cross(&hull), Err(8));
+
+        let script = "NOT C J\nAND D J\nNOT A T\nOR T J\nWALK".parse::<Script>().unwrap();
+        assert_eq!(script.cross(&hull), Ok(()));
+        assert!(script.jumps(0b1011));
+        assert!(!script.jumps(0b0011));
+    }
+
+    #[test]
+    fn test_search_formula() {
+        let script = search_formula(Mode::Walk, |s| s & 1 == 0).unwrap();
+        assert_eq!(script.to_string(), "NOT A J\nWALK\n");
+
+        // jump if there is a hole in A, B or C and ground at D
+        let formula = |s: u16| s & 0b111 != 0b111 && s & 0b1000 != 0;
+        let script = search_formula(Mode::Walk, formula).unwrap();
+        assert_eq!(script.instructions.len(), 5);
+        assert!((0..16).all(|s| script.jumps(s) == formula(s)));
+    }
+
+    #[test]
+    fn test_parse_formula() {
+        let formula = "!(A & B & C) & D".parse::<Formula>().unwrap();
+        assert_eq!(formula.sensors(), 4);
+        assert!((0..16).all(|s| formula.eval(s) == (s & 0b111 != 0b111 && s & 0b1000 != 0)));
+
+        // `!` binds tighter than `&`, which binds tighter than `|`
+        let formula = "!A | B & C".parse::<Formula>().unwrap();
+        assert!((0..8).all(|s| formula.eval(s) == (s & 1 == 0 || s & 0b110 == 0b110)));
+
+        let script = search_formula(Mode::Run, |s| formula.eval(s)).unwrap();
+        assert_eq!(script.instructions.len(), 4);
+        assert!((0..512).all(|s| script.jumps(s) == formula.eval(s)));
+
+        for bad in ["", "A &", "(A | B", "A B", "J", "a"] {
+            assert!(bad.parse::<Formula>().is_err(), "{}", bad);
+        }
+    }
+
+    #[test]
+    fn test_search_hulls() {
+        let hulls = ["#####.#..########", "#####..#.########", "#####...#########"]
+            .map(|hull| hull.parse::<Hull>().unwrap());
+
+        let script = search_hulls(Mode::Walk, &hulls).unwrap();
+        assert!(hulls.iter().all(|hull| script.cross(hull).is_ok()), "{}", script);
+        assert!(script.instructions.len() <= 4);
+
+        assert_eq!(search_hulls(Mode::Walk, &[]), Some(Script::new(Mode::Walk, vec![]).unwrap()));
+    }
+}
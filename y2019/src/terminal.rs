@@ -0,0 +1,101 @@
+//! Full screen terminal for the days that can be played or driven by hand. Keys are read on their
+//! own thread, so that a day can keep animating (an autopilot, an explorer) until one is pressed.
+
+use std::io::{self, IsTerminal, Write};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+pub use crossterm::event::KeyCode as Key;
+use crossterm::{cursor, event, execute, queue, style, terminal};
+use crossterm::event::{Event, KeyEventKind};
+use crossterm::terminal::ClearType;
+
+/// How often the reader thread looks up from waiting for a key to see whether it should stop
+const READER_POLL: Duration = Duration::from_millis(50);
+
+/// Puts the terminal in raw mode until it is dropped, which stops the reader thread and puts the
+/// terminal back the way it was
+pub struct Terminal {
+    keys: Receiver<Key>,
+    shutdown: Arc<AtomicBool>,
+    reader: Option<JoinHandle<()>>,
+}
+
+impl Terminal {
+    /// Takes over the terminal, or fails when not running in one
+    pub fn open() -> Result<Terminal, String> {
+        if !io::stdin().is_terminal() || !io::stdout().is_terminal() {
+            return Err("This needs an interactive terminal".to_string());
+        }
+        terminal::enable_raw_mode().map_err(|e| e.to_string())?;
+
+        let (sender, keys) = mpsc::channel();
+        let shutdown = Arc::new(AtomicBool::new(false));
+        let stop = shutdown.clone();
+        let reader = thread::spawn(move || {
+            while !stop.load(Ordering::Relaxed) {
+                match event::poll(READER_POLL) {
+                    Ok(false) => {}
+                    Ok(true) => match event::read() {
+                        Ok(Event::Key(key)) if key.kind != KeyEventKind::Release => {
+                            if sender.send(key.code).is_err() {
+                                break;
+                            }
+                        }
+                        Ok(_) => {}
+                        Err(_) => break,
+                    },
+                    Err(_) => break,
+                }
+            }
+        });
+
+        // from here on dropping the terminal cleans up, even if setting it up fails half way
+        let terminal = Terminal { keys, shutdown, reader: Some(reader) };
+        execute!(io::stdout(), terminal::Clear(ClearType::All), cursor::Hide).map_err(|e| e.to_string())?;
+        Ok(terminal)
+    }
+
+    /// Replaces whatever is on the screen with `frame`
+    pub fn draw(&self, frame: &str) -> Result<(), String> {
+        let mut stdout = io::stdout().lock();
+        queue!(stdout, cursor::MoveTo(0, 0)).map_err(|e| e.to_string())?;
+        for (i, line) in frame.lines().enumerate() {
+            // raw mode doesn't go back to the start of the line on a new line
+            if i > 0 {
+                queue!(stdout, style::Print("\r\n")).map_err(|e| e.to_string())?;
+            }
+            queue!(stdout, style::Print(line), terminal::Clear(ClearType::UntilNewLine)).map_err(|e| e.to_string())?;
+        }
+        queue!(stdout, terminal::Clear(ClearType::FromCursorDown)).map_err(|e| e.to_string())?;
+        stdout.flush().map_err(|e| e.to_string())
+    }
+
+    /// Waits for the next key
+    pub fn key(&self) -> Result<Key, String> {
+        self.keys.recv().map_err(|_| "The terminal stopped reading keys".to_string())
+    }
+
+    /// Waits at most `timeout` for the next key
+    pub fn poll(&self, timeout: Duration) -> Result<Option<Key>, String> {
+        match self.keys.recv_timeout(timeout) {
+            Ok(key) => Ok(Some(key)),
+            Err(RecvTimeoutError::Timeout) => Ok(None),
+            Err(RecvTimeoutError::Disconnected) => Err("The terminal stopped reading keys".to_string()),
+        }
+    }
+}
+
+impl Drop for Terminal {
+    fn drop(&mut self) {
+        self.shutdown.store(true, Ordering::Relaxed);
+        if let Some(reader) = self.reader.take() {
+            let _ = reader.join();
+        }
+        let _ = terminal::disable_raw_mode();
+        let _ = execute!(io::stdout(), cursor::Show, style::Print("\r\n"));
+    }
+}
@@ -1,3 +1,5 @@
+use aoc_macros::aoc;
+
 use crate::inputs::read_contents;
 
 fn get_numbers() -> Vec<i32> {
@@ -10,6 +12,7 @@ fn get_numbers() -> Vec<i32> {
     numbers
 }
 
+#[aoc(year = 2020, day = 1, part = 1)]
 pub fn solve_a() {
     let numbers = get_numbers();
     let ans = get_product_of_2020_sum(&numbers).unwrap();
@@ -38,6 +41,7 @@ fn get_product_of_2020_sum(numbers: &Vec<i32>) -> Option<i32> {
     None
 }
 
+#[aoc(year = 2020, day = 1, part = 2)]
 pub fn solve_b() {
     let numbers = get_numbers();
     let ans = three_sum(&numbers).unwrap();
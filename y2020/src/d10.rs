@@ -1,11 +1,14 @@
 use std::collections::HashMap;
 
+use aoc_macros::aoc;
+
 use crate::inputs::read_contents;
 
 fn get_puzzle_input() -> Vec<usize> {
     read_contents(10).lines().map(|line| line.parse().unwrap()).collect()
 }
 
+#[aoc(year = 2020, day = 10, part = 1)]
 pub fn solve_a() {
     let mut numbers = get_puzzle_input();
 
@@ -32,6 +35,7 @@ fn threes_times_ones(numbers: &mut Vec<usize>) -> usize {
     ones * threes
 }
 
+#[aoc(year = 2020, day = 10, part = 2)]
 pub fn solve_b() {
     let mut numbers = get_puzzle_input();
     let ans = number_of_arrangements(&mut numbers);
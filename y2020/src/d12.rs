@@ -1,3 +1,5 @@
+use aoc_macros::aoc;
+
 use crate::inputs::read_contents;
 
 enum Face {
@@ -165,6 +167,7 @@ fn form_instructions(input: &str) -> Vec<Instruction> {
          .collect()
 }
 
+#[aoc(year = 2020, day = 12, part = 1)]
 pub fn solve_a() {
     let instructions = form_instructions(&read_contents(12));
     let mut ship = Ship::new();
@@ -176,6 +179,7 @@ pub fn solve_a() {
     println!("Solution A: {}", ship.distance_from_origin());
 }
 
+#[aoc(year = 2020, day = 12, part = 2)]
 pub fn solve_b() {
     let instructions = form_instructions(&read_contents(12));
     let mut ship = Ship::new();
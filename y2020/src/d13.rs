@@ -1,6 +1,7 @@
 use std::collections::HashMap;
 
-use itertools::izip;
+use aoc_macros::aoc;
+use aoc_utils::number::crt;
 
 use crate::inputs::read_contents;
 
@@ -22,6 +23,7 @@ fn form_inputs(input: &str) -> (usize, HashMap<usize, usize>) {
     (start, buses)
 }
 
+#[aoc(year = 2020, day = 13, part = 1)]
 pub fn solve_a() {
     let (start_time, buses) = form_inputs(&read_contents(13));
     let (bus_id, wait_time) = first_bus_id_and_waiting_time(start_time, &buses);
@@ -50,6 +52,7 @@ fn first_bus_id_and_waiting_time(start_time: usize, buses: &HashMap<usize, usize
     (best_bus, shortest_waiting_time)
 }
 
+#[aoc(year = 2020, day = 13, part = 2)]
 pub fn solve_b() {
     let (_, buses) = form_inputs(&read_contents(13));
     let ans = chinese_remainder(&buses);
@@ -59,36 +62,13 @@ pub fn solve_b() {
 }
 
 
-/**
-Chinese remainder theorem implementation from
-https://homepages.math.uic.edu/~leon/mcs425-s08/handouts/chinese_remainder.pdf
-
-Also look at https://www.khanacademy.org/computing/computer-science/cryptography/modarithmetic/a/modular-inverses
-for how to calculate modulo inverses
- **/
+/// The earliest time `t` at which every bus leaves `offset` minutes after `t`, i.e. `t = -offset`
+/// modulo every bus ID
 fn chinese_remainder(buses: &HashMap<usize, usize>) -> usize {
-    let (modulo, a) = buses.into_iter()
-                           .fold((Vec::new(), Vec::new()),
-                                 |(mut modulo, mut remainder), (bus_id, offset)| {
-                                     modulo.push(*bus_id);
-                                     remainder.push(if *offset == 0 { 0 } else { bus_id - (offset % bus_id) });
-                                     (modulo, remainder)
-                                 });
-    let m = modulo.iter().product::<usize>();
-    let z = modulo.iter().map(|v| m / v).collect::<Vec<_>>();
-    let y = z.iter()
-             .zip(modulo.iter())
-             .map(|(zi, mi)| {
-                 // find modulo inverse
-                 (1..*mi).find(|vi| (vi * zi) % mi == 1).unwrap()
-             })
-             .collect::<Vec<_>>();
-
-    let x = izip!(y.iter(), z.iter(), a.iter())
-        .map(|(yi, zi, ai)| yi * zi * ai)
-        .sum::<usize>() % m;
+    let (t, _) = crt(buses.iter().map(|(&bus_id, &offset)| (-(offset as i128), bus_id as i128)))
+        .expect("the buses never line up");
 
-    x
+    t as usize
 }
 
 #[cfg(test)]
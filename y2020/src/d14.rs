@@ -1,5 +1,6 @@
 use std::collections::HashMap;
 
+use aoc_macros::aoc;
 use itertools::izip;
 use regex::Regex;
 
@@ -65,6 +66,7 @@ fn to_binary(value: usize) -> Vec<usize> {
     (0..36).rev().map(|i| (value >> i) & 1).collect()
 }
 
+#[aoc(year = 2020, day = 14, part = 1)]
 pub fn solve_a() {
     let commands = parse_commands(&read_contents(14));
     let ans: usize = memory_store_sum(&commands, apply_mask).values().sum();
@@ -123,6 +125,7 @@ fn to_number(mem: &Vec<usize>) -> usize {
     sum
 }
 
+#[aoc(year = 2020, day = 14, part = 2)]
 pub fn solve_b() {
     let commands = parse_commands(&read_contents(14));
     let ans: usize = memory_store_sum(&commands, apply_mask_2).values().sum();
@@ -1,9 +1,12 @@
 use std::collections::HashMap;
 
+use aoc_macros::aoc;
+
 fn get_puzzle_input() -> Vec<usize> {
     vec![16, 1, 0, 18, 12, 14, 19]
 }
 
+#[aoc(year = 2020, day = 15, part = 1)]
 pub fn solve_a() {
     let input = get_puzzle_input();
     let ans = get_number(&input, 2020);
@@ -39,6 +42,7 @@ fn get_number(input: &Vec<usize>, position: usize) -> usize {
     next_number
 }
 
+#[aoc(year = 2020, day = 15, part = 2)]
 pub fn solve_b() {
     let input = get_puzzle_input();
     let ans = get_number(&input, 30_000_000);
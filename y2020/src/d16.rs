@@ -1,13 +1,15 @@
 use std::collections::{HashMap, HashSet};
 
+use aoc_macros::aoc;
+use aoc_utils::IntervalSet;
 use itertools::Itertools;
 use regex::{Match, Regex};
 
 use crate::inputs::read_contents;
 
-fn parse_input(input: &str) -> (HashMap<String, HashSet<usize>>, Vec<usize>, Vec<Vec<usize>>) {
-    fn match_to_usize(cap: Match) -> usize {
-        cap.as_str().parse::<usize>().unwrap()
+fn parse_input(input: &str) -> (HashMap<String, IntervalSet>, Vec<usize>, Vec<Vec<usize>>) {
+    fn match_to_i64(cap: Match) -> i64 {
+        cap.as_str().parse::<i64>().unwrap()
     }
 
     let constraint_re = Regex::new(r"^([^:]+): (\d+)-(\d+) or (\d+)-(\d+)$").unwrap();
@@ -34,11 +36,9 @@ fn parse_input(input: &str) -> (HashMap<String, HashSet<usize>>, Vec<usize>, Vec
                 let caps = constraint_re.captures(line).expect(&format!("Could not parse constraint: {}", line));
                 let class = caps.get(1).unwrap().as_str().to_string();
                 let ranges = [
-                    match_to_usize(caps.get(2).unwrap())..=match_to_usize(caps.get(3).unwrap()),
-                    match_to_usize(caps.get(4).unwrap())..=match_to_usize(caps.get(5).unwrap()),
-                ].iter()
-                 .flat_map(|range| range.clone().collect::<HashSet<_>>())
-                 .collect();
+                    match_to_i64(caps.get(2).unwrap())..=match_to_i64(caps.get(3).unwrap()),
+                    match_to_i64(caps.get(4).unwrap())..=match_to_i64(caps.get(5).unwrap()),
+                ].into_iter().collect();
 
                 constraints.insert(class, ranges);
             }
@@ -56,35 +56,30 @@ fn parse_input(input: &str) -> (HashMap<String, HashSet<usize>>, Vec<usize>, Vec
     (constraints, ticket, nearby_ticket)
 }
 
-fn get_valid_numbers(constraints: &HashMap<String, HashSet<usize>>) -> HashSet<usize> {
-    constraints.values()
-               .into_iter()
-               .fold(HashSet::new(), |mut acc, other| {
-                   for v in other {
-                       acc.insert(*v);
-                   }
-                   acc
-               })
+fn get_valid_numbers(constraints: &HashMap<String, IntervalSet>) -> IntervalSet {
+    constraints.values().fold(IntervalSet::new(), |acc, other| acc.union(other))
 }
 
+#[aoc(year = 2020, day = 16, part = 1)]
 pub fn solve_a() {
     let (constraints, _, nearby) = parse_input(&read_contents(16));
 
     let valid_numbers = get_valid_numbers(&constraints);
 
     let ans: usize = nearby.iter()
-                           .map(|ticket| ticket.iter().filter(|v| !valid_numbers.contains(v)).sum::<usize>())
+                           .map(|ticket| ticket.iter().filter(|&&v| !valid_numbers.contains(v as i64)).sum::<usize>())
                            .sum();
 
     println!("Solution A: {}", ans);
 }
 
+#[aoc(year = 2020, day = 16, part = 2)]
 pub fn solve_b() {
     let (constraints, my_ticket, nearby) = parse_input(&read_contents(16));
     let valid_numbers = get_valid_numbers(&constraints);
 
     let valid_nearby = nearby.into_iter()
-                             .filter(|ticket| ticket.iter().all(|v| valid_numbers.contains(v)))
+                             .filter(|ticket| ticket.iter().all(|&v| valid_numbers.contains(v as i64)))
                              .collect_vec();
 
     let mut fixed: HashMap<String, usize> = HashMap::new();
@@ -95,7 +90,7 @@ pub fn solve_b() {
 
         let mut possible_fields = Vec::new();
         for (field, valid) in constraints.iter() {
-            if nums.iter().all(|v| valid.contains(v)) {
+            if nums.iter().all(|&v| valid.contains(v as i64)) {
                 possible_fields.push(field.clone());
             }
         }
@@ -1,5 +1,7 @@
 use std::fmt::{Debug, Formatter};
 
+use aoc_macros::aoc;
+
 use crate::inputs::read_contents;
 
 enum Operator { Add, Mul, None }
@@ -51,6 +53,7 @@ fn parse_input(input: &str) -> Vec<Vec<Element>> {
          .collect()
 }
 
+#[aoc(year = 2020, day = 18, part = 1)]
 pub fn solve_a() {
     let questions = parse_input(&read_contents(18));
     let ans: usize = questions.iter()
@@ -117,6 +120,7 @@ fn calculate(elements: &[Element]) -> (usize, usize) {
     (number.unwrap(), i)
 }
 
+#[aoc(year = 2020, day = 18, part = 2)]
 pub fn solve_b() {
     let questions = parse_input(&read_contents(18));
     let ans: usize = questions.iter()
@@ -1,5 +1,7 @@
 use std::collections::{HashMap, VecDeque};
 
+use aoc_macros::aoc;
+
 use crate::inputs::read_contents;
 
 #[derive(Debug, Clone)]
@@ -91,6 +93,7 @@ fn matches(rules: &HashMap<usize, Vec<Rule>>, phrase: &[char], mut with: VecDequ
     false
 }
 
+#[aoc(year = 2020, day = 19, part = 1)]
 pub fn solve_a() {
     let ans = solve_without_loops(&read_contents(19));
     println!("Solution A: {}", ans);
@@ -126,6 +129,7 @@ fn modify_part2(rules: &mut HashMap<usize, Vec<Rule>>) {
     }
 }
 
+#[aoc(year = 2020, day = 19, part = 2)]
 pub fn solve_b() {
     let ans = solve_with_loops(&read_contents(19));
 
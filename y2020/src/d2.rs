@@ -1,3 +1,4 @@
+use aoc_macros::aoc;
 use regex::{Captures, Regex};
 
 use crate::inputs::read_contents;
@@ -46,6 +47,7 @@ fn get_inputs<T: Password>(inputs: &str) -> Vec<T> {
         .collect()
 }
 
+#[aoc(year = 2020, day = 2, part = 1)]
 pub fn solve_a() {
     let passwords = get_inputs::<PasswordA>(&read_contents(2));
 
@@ -83,6 +85,7 @@ impl Password for PasswordB {
 }
 
 
+#[aoc(year = 2020, day = 2, part = 2)]
 pub fn solve_b() {
     let passwords = get_inputs::<PasswordB>(&read_contents(2));
 
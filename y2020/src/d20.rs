@@ -1,6 +1,7 @@
 use std::collections::{HashMap, VecDeque};
 use std::fmt::{Display, Formatter};
 
+use aoc_macros::aoc;
 use itertools::{Itertools, MinMaxResult};
 
 use crate::inputs::read_contents;
@@ -85,6 +86,7 @@ fn parse_input(input: &str) -> Vec<Tile> {
          .collect()
 }
 
+#[aoc(year = 2020, day = 20, part = 1)]
 pub fn solve_a() {
     let tiles = parse_input(&read_contents(20));
 
@@ -120,6 +122,7 @@ fn find_corner_multiple(tiles: Vec<Tile>) -> usize {
                     .fold(1, |acc, (k, _)| acc * k)
 }
 
+#[aoc(year = 2020, day = 20, part = 2)]
 pub fn solve_b() {
     let tiles = parse_input(&read_contents(20));
     let image = piece_puzzle(tiles);
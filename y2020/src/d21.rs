@@ -1,6 +1,7 @@
 use std::collections::{HashMap, HashSet};
 use std::fmt::{Debug, Formatter};
 
+use aoc_macros::aoc;
 use itertools::Itertools;
 use regex::Regex;
 
@@ -43,6 +44,7 @@ fn parse_input(input: &str) -> Vec<Recipe> {
          .collect()
 }
 
+#[aoc(year = 2020, day = 21, part = 1)]
 pub fn solve_a() {
     let recipes = parse_input(&read_contents(21));
     let list = check_allergens(&recipes);
@@ -112,6 +114,7 @@ fn count_appearance(ingredient_allergens: &HashMap<String, HashSet<String>>, rec
            .sum()
 }
 
+#[aoc(year = 2020, day = 21, part = 2)]
 pub fn solve_b() {
     let recipes = parse_input(&read_contents(21));
     let list = check_allergens(&recipes);
@@ -1,5 +1,6 @@
 use std::collections::{HashSet, VecDeque};
 
+use aoc_macros::aoc;
 use itertools::Itertools;
 
 use crate::inputs::read_contents;
@@ -31,6 +32,7 @@ fn parse_input(input: &str) -> [VecDeque<usize>; 2] {
     [v1, v2]
 }
 
+#[aoc(year = 2020, day = 22, part = 1)]
 pub fn solve_a() {
     let [mut player1, mut player2] = parse_input(&read_contents(22));
 
@@ -62,6 +64,7 @@ fn count_score(player: &VecDeque<usize>) -> usize {
     player.iter().enumerate().map(|(i, v)| (n - i) * v).sum()
 }
 
+#[aoc(year = 2020, day = 22, part = 2)]
 pub fn solve_b() {
     let [player1, player2] = parse_input(&read_contents(22));
     let (_, winning_deck) = recursive_simulate(player1, player2);
@@ -3,6 +3,7 @@ use std::collections::{HashMap, HashSet};
 use std::ops::Index;
 use std::ptr;
 
+use aoc_macros::aoc;
 use indicatif::ProgressIterator;
 use itertools::Itertools;
 
@@ -137,6 +138,7 @@ impl List {
 }
 
 
+#[aoc(year = 2020, day = 23, part = 1)]
 pub fn solve_a() {
     let mut list = List::from(input_to_vec(PUZZLE_INPUT));
 
@@ -145,6 +147,7 @@ pub fn solve_a() {
     println!("Solution A: {}", ans);
 }
 
+#[aoc(year = 2020, day = 23, part = 2)]
 pub fn solve_b() {
     let mut input = input_to_vec(PUZZLE_INPUT);
     input.extend((input.iter().max().unwrap() + 1)..=1_000_000);
@@ -1,9 +1,13 @@
+use aoc_macros::aoc;
+use aoc_utils::number::{discrete_log, mod_pow};
+
 const DOOR_PK: usize = 1717001;
 const CARD_PK: usize = 523731;
 
 const SUB_NO: usize = 7;
 const REM_NO: usize = 20201227;
 
+#[aoc(year = 2020, day = 25, part = 1)]
 pub fn solve_a() {
     let door_loop = get_loop_size(DOOR_PK);
     let card_loop = get_loop_size(CARD_PK);
@@ -17,26 +21,14 @@ pub fn solve_a() {
 }
 
 fn get_loop_size(target: usize) -> usize {
-    let mut value = 1;
-
-    let mut loop_size = 0;
-    while value != target {
-        loop_size += 1;
-        value = (value * SUB_NO) % REM_NO;
-    }
-
-    loop_size
+    discrete_log(SUB_NO as i64, target as i64, REM_NO as i64).expect("public key can't be made from the subject number") as usize
 }
 
 fn get_encryption_key(public_key: usize, loop_size: usize) -> usize {
-    let mut key = 1;
-    for _ in 0..loop_size {
-        key = (key * public_key) % REM_NO;
-    }
-
-    key
+    mod_pow(public_key, loop_size, REM_NO)
 }
 
+#[aoc(year = 2020, day = 25, part = 2)]
 pub fn solve_b() {
     println!("Completed AOC 2020");
 }
\ No newline at end of file
@@ -1,66 +1,35 @@
-use std::collections::HashMap;
 use std::sync::Arc;
 use std::thread;
 
-use crate::inputs::read_contents;
+use aoc_macros::{aoc, AocTile};
+use aoc_utils::Grid;
 
-#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
-struct Point(usize, usize);
+use crate::inputs::read_contents;
 
-#[derive(PartialEq, Eq)]
+#[derive(PartialEq, Eq, AocTile)]
 enum Tile {
+    #[tile('.')]
     Empty,
+    #[tile('#')]
     Tree,
 }
 
-impl Tile {
-    fn new(c: char) -> Self {
-        match c {
-            '#' => Tile::Tree,
-            '.' => Tile::Empty,
-            _ => { panic!("Invalid tile char: {}", c) }
-        }
-    }
-}
-
-fn setup(input: &str) -> (HashMap<Point, Tile>, usize, usize) {
-    let mut map = HashMap::new();
-
-    for (y, line) in input.lines().enumerate() {
-        for (x, c) in line.chars().enumerate() {
-            map.insert(Point(x, y), Tile::new(c));
-        }
-    }
-
-    let max_y = input.lines().count() - 1;
-    let max_x = input.lines().next().unwrap().len() - 1;
-
-    (map, max_x, max_y)
-}
-
+#[aoc(year = 2020, day = 3, part = 1)]
 pub fn solve_a() {
     let ans = count_number_trees_encountered(&read_contents(3), 3, 1);
     println!("Solution A: {}", ans);
 }
 
 fn count_number_trees_encountered(input: &str, x_offset: usize, y_offset: usize) -> usize {
-    let (map, max_x, max_y) = setup(input);
+    let map = input.parse::<Grid<Tile>>().unwrap();
 
-    let mut count = 0;
-    let mut point = Point(0, 0);
-
-    while point.1 <= max_y {
-        if let Some(tile) = map.get(&point) {
-            if *tile == Tile::Tree {
-                count += 1;
-            }
-        }
-
-        point = Point((point.0 + x_offset) % (max_x + 1), point.1 + y_offset)
-    }
-    count
+    (0..map.height()).step_by(y_offset)
+                     .enumerate()
+                     .filter(|&(step, y)| map[(step * x_offset % map.width(), y)] == Tile::Tree)
+                     .count()
 }
 
+#[aoc(year = 2020, day = 3, part = 2)]
 pub fn solve_b() {
     let input = Arc::new(read_contents(3));
 
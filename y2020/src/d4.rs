@@ -1,3 +1,4 @@
+use aoc_macros::aoc;
 use once_cell::sync::OnceCell;
 use regex::Regex;
 
@@ -104,6 +105,7 @@ fn form_passports(input: &str) -> Vec<Passport> {
         .collect()
 }
 
+#[aoc(year = 2020, day = 4, part = 1)]
 pub fn solve_a() {
     let input = read_contents(4);
     let passports = form_passports(&input);
@@ -116,6 +118,7 @@ pub fn solve_a() {
     println!("Solution A: {}", ans);
 }
 
+#[aoc(year = 2020, day = 4, part = 2)]
 pub fn solve_b() {
     let input = read_contents(4);
     let passports = form_passports(&input);
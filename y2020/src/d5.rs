@@ -1,5 +1,7 @@
 use std::collections::HashSet;
 
+use aoc_macros::aoc;
+
 use crate::inputs::read_contents;
 
 #[derive(Debug)]
@@ -31,6 +33,7 @@ impl BoardingPass {
     }
 }
 
+#[aoc(year = 2020, day = 5, part = 1)]
 pub fn solve_a() {
     let ans = read_contents(5).lines()
         .map(|line| BoardingPass::new(line).id())
@@ -40,6 +43,7 @@ pub fn solve_a() {
     println!("Solution A: {}", ans);
 }
 
+#[aoc(year = 2020, day = 5, part = 2)]
 pub fn solve_b() {
     let ids = read_contents(5).lines()
         .map(|line| BoardingPass::new(line).id())
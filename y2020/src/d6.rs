@@ -1,7 +1,10 @@
 use std::collections::HashSet;
 
+use aoc_macros::aoc;
+
 use crate::inputs::read_contents;
 
+#[aoc(year = 2020, day = 6, part = 1)]
 pub fn solve_a() {
     let ans = read_contents(6).replace("\r\n", "\n")
         .split("\n\n")
@@ -13,6 +16,7 @@ pub fn solve_a() {
     println!("Solution A: {}", ans);
 }
 
+#[aoc(year = 2020, day = 6, part = 2)]
 pub fn solve_b() {
     let ans = read_contents(6).replace("\r\n", "\n")
         .split("\n\n")
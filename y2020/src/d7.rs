@@ -2,6 +2,7 @@ use std::collections::{HashMap, HashSet, VecDeque};
 use std::hash::{Hash, Hasher};
 use std::ops::Deref;
 
+use aoc_macros::aoc;
 use regex::Regex;
 
 use crate::inputs::read_contents;
@@ -55,6 +56,7 @@ fn get_bag_rules(input: &str) -> Vec<BagRule> {
         .collect()
 }
 
+#[aoc(year = 2020, day = 7, part = 1)]
 pub fn solve_a() {
     let rules = get_bag_rules(&read_contents(7));
     let ans = find_gold_bag_source(&rules);
@@ -83,6 +85,7 @@ fn find_gold_bag_source(rules: &Vec<BagRule>) -> usize {
     holds_gold_bag.len() - 1
 }
 
+#[aoc(year = 2020, day = 7, part = 2)]
 pub fn solve_b() {
     let rules = get_bag_rules_map(&read_contents(7));
     let ans = count_bags("shiny gold", &rules);
@@ -1,14 +1,47 @@
-use std::collections::HashSet;
+use std::collections::VecDeque;
+use std::fmt::{Display, Formatter};
+
+use aoc_macros::aoc;
+use aoc_utils::machine::{self, Flow, Machine, Registers, Stop};
 
 use crate::inputs::read_contents;
 
-#[derive(Clone)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum Instruction {
     Acc(i32),
     Noop(i32),
     Jump(i32),
 }
 
+impl Display for Instruction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Instruction::Acc(v) => write!(f, "acc {:+}", v),
+            Instruction::Noop(v) => write!(f, "nop {:+}", v),
+            Instruction::Jump(v) => write!(f, "jmp {:+}", v),
+        }
+    }
+}
+
+impl Instruction {
+    /// Where the program goes after running this at `i`
+    fn successor(&self, i: usize) -> i64 {
+        match self {
+            Instruction::Jump(v) => i as i64 + *v as i64,
+            _ => i as i64 + 1,
+        }
+    }
+
+    /// The instruction with `nop` and `jmp` swapped, if it is one of them
+    fn flipped(&self) -> Option<Instruction> {
+        match *self {
+            Instruction::Noop(v) => Some(Instruction::Jump(v)),
+            Instruction::Jump(v) => Some(Instruction::Noop(v)),
+            Instruction::Acc(_) => None,
+        }
+    }
+}
+
 fn parse_instructions(input: &str) -> Vec<Instruction> {
     input.lines()
         .map(|line| {
@@ -25,88 +58,157 @@ fn parse_instructions(input: &str) -> Vec<Instruction> {
         .collect()
 }
 
-pub fn solve_a() {
-    let instructions = parse_instructions(&read_contents(8));
-    let mut i = 0;
-    let mut acc = 0;
-
-    let mut seen = HashSet::new();
+/// The state is the accumulator
+impl machine::Instruction<i64> for Instruction {
+    type Register = ();
 
-    while !seen.contains(&i) {
-        seen.insert(i);
-        match &instructions[i] {
+    fn execute(&self, _: &mut Registers<()>, acc: &mut i64) -> Flow {
+        match self {
             Instruction::Acc(v) => {
-                acc += v;
-                i += 1;
-            }
-            Instruction::Noop(_) => { i += 1; }
-            Instruction::Jump(v) => {
-                if v.is_positive() {
-                    i += *v as usize;
-                } else {
-                    i -= v.abs() as usize
-                }
+                *acc += *v as i64;
+                Flow::Next
             }
+            Instruction::Noop(_) => Flow::Next,
+            Instruction::Jump(v) => Flow::Jump(*v as i64),
         }
     }
+}
 
-    println!("Solution A: {}", acc);
+#[aoc(year = 2020, day = 8, part = 1)]
+pub fn solve_a() {
+    let instructions = parse_instructions(&read_contents(8));
+    let mut machine = Machine::new(&instructions).with_loop_detection();
+    machine.run();
+
+    println!("Solution A: {}", machine.state());
 }
 
-fn get_no_cycle_accumulation(instructions: &Vec<Instruction>) -> Option<i32> {
-    let mut i = 0;
-    let mut acc = 0;
+/// The accumulator once the program has run off its end, or `None` if it loops forever
+fn get_no_cycle_accumulation(instructions: &[Instruction]) -> Option<i64> {
+    let mut machine = Machine::new(instructions).with_loop_detection();
+    match machine.run() {
+        Stop::Exited => Some(*machine.state()),
+        _ => None,
+    }
+}
 
-    let mut seen = HashSet::new();
 
-    while i < instructions.len() {
-        if seen.contains(&i) {
-            return None;
+/// Which instructions lead out of the program, found by walking backwards from the ones that jump
+/// straight out of it. Every instruction has a single successor, so this takes linear time.
+fn terminating(instructions: &[Instruction]) -> Vec<bool> {
+    let len = instructions.len() as i64;
+    let mut predecessors = vec![Vec::new(); instructions.len()];
+    let mut queue = VecDeque::new();
+    for (i, instruction) in instructions.iter().enumerate() {
+        match instruction.successor(i) {
+            next if (0..len).contains(&next) => predecessors[next as usize].push(i),
+            _ => queue.push_back(i),
         }
-        seen.insert(i);
-        match &instructions[i] {
-            Instruction::Acc(v) => {
-                acc += v;
-                i += 1;
-            }
-            Instruction::Noop(_) => { i += 1; }
-            Instruction::Jump(v) => {
-                if v.is_positive() {
-                    i += *v as usize;
-                } else {
-                    i -= v.abs() as usize
-                }
-            }
+    }
+
+    let mut terminates = vec![false; instructions.len()];
+    while let Some(i) = queue.pop_front() {
+        if !terminates[i] {
+            terminates[i] = true;
+            queue.extend(predecessors[i].iter().copied());
         }
     }
+    terminates
+}
 
-    Some(acc)
+/// A single instruction to swap so that the program terminates
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Repair {
+    line: usize,
+    from: Instruction,
+    to: Instruction,
 }
 
+impl Repair {
+    fn apply(&self, instructions: &[Instruction]) -> Vec<Instruction> {
+        let mut repaired = instructions.to_vec();
+        repaired[self.line] = self.to;
+        repaired
+    }
+}
 
-pub fn solve_b() {
-    let instructions = parse_instructions(&read_contents(8));
+impl Display for Repair {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}: {} -> {}", self.line + 1, self.from, self.to)
+    }
+}
 
-    let ans = 'outer: loop {
-        for i in 0..instructions.len() {
-            if let Some(ins) = instructions.get(i) {
-                let mut instructions = (&instructions).clone();
-                match ins {
-                    Instruction::Noop(v) if v.is_positive() => {
-                        instructions[i] = Instruction::Jump(*v);
-                    }
-                    Instruction::Jump(v) if v.is_negative() => {
-                        instructions[i] = Instruction::Noop(*v);
-                    }
-                    _ => { continue; }
-                }
-
-                if let Some(ans) = get_no_cycle_accumulation(&instructions) {
-                    break 'outer ans;
-                }
+/// Finds the `nop` or `jmp` to swap to make a looping program terminate, or `None` if the program
+/// already terminates or no single swap does it.
+///
+/// Only the instructions the program runs before it loops are worth swapping. The loop never gets
+/// out, so no instruction on it is on a way out, and swapping the one at `i` works exactly when its
+/// new successor leads out of the original program.
+fn find_repair(instructions: &[Instruction]) -> Option<Repair> {
+    let terminates = terminating(instructions);
+    let len = instructions.len() as i64;
+    if terminates.first().is_none_or(|&t| t) {
+        return None;
+    }
+
+    let mut visited = vec![false; instructions.len()];
+    let mut i = 0;
+    while (0..len).contains(&i) && !visited[i as usize] {
+        let line = i as usize;
+        visited[line] = true;
+
+        let from = instructions[line];
+        if let Some(to) = from.flipped() {
+            let next = to.successor(line);
+            if !(0..len).contains(&next) || terminates[next as usize] {
+                return Some(Repair { line, from, to });
             }
         }
-    };
+        i = from.successor(line);
+    }
+    None
+}
 
+#[aoc(year = 2020, day = 8, part = 2)]
+pub fn solve_b() {
+    let instructions = parse_instructions(&read_contents(8));
+    let repair = find_repair(&instructions).expect("No single swap makes the program terminate");
+    let ans = get_no_cycle_accumulation(&repair.apply(&instructions)).unwrap();
+
+    println!("Repaired {}", repair);
     println!("Solution B: {}", ans);
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "nop +0
+acc +1
+jmp +4
+acc +3
+jmp -3
+acc -99
+acc +1
+jmp -4
+acc +6";
+
+    #[test]
+    fn test_terminating() {
+        let instructions = parse_instructions(EXAMPLE);
+        assert_eq!(terminating(&instructions), vec![false, false, false, false, false, false, false, false, true]);
+    }
+
+    #[test]
+    fn test_find_repair() {
+        let instructions = parse_instructions(EXAMPLE);
+        let repair = find_repair(&instructions).unwrap();
+
+        assert_eq!(repair, Repair { line: 7, from: Instruction::Jump(-4), to: Instruction::Noop(-4) });
+        assert_eq!(repair.to_string(), "line 8: jmp -4 -> nop -4");
+        assert_eq!(get_no_cycle_accumulation(&repair.apply(&instructions)), Some(8));
+
+        // nothing to repair
+        assert_eq!(find_repair(&repair.apply(&instructions)), None);
+    }
+}
@@ -1,5 +1,7 @@
 use std::collections::{HashSet, VecDeque};
 
+use aoc_macros::aoc;
+
 use crate::inputs::read_contents;
 
 fn form_numbers(input: &str) -> Vec<usize> {
@@ -8,6 +10,7 @@ fn form_numbers(input: &str) -> Vec<usize> {
         .collect()
 }
 
+#[aoc(year = 2020, day = 9, part = 1)]
 pub fn solve_a() {
     let numbers: Vec<usize> = form_numbers(&read_contents(9));
     let ans = find_first_invalid_number(&numbers, 25);
@@ -37,6 +40,7 @@ fn find_first_invalid_number(numbers: &Vec<usize>, preamble_length: usize) -> us
     panic!("Could not find any invalid number");
 }
 
+#[aoc(year = 2020, day = 9, part = 2)]
 pub fn solve_b() {
     let numbers: Vec<usize> = form_numbers(&read_contents(9));
     let target = find_first_invalid_number(&numbers, 25);
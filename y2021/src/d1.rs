@@ -1,3 +1,5 @@
+use aoc_macros::aoc;
+
 use crate::inputs::read_contents;
 
 fn parse_input(input: &str) -> Vec<usize> {
@@ -6,6 +8,7 @@ fn parse_input(input: &str) -> Vec<usize> {
          .collect()
 }
 
+#[aoc(year = 2021, day = 1, part = 1)]
 pub fn solve_a() {
     let measurements = parse_input(&read_contents(1));
     let ans = count_depth_increases(&measurements);
@@ -25,6 +28,7 @@ fn count_depth_increases(measurements: &Vec<usize>) -> usize {
                      .0
 }
 
+#[aoc(year = 2021, day = 1, part = 2)]
 pub fn solve_b() {
     let measurements = parse_input(&read_contents(1));
     let ans = rolling_sum(&measurements, 3);
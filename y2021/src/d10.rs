@@ -1,6 +1,8 @@
+use aoc_macros::aoc;
 use itertools::Itertools;
 use crate::inputs::read_contents;
 
+#[aoc(year = 2021, day = 10, part = 1)]
 pub fn solve_a() {
     let ans: usize = read_contents(10)
         .lines()
@@ -61,6 +63,7 @@ fn score_syntax_error(line: &str) -> (usize, Option<String>) {
     (0, Some(openings.iter().collect::<String>()))
 }
 
+#[aoc(year = 2021, day = 10, part = 2)]
 pub fn solve_b() {
     let scores = read_contents(10)
         .lines()
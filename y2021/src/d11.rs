@@ -1,29 +1,13 @@
 use std::collections::{HashMap, HashSet, VecDeque};
 
+use aoc_macros::aoc;
+use aoc_utils::Point2;
 use indicatif::ProgressIterator;
 
 use crate::inputs::read_contents;
 
-#[derive(Eq, PartialEq, Hash, Debug, Clone, Copy)]
-struct Point(i32, i32);
-
-impl Point {
-    fn neighbours(&self) -> [Point; 8] {
-        [
-            Point(self.0 - 1, self.1 - 1),
-            Point(self.0, self.1 - 1),
-            Point(self.0 + 1, self.1 - 1),
-            Point(self.0 - 1, self.1),
-            Point(self.0 + 1, self.1),
-            Point(self.0 - 1, self.1 + 1),
-            Point(self.0, self.1 + 1),
-            Point(self.0 + 1, self.1 + 1),
-        ]
-    }
-}
-
 struct Grid {
-    map: HashMap<Point, usize>,
+    map: HashMap<Point2, usize>,
     flashes: usize,
 }
 
@@ -34,7 +18,7 @@ impl From<&str> for Grid {
                        .fold(HashMap::new(), |mut map, (row_no, line)| {
                            for (col_no, energy) in line.chars().enumerate() {
                                let energy = energy.to_digit(10).unwrap();
-                               map.insert(Point(col_no as i32, row_no as i32), energy as usize);
+                               map.insert(Point2::new([col_no as i64, row_no as i64]), energy as usize);
                            }
                            map
                        });
@@ -60,8 +44,8 @@ impl Grid {
     }
 
     fn step(&mut self) -> usize {
-        let mut flashed: HashSet<Point> = HashSet::new();
-        let mut to_flash: VecDeque<Point> = VecDeque::new();
+        let mut flashed: HashSet<Point2> = HashSet::new();
+        let mut to_flash: VecDeque<Point2> = VecDeque::new();
 
         for (point, energy) in self.map.iter_mut() {
             *energy += 1;
@@ -75,7 +59,7 @@ impl Grid {
                 continue;
             }
 
-            for nb in pt.neighbours() {
+            for nb in pt.neighbours_all() {
                 if flashed.contains(&nb) { continue; }
                 if let Some(energy) = self.map.get_mut(&nb) {
                     *energy += 1;
@@ -98,6 +82,7 @@ impl Grid {
     }
 }
 
+#[aoc(year = 2021, day = 11, part = 1)]
 pub fn solve_a() {
     let mut grid = Grid::from(read_contents(11).as_str());
     grid.run_steps(100);
@@ -105,6 +90,7 @@ pub fn solve_a() {
     println!("Solution A: {}", grid.flashes);
 }
 
+#[aoc(year = 2021, day = 11, part = 2)]
 pub fn solve_b() {
     let mut grid = Grid::from(read_contents(11).as_str());
     let ans = grid.run_till_synchronized();
@@ -1,8 +1,11 @@
-use std::collections::{HashMap, HashSet, VecDeque};
+use std::collections::{BTreeSet, HashMap};
+
+use aoc_macros::aoc;
+use aoc_utils::search;
 
 use crate::inputs::read_contents;
 
-#[derive(PartialEq, Eq, Hash, Clone)]
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Clone)]
 struct Node {
     value: String,
     is_multi: bool,
@@ -41,6 +44,7 @@ fn form_graph(input: &str) -> HashMap<Node, Vec<Node>> {
 }
 
 
+#[aoc(year = 2021, day = 12, part = 1)]
 pub fn solve_a() {
     let graph = form_graph(&read_contents(12));
     let ans = count_number_of_pathways(&graph);
@@ -48,33 +52,43 @@ pub fn solve_a() {
     println!("Solution A: {}", ans);
 }
 
-fn count_number_of_pathways(graph: &HashMap<Node, Vec<Node>>) -> usize {
+/// A position in the cave system: the current cave, the small caves visited so far and whether a
+/// small cave has already been visited twice
+type Route = (Node, BTreeSet<Node>, bool);
+
+fn count_routes(graph: &HashMap<Node, Vec<Node>>, allow_twice: bool) -> usize {
     let end = Node::from("end");
     let start = Node::from("start");
-    let mut queue = VecDeque::from([(start.clone(), HashSet::from([start]))]);
-
-    let mut total = 0;
-    while let Some((current, mut visited)) = queue.pop_front() {
-        if current == end {
-            total += 1;
-            continue;
-        }
 
+    let mut next_routes = |(current, visited, has_twice): &Route| -> Vec<(Route, usize)> {
+        let mut visited = visited.clone();
         if !current.is_multi {
             visited.insert(current.clone());
         }
 
-        if let Some(nodes) = graph.get(&current) {
-            for node in nodes {
-                if visited.contains(node) { continue; }
-                queue.push_back((node.clone(), visited.clone()));
-            }
-        }
-    }
+        graph.get(current)
+             .into_iter()
+             .flatten()
+             .filter(|&node| *node != start)
+             .filter_map(|node| {
+                 let twice = visited.contains(node);
+                 if twice && (*has_twice || !allow_twice) {
+                     None
+                 } else {
+                     Some(((node.clone(), visited.clone(), *has_twice || twice), 1))
+                 }
+             })
+             .collect()
+    };
+
+    search::count_paths(&mut next_routes, (start.clone(), BTreeSet::new(), false), |(node, _, _)| *node == end)
+}
 
-    total
+fn count_number_of_pathways(graph: &HashMap<Node, Vec<Node>>) -> usize {
+    count_routes(graph, false)
 }
 
+#[aoc(year = 2021, day = 12, part = 2)]
 pub fn solve_b() {
     let graph = form_graph(&read_contents(12));
     let ans = count_number_of_pathways2(&graph);
@@ -83,37 +97,7 @@ pub fn solve_b() {
 }
 
 fn count_number_of_pathways2(graph: &HashMap<Node, Vec<Node>>) -> usize {
-    let end = Node::from("end");
-    let start = Node::from("start");
-
-    let mut queue = VecDeque::from([(start.clone(), HashSet::new(), false)]);
-
-    let mut total = 0;
-    while let Some((current, mut visited, has_twice)) = queue.pop_front() {
-        let next_has_twice = if visited.contains(&current) {
-            if has_twice { continue; } else { true }
-        } else {
-            has_twice
-        };
-
-        if !current.is_multi {
-            visited.insert(current.clone());
-        }
-
-        if let Some(nodes) = graph.get(&current) {
-            for node in nodes {
-                if *node == end {
-                    total += 1;
-                    continue;
-                }
-                if (*node == start) || (has_twice && visited.contains(node)) { continue; }
-
-                queue.push_back((node.clone(), visited.clone(), next_has_twice));
-            }
-        }
-    }
-
-    total
+    count_routes(graph, true)
 }
 
 #[cfg(test)]
@@ -1,8 +1,10 @@
 use std::cmp::{max, min};
 use std::collections::HashSet;
-use itertools::Itertools;
 use crate::inputs::read_contents;
 
+use aoc_macros::aoc;
+use aoc_utils::{ocr, Point2};
+
 #[derive(Eq, PartialEq, Hash, Debug)]
 struct Point(usize, usize);
 
@@ -42,6 +44,7 @@ fn parse_input(input: &str) -> (HashSet<Point>, Vec<Fold>) {
     (points, folds)
 }
 
+#[aoc(year = 2021, day = 13, part = 1)]
 pub fn solve_a() {
     let (points0, folds) = parse_input(&read_contents(13));
     let points1 = fold_once(&points0, &folds[0]);
@@ -121,36 +124,21 @@ fn fold_once(points: &HashSet<Point>, fold: &Fold) -> HashSet<Point> {
     next_points
 }
 
+#[aoc(year = 2021, day = 13, part = 2)]
 pub fn solve_b() {
     let (mut points, folds) = parse_input(&read_contents(13));
     for fold in folds.iter() {
         points = fold_once(&points, fold)
     }
 
-    let ans = draw_points(&points);
-    println!("Solution B: \n{}", ans);
+    let ans = read_code(&points);
+    println!("Solution B: {}", ans);
 }
 
-fn draw_points(points: &HashSet<Point>) -> String {
-    let [min_x, max_x, min_y, max_y] = get_boundaries(points);
-
-    let n_cols = max_x - min_x + 1;
-
-    let mut screen = vec![];
-    for _ in min_y..=max_y {
-        screen.push(vec![' '; n_cols]);
-    }
-
-    for p in points {
-        let x = p.0 - min_x;
-        let y = p.1 - min_y;
-        screen[y][x] = '#';
-    }
-
-    screen.iter()
-          .map(|row| row.iter().rev().collect::<String>())
-          .rev()
-          .join("\n")
+fn read_code(points: &HashSet<Point>) -> String {
+    // the folds leave the paper upside down and back to front, so flip both axes to read it
+    ocr::read_points(points.iter().map(|p| Point2::new([-(p.0 as i64), -(p.1 as i64)])))
+        .unwrap_or_else(|e| panic!("{}", e))
 }
 
 
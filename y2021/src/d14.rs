@@ -1,5 +1,6 @@
 use std::collections::HashMap;
 
+use aoc_macros::aoc;
 use indicatif::ProgressIterator;
 use itertools::{Itertools, izip, MinMaxResult};
 
@@ -27,6 +28,7 @@ fn parse_input(input: &str) -> (String, RuleMap) {
     (top.to_string(), rules)
 }
 
+#[aoc(year = 2021, day = 14, part = 1)]
 pub fn solve_a() {
     let (template, rules) = parse_input(&read_contents(14));
     let ans = most_vs_least_difference(&template, &rules, 10);
@@ -89,6 +91,7 @@ fn run_insertion_once(counter: &Counter, rules: &RuleMap) -> Counter {
     next
 }
 
+#[aoc(year = 2021, day = 14, part = 2)]
 pub fn solve_b() {
     let (template, rules) = parse_input(&read_contents(14));
     let ans = most_vs_least_difference(&template, &rules, 40);
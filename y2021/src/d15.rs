@@ -1,6 +1,8 @@
 use std::cmp::Ordering;
-use std::collections::{BTreeSet, HashMap};
+use std::collections::HashMap;
 
+use aoc_macros::aoc;
+use aoc_utils::search;
 use itertools::Itertools;
 
 use crate::inputs::read_contents;
@@ -55,39 +57,19 @@ fn get_neighbours(map: &HashMap<Point, usize>, point: &Point) -> Vec<(Point, usi
 
 
 fn find_risk_of_best_path(map: &HashMap<Point, usize>) -> usize {
-    let mut queue = BTreeSet::from([Point(0, 0)]);
-    let mut risk_scores: HashMap<Point, usize> = HashMap::from([(Point(0, 0), 0)]);
-
     let endpoint = map.keys()
                       .sorted_by(|p1, p2| p2.cmp(p1))
                       .next()
                       .unwrap()
                       .clone();
 
-    let mut lowest = usize::MAX;
-    while let Some(curr) = queue.pop_first() {
-        let curr_risk = *risk_scores.get(&curr).unwrap();
-
-        if curr == endpoint {
-            if curr_risk < lowest {
-                lowest = curr_risk;
-            }
-            continue;
-        }
-
-        for (next_pt, nb_risk) in get_neighbours(&map, &curr) {
-            let risk = curr_risk + nb_risk;
-            if !risk_scores.contains_key(&next_pt) || *risk_scores.get(&next_pt).unwrap() > risk {
-                risk_scores.insert(next_pt.clone(), risk);
-                queue.insert(next_pt);
-            }
-        }
-    }
-
-    lowest
+    search::dijkstra(&mut |point: &Point| get_neighbours(map, point), Point(0, 0), |p| *p == endpoint)
+        .unwrap()
+        .cost
 }
 
 
+#[aoc(year = 2021, day = 15, part = 1)]
 pub fn solve_a() {
     let map = parse_map(&read_contents(15));
     let ans = find_risk_of_best_path(&map);
@@ -120,6 +102,7 @@ fn parse_map2(input: &str) -> HashMap<Point, usize> {
     map
 }
 
+#[aoc(year = 2021, day = 15, part = 2)]
 pub fn solve_b() {
     let map = parse_map2(&read_contents(15));
     let ans = find_risk_of_best_path(&map);
@@ -1,6 +1,7 @@
 use std::cmp::{max, min};
 use std::collections::BTreeSet;
 
+use aoc_macros::aoc;
 use regex::Regex;
 
 const PUZZLE_INPUT: &str = "target area: x=206..250, y=-105..-57";
@@ -82,6 +83,7 @@ impl Target {
     }
 }
 
+#[aoc(year = 2021, day = 17, part = 1)]
 pub fn solve_a() {
     let target = Target::from(PUZZLE_INPUT);
     let ans = target.highest_point();
@@ -89,6 +91,7 @@ pub fn solve_a() {
     println!("Solution A: {}", ans);
 }
 
+#[aoc(year = 2021, day = 17, part = 2)]
 pub fn solve_b() {
     let target = Target::from(PUZZLE_INPUT);
     let ans = target.num_initial_conditions();
@@ -2,6 +2,7 @@ use std::fmt::{Debug, Formatter};
 use std::ops::{Deref, DerefMut};
 use std::ptr::null_mut;
 
+use aoc_macros::aoc;
 use itertools::Itertools;
 
 use crate::inputs::read_contents;
@@ -313,12 +314,14 @@ enum Operation {
     Noop,
 }
 
+#[aoc(year = 2021, day = 18, part = 1)]
 pub fn solve_a() {
     let tree_ptr = TreePtr::from(read_contents(18).lines().collect_vec());
 
     println!("Solution A: {}", tree_ptr.magnitude());
 }
 
+#[aoc(year = 2021, day = 18, part = 2)]
 pub fn solve_b() {
     let input = read_contents(18);
     let lines = input.lines().collect_vec();
@@ -1,6 +1,8 @@
 use std::collections::{BTreeSet, HashMap, HashSet, VecDeque};
 use std::ops::{Add, Sub};
 
+use aoc_macros::aoc;
+use once_cell::sync::OnceCell;
 use regex::Regex;
 
 use crate::inputs::read_contents;
@@ -175,18 +177,25 @@ fn coalesce_all_points(mut scanners: VecDeque<Scanner>) -> (HashSet<Beacon>, Vec
     (beacons, centers)
 }
 
+static COALESCED: OnceCell<(HashSet<Beacon>, Vec<Beacon>)> = OnceCell::new();
+
+/// Both parts need every scanner lined up, which is by far the slow bit, so it is only done once
+fn coalesced_input() -> &'static (HashSet<Beacon>, Vec<Beacon>) {
+    COALESCED.get_or_init(|| coalesce_all_points(parse_input(&read_contents(19))))
+}
+
+#[aoc(year = 2021, day = 19, part = 1)]
 pub fn solve_a() {
-    let scanners = parse_input(&read_contents(19));
-    let (points, _) = coalesce_all_points(scanners);
+    let (points, _) = coalesced_input();
 
     println!("Solution A: {}", points.len());
 }
 
+#[aoc(year = 2021, day = 19, part = 2)]
 pub fn solve_b() {
-    let scanners = parse_input(&read_contents(19));
-    let (_, centers) = coalesce_all_points(scanners);
+    let (_, centers) = coalesced_input();
 
-    println!("Solution B: {}", max_manhattan_distance(&centers));
+    println!("Solution B: {}", max_manhattan_distance(centers));
 }
 
 #[cfg(test)]
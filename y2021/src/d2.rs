@@ -1,3 +1,5 @@
+use aoc_macros::aoc;
+
 use crate::inputs::read_contents;
 
 #[derive(Debug)]
@@ -23,6 +25,7 @@ fn parse_course(input: &str) -> Vec<Command> {
          .collect()
 }
 
+#[aoc(year = 2021, day = 2, part = 1)]
 pub fn solve_a() {
     let course = parse_course(&read_contents(2));
     let (x, y) = run_course(&course);
@@ -46,6 +49,7 @@ fn run_course(course: &Vec<Command>) -> (usize, usize) {
     (horizontal, depth)
 }
 
+#[aoc(year = 2021, day = 2, part = 2)]
 pub fn solve_b() {
     let course = parse_course(&read_contents(2));
     let (x, y) = run_course_with_aim(&course);
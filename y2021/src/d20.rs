@@ -1,53 +1,37 @@
-use std::collections::{HashMap, HashSet};
 use std::fmt::{Debug, Formatter};
 
+use aoc_macros::aoc;
+use aoc_utils::{Grid, Point2, SparseGrid};
+use aoc_utils::automaton::{Automaton, Topology};
 use itertools::Itertools;
 use crate::inputs::read_contents;
 
-#[derive(Eq, PartialEq, Hash)]
-struct Point(isize, isize);
-
-impl Point {
-    fn surroundings(&self) -> [Point; 9] {
-        [
-            Point(self.0 - 1, self.1 - 1),
-            Point(self.0, self.1 - 1),
-            Point(self.0 + 1, self.1 - 1),
-            Point(self.0 - 1, self.1),
-            Point(self.0, self.1),
-            Point(self.0 + 1, self.1),
-            Point(self.0 - 1, self.1 + 1),
-            Point(self.0, self.1 + 1),
-            Point(self.0 + 1, self.1 + 1),
-        ]
+/// The 3x3 square centred on a pixel in reading order, the pixel itself included
+struct Block;
+
+impl Topology for Block {
+    type Cell = Point2;
+
+    fn neighbours(&self, point: &Point2) -> Vec<Point2> {
+        (-1..=1).flat_map(|dy| (-1..=1).map(move |dx| *point + Point2::new([dx, dy]))).collect()
+    }
+
+    fn degree(&self) -> usize {
+        9
     }
 }
 
 struct Image {
-    image: HashMap<Point, char>,
+    image: Automaton<Block, char>,
     enhancer: Vec<char>,
-    alternate: bool,
 }
 
 impl Debug for Image {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        let (min_x, max_x, min_y, max_y) = self.image
-                                               .keys()
-                                               .fold((isize::MAX, isize::MIN, isize::MAX, isize::MIN),
-                                                     |(min_x, max_x, min_y, max_y), p| {
-                                                         let min_x = if p.0 < min_x { p.0 } else { min_x };
-                                                         let max_x = if p.0 > max_x { p.0 } else { max_x };
-                                                         let min_y = if p.1 < min_y { p.0 } else { min_y };
-                                                         let max_y = if p.1 > max_y { p.0 } else { max_y };
-
-                                                         (min_x, max_x, min_y, max_y)
-                                                     });
-
-        let image = (min_y..=max_y)
-            .map(|y| {
-                (min_x..=max_x).map(|x| self.image.get(&Point(x, y)).unwrap())
-                               .collect::<String>()
-            }).join("\n");
+        let mut image = SparseGrid::new(*self.image.background());
+        for (pt, &c) in self.image.cells() {
+            image.insert(pt, c);
+        }
 
         write!(f, "{}", image)
     }
@@ -60,77 +44,31 @@ impl From<&str> for Image {
 
         let enhancer = conversion.chars().collect_vec();
 
-        let image = image.lines()
-                         .enumerate()
-                         .fold(HashMap::new(), |mut acc, (rn, line)| {
-                             for (cn, c) in line.chars().enumerate() {
-                                 acc.insert(Point(cn as isize, rn as isize), c);
-                             }
-                             acc
-                         });
-
-        let alternate = enhancer[0] == '#';
-        Self { enhancer, image, alternate }
+        let image = Grid::parse_with(image, Ok::<_, String>).unwrap_or_else(|e| panic!("{}", e));
+        let pixels = image.iter().map(|((x, y), &c)| (Point2::new([x as i64, y as i64]), c));
+
+        // the background starts dark, but flips every step if a dark block lights up
+        Self { enhancer, image: Automaton::unbounded(Block, pixels, '.') }
     }
 }
 
 impl Image {
-    fn current_image_points(&self) -> HashSet<Point> {
-        let mut points = HashSet::new();
-
-        for p in self.image.keys() {
-            points.extend(p.surroundings());
-        }
-
-        points
-    }
-
     fn evolve(&mut self, times: usize) {
-        for i in 0..times {
-            self.step_once(i);
-        }
-    }
-
-    fn step_once(&mut self, step: usize) {
-        let mut next_image = HashMap::new();
-
-        for pt in self.current_image_points() {
-            let state = self.get_next_substate(&pt, step);
-            next_image.insert(pt, state);
-        }
-
-        self.image = next_image;
-    }
-
-    fn get_next_substate(&self, point: &Point, step: usize) -> char {
-        let mut v: usize = 0;
-        for (i, p) in point.surroundings().iter().enumerate() {
-            let c = match self.image.get(p) {
-                None => {
-                    if self.alternate {
-                        if step % 2 == 0 { self.enhancer[511] } else { self.enhancer[0] }
-                    } else {
-                        '.'
-                    }
-                }
-                Some(c) => *c,
-            };
-            if c == '#' {
-                v += 1 << (8 - i);
-            }
+        let enhancer = &self.enhancer;
+        for _ in 0..times {
+            self.image.par_step(|_, block| {
+                enhancer[block.iter().fold(0, |v, &&c| (v << 1) | (c == '#') as usize)]
+            });
         }
-        self.enhancer[v]
     }
 
     fn num_lit_pixels(&self) -> usize {
-        self.image
-            .values()
-            .filter(|c| **c == '#')
-            .count()
+        self.image.count(&'#')
     }
 }
 
 
+#[aoc(year = 2021, day = 20, part = 1)]
 pub fn solve_a() {
     let mut image = Image::from(read_contents(20).as_str());
     image.evolve(2);
@@ -139,6 +77,7 @@ pub fn solve_a() {
     println!("Solution A: {}", ans);
 }
 
+#[aoc(year = 2021, day = 20, part = 2)]
 pub fn solve_b() {
     let mut image = Image::from(read_contents(20).as_str());
     image.evolve(50);
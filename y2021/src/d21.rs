@@ -1,9 +1,12 @@
 use std::cmp::max;
 use std::collections::HashMap;
 
+use aoc_macros::aoc;
+
 const P1: usize = 3;
 const P2: usize = 7;
 
+#[aoc(year = 2021, day = 21, part = 1)]
 pub fn solve_a() {
     let ans = simulate_deterministic(P1, P2, 100, 1000);
     println!("Solution A: {}", ans);
@@ -50,6 +53,7 @@ fn simulate_deterministic(p1: usize, p2: usize, sides: usize, target: usize) ->
     unreachable!()
 }
 
+#[aoc(year = 2021, day = 21, part = 2)]
 pub fn solve_b() {
     let mut sim = MultiDimensionSimulator::new(21);
     let (w1, w2) = sim.simulate(P1, P2, 0, 0, true);
@@ -1,122 +1,33 @@
-use std::ops::{RangeInclusive, Sub};
-
-use regex::Regex;
+use aoc_macros::{aoc, AocParse};
+use aoc_utils::{Cuboid, Interval};
 
 use crate::inputs::read_contents;
 
+#[derive(AocParse)]
 enum State {
+    #[aoc(pattern = "on")]
     On,
+    #[aoc(pattern = "off")]
     Off,
 }
 
-impl From<&str> for State {
-    fn from(value: &str) -> Self {
-        match value {
-            "on" => State::On,
-            "off" => State::Off,
-            _ => panic!("Invalid value for State: '{}'", value)
-        }
-    }
-}
-
-#[derive(Clone, Debug)]
-struct Cuboid {
-    x: RangeInclusive<isize>,
-    y: RangeInclusive<isize>,
-    z: RangeInclusive<isize>,
-}
-
-impl Cuboid {
-    fn x_min(&self) -> isize { *self.x.start() }
-    fn x_max(&self) -> isize { *self.x.end() }
-    fn y_min(&self) -> isize { *self.y.start() }
-    fn y_max(&self) -> isize { *self.y.end() }
-    fn z_min(&self) -> isize { *self.z.start() }
-    fn z_max(&self) -> isize { *self.z.end() }
-
-    fn overlaps(&self, other: &Cuboid) -> bool {
-        other.x_min() <= self.x_max()
-            && other.x_max() >= self.x_min()
-            && other.y_min() <= self.y_max()
-            && other.y_max() >= self.y_min()
-            && other.z_min() <= self.z_max()
-            && other.z_max() >= self.z_min()
-    }
-
-    fn is_empty(&self) -> bool {
-        self.x.is_empty() || self.y.is_empty() || self.z.is_empty()
-    }
-
-    fn volume(&self) -> usize {
-        self.x.to_owned().count()
-            * self.y.to_owned().count()
-            * self.z.to_owned().count()
-    }
-}
-
-impl Sub<&Cuboid> for &Cuboid {
-    type Output = Vec<Cuboid>;
-
-    fn sub(self, rhs: &Cuboid) -> Self::Output {
-        if self.overlaps(rhs) {
-            [
-                Cuboid {
-                    x: self.x_min()..=(rhs.x_min() - 1),
-                    y: self.y_min()..=self.y_max(),
-                    z: self.z_min()..=self.z_max(),
-                },
-                Cuboid {
-                    x: (rhs.x_max() + 1)..=self.x_max(),
-                    y: self.y_min()..=self.y_max(),
-                    z: self.z_min()..=self.z_max(),
-                },
-                Cuboid {
-                    x: self.x_min().max(rhs.x_min())..=self.x_max().min(rhs.x_max()),
-                    y: self.y_min()..=(rhs.y_min() - 1),
-                    z: self.z_min()..=self.z_max(),
-                },
-                Cuboid {
-                    x: self.x_min().max(rhs.x_min())..=self.x_max().min(rhs.x_max()),
-                    y: (rhs.y_max() + 1)..=self.y_max(),
-                    z: self.z_min()..=self.z_max(),
-                },
-                Cuboid {
-                    x: self.x_min().max(rhs.x_min())..=self.x_max().min(rhs.x_max()),
-                    y: self.y_min().max(rhs.y_min())..=self.y_max().min(rhs.y_max()),
-                    z: self.z_min()..=(rhs.z_min() - 1),
-                },
-                Cuboid {
-                    x: self.x_min().max(rhs.x_min())..=self.x_max().min(rhs.x_max()),
-                    y: self.y_min().max(rhs.y_min())..=self.y_max().min(rhs.y_max()),
-                    z: (rhs.z_max() + 1)..=self.z_max(),
-                },
-            ]
-                .into_iter()
-                .filter(|c| !c.is_empty())
-                .collect()
-        } else {
-            vec![self.to_owned()]
-        }
-    }
-}
-
 struct RebootStep {
     state: State,
-    cuboid: Cuboid,
+    cuboid: Cuboid<3>,
 }
 
 
 trait Overlap {
-    fn find_combined_active_regions(&self) -> Vec<Cuboid>;
+    fn find_combined_active_regions(&self) -> Vec<Cuboid<3>>;
 }
 
 
 impl Overlap for &[RebootStep] {
-    fn find_combined_active_regions(&self) -> Vec<Cuboid> {
-        let mut active_regions = vec![];
+    fn find_combined_active_regions(&self) -> Vec<Cuboid<3>> {
+        let mut active_regions: Vec<Cuboid<3>> = vec![];
 
         for step in self.iter() {
-            let cuboid = step.cuboid.to_owned();
+            let cuboid = step.cuboid;
 
             match step.state {
                 State::On => {
@@ -124,14 +35,14 @@ impl Overlap for &[RebootStep] {
                         active_regions.iter()
                                       .fold(vec![cuboid], |cuts, active_cuboid| {
                                           cuts.iter()
-                                              .flat_map(|c| c - active_cuboid)
+                                              .flat_map(|c| c.difference(active_cuboid))
                                               .collect()
                                       })
                     );
                 }
                 State::Off => {
                     active_regions = active_regions.iter()
-                                                   .flat_map(|c| c - &cuboid)
+                                                   .flat_map(|c| c.difference(&cuboid))
                                                    .collect();
                 }
             }
@@ -141,33 +52,34 @@ impl Overlap for &[RebootStep] {
     }
 }
 
-fn parse_reboot_steps(input: &str) -> Vec<RebootStep> {
-    let re = Regex::new(r"(?<cmd>(on|off)) x=(?<x1>-?\d+)..(?<x2>-?\d+),y=(?<y1>-?\d+)..(?<y2>-?\d+),z=(?<z1>-?\d+)..(?<z2>-?\d+)").unwrap();
-
-    let mut steps = vec![];
-
-    for line in input.lines() {
-        let caps = re.captures(line).expect(&format!("Could not parse: '{}'", line));
-
-        let state = State::from(caps.name("cmd").unwrap().as_str());
-        let x1: isize = caps.name("x1").unwrap().as_str().parse().unwrap();
-        let x2: isize = caps.name("x2").unwrap().as_str().parse().unwrap();
-        let y1: isize = caps.name("y1").unwrap().as_str().parse().unwrap();
-        let y2: isize = caps.name("y2").unwrap().as_str().parse().unwrap();
-        let z1: isize = caps.name("z1").unwrap().as_str().parse().unwrap();
-        let z2: isize = caps.name("z2").unwrap().as_str().parse().unwrap();
-
-        let cuboid = Cuboid {
-            x: x1..=x2,
-            y: y1..=y2,
-            z: z1..=z2,
-        };
-        steps.push(RebootStep { state, cuboid })
-    }
+#[derive(AocParse)]
+#[aoc(pattern = "{state} x={x1}..{x2},y={y1}..{y2},z={z1}..{z2}")]
+struct RebootLine {
+    state: State,
+    x1: i64,
+    x2: i64,
+    y1: i64,
+    y2: i64,
+    z1: i64,
+    z2: i64,
+}
 
-    steps
+fn parse_reboot_steps(input: &str) -> Vec<RebootStep> {
+    input.lines()
+         .map(|line| {
+             let line: RebootLine = line.parse().unwrap_or_else(|e| panic!("{}", e));
+             let cuboid = Cuboid::new([
+                 Interval::inclusive(line.x1, line.x2),
+                 Interval::inclusive(line.y1, line.y2),
+                 Interval::inclusive(line.z1, line.z2),
+             ]);
+
+             RebootStep { state: line.state, cuboid }
+         })
+         .collect()
 }
 
+#[aoc(year = 2021, day = 22, part = 1)]
 pub fn solve_a() {
     let steps = parse_reboot_steps(&read_contents(22));
     let ans = num_active_reactors(&steps, -50, 50);
@@ -176,17 +88,18 @@ pub fn solve_a() {
 }
 
 fn num_active_reactors(steps: &[RebootStep], ll: isize, ul: isize) -> usize {
+    // saturating so that the whole space can be asked for
+    let limits = Cuboid::new([Interval::new(ll as i64, (ul as i64).saturating_add(1)); 3]);
+
     steps
         .find_combined_active_regions()
         .iter()
-        .map(|c| Cuboid {
-            x: c.x_min().max(ll)..=c.x_max().min(ul),
-            y: c.y_min().max(ll)..=c.y_max().min(ul),
-            z: c.z_min().max(ll)..=c.z_max().min(ul),
-        }.volume())
+        .filter_map(|c| c.intersection(&limits))
+        .map(|c| c.volume() as usize)
         .sum()
 }
 
+#[aoc(year = 2021, day = 22, part = 2)]
 pub fn solve_b() {
     let steps = parse_reboot_steps(&read_contents(22));
     let ans = num_active_reactors(&steps, isize::MIN, isize::MAX);
@@ -1,7 +1,10 @@
-use std::cmp::Ordering;
-use std::collections::{BinaryHeap, HashMap};
+use std::collections::HashMap;
 use Amphipod::*;
 
+use aoc_macros::aoc;
+use aoc_utils::search;
+
+#[aoc(year = 2021, day = 23, part = 1)]
 pub fn solve_a() {
     /*
     #############
@@ -221,24 +224,6 @@ impl Configuration {
     }
 }
 
-#[derive(Clone, Eq, PartialEq)]
-struct State {
-    cost: usize,
-    configuration: Configuration,
-}
-
-impl PartialOrd for State {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.cmp(other))
-    }
-}
-
-impl Ord for State {
-    fn cmp(&self, other: &Self) -> Ordering {
-        other.cost.cmp(&self.cost)
-    }
-}
-
 fn organization_cost(side_rooms: &SideRooms) -> Option<usize> {
     let configuration = Configuration {
         side_rooms: side_rooms.to_owned(),
@@ -246,52 +231,12 @@ fn organization_cost(side_rooms: &SideRooms) -> Option<usize> {
         room_capacity: side_rooms[0].len(),
     };
 
-    let mut heap = BinaryHeap::new();
-    let mut energy_to: HashMap<Configuration, usize> = HashMap::new();
-
-    energy_to.insert(configuration.to_owned(), 0);
-
-    heap.push(State {
-        cost: 0,
-        configuration,
-    });
-
-    while let Some(State {
-                       cost,
-                       configuration,
-                   }) = heap.pop()
-    {
-        if configuration.is_final() {
-            return Some(cost);
-        }
-
-        if let Some(old_cost) = energy_to.get(&configuration) {
-            if cost > *old_cost {
-                continue;
-            }
-        }
-
-        for (next_configuration, extra_cost) in configuration.adjacent_configurations() {
-            let next = State {
-                cost: cost + extra_cost,
-                configuration: next_configuration,
-            };
-
-            if next.cost
-                < *energy_to
-                .get(&next.configuration)
-                .unwrap_or(&(next.cost + 1))
-            {
-                energy_to.insert(next.configuration.clone(), next.cost);
-                heap.push(next);
-            }
-        }
-    }
-
-    None
+    search::dijkstra(&mut Configuration::adjacent_configurations, configuration, Configuration::is_final)
+        .map(|path| path.cost)
 }
 
 
+#[aoc(year = 2021, day = 23, part = 2)]
 pub fn solve_b() {
     let input = "#############
 #...........#
@@ -1,148 +1,363 @@
+use std::collections::{HashSet, VecDeque};
+
+use aoc_macros::{aoc, AocParse};
+use aoc_utils::Interval;
+use aoc_utils::machine::{self, Flow, Machine, Registers, Stop, Value};
+
 use crate::inputs::read_contents;
 
-// this is not necessary, but is here due to a previous implementation
+const REGISTERS: [char; 4] = ['w', 'x', 'y', 'z'];
+
+/// Values that don't fit in an `i64` (or are `i64::MAX`, so that an inclusive range of them still
+/// fits in an [`Interval`]) crash the ALU
+const LOWEST: i128 = i64::MIN as i128;
+const HIGHEST: i128 = i64::MAX as i128 - 1;
+
+#[derive(AocParse, Debug, Clone, Copy, PartialEq, Eq)]
 enum Instruction {
-    Noop,
-    Add(i64),
-    Div(i64),
+    #[aoc(pattern = "inp {0}")]
+    Inp(char),
+    #[aoc(pattern = "add {0} {1}")]
+    Add(char, Value),
+    #[aoc(pattern = "mul {0} {1}")]
+    Mul(char, Value),
+    #[aoc(pattern = "div {0} {1}")]
+    Div(char, Value),
+    #[aoc(pattern = "mod {0} {1}")]
+    Mod(char, Value),
+    #[aoc(pattern = "eql {0} {1}")]
+    Eql(char, Value),
 }
 
-struct Block {
-    div_z: i64,
-    add_x: i64,
-    add_y: i64,
+fn parse_program(input: &str) -> Vec<Instruction> {
+    input.lines()
+         .map(|line| {
+             let instruction: Instruction = line.parse().unwrap_or_else(|e| panic!("{}", e));
+             let operand = instruction.operand().and_then(|b| match b {
+                 Value::Register(b) => Some(b),
+                 Value::Literal(_) => None,
+             });
+             for register in [Some(instruction.target()), operand].into_iter().flatten() {
+                 assert!(REGISTERS.contains(&register), "Invalid register '{}' in '{}'", register, line);
+             }
+             instruction
+         })
+         .collect()
 }
 
-fn parse_blocks(input: &str) -> Vec<Block> {
-    let instructions: Vec<Instruction> = {
-        fn parse_values(line: &str) -> Option<i64> {
-            match line.split_once(" ") {
-                None => None,
-                Some((_, value)) => {
-                    if value.len() == 1 && value.chars().next().unwrap().is_alphabetic() {
-                        None
-                    } else {
-                        Some(value.parse::<i64>().expect(&format!("Could not parse value: {}", value)))
-                    }
-                }
-            }
+fn index(register: char) -> usize {
+    REGISTERS.iter().position(|&r| r == register).unwrap()
+}
+
+impl Instruction {
+    /// The register the instruction writes
+    fn target(&self) -> char {
+        match *self {
+            Instruction::Inp(a) |
+            Instruction::Add(a, _) |
+            Instruction::Mul(a, _) |
+            Instruction::Div(a, _) |
+            Instruction::Mod(a, _) |
+            Instruction::Eql(a, _) => a,
         }
+    }
 
-        input.lines()
-             .map(|line| {
-                 let (cmd, rest) = line.split_once(" ").unwrap();
-                 let value = parse_values(rest);
-                 if let Some(value) = value {
-                     match cmd {
-                         "add" => Instruction::Add(value),
-                         "div" => Instruction::Div(value),
-                         _ => Instruction::Noop
-                     }
-                 } else {
-                     Instruction::Noop
-                 }
-             })
-             .collect()
-    };
-
-    assert_eq!(instructions.len() % 18, 0);
-
-    (0..instructions.len())
-        .step_by(18)
-        .map(|i| {
-            let div_z = match &instructions[i + 4] {
-                Instruction::Div(i) => *i,
-                _ => unreachable!()
-            };
+    fn operand(&self) -> Option<Value> {
+        match *self {
+            Instruction::Inp(_) => None,
+            Instruction::Add(_, b) |
+            Instruction::Mul(_, b) |
+            Instruction::Div(_, b) |
+            Instruction::Mod(_, b) |
+            Instruction::Eql(_, b) => Some(b),
+        }
+    }
 
-            let add_x = match &instructions[i + 5] {
-                Instruction::Add(i) => *i,
-                _ => unreachable!()
-            };
+    /// `a` combined with `b`, or `None` where the ALU crashes
+    fn apply(&self, a: i64, b: i64) -> Option<i64> {
+        let (a, b) = (a as i128, b as i128);
+        let v = match self {
+            Instruction::Inp(_) => unreachable!("inp has no operand"),
+            Instruction::Add(..) => a + b,
+            Instruction::Mul(..) => a * b,
+            Instruction::Div(..) if b == 0 => return None,
+            Instruction::Div(..) => a / b,
+            Instruction::Mod(..) if a < 0 || b <= 0 => return None,
+            Instruction::Mod(..) => a % b,
+            Instruction::Eql(..) => (a == b) as i128,
+        };
+        (LOWEST..=HIGHEST).contains(&v).then_some(v as i64)
+    }
 
-            let add_y = match &instructions[i + 15] {
-                Instruction::Add(i) => *i,
-                _ => unreachable!()
-            };
+    /// Bounds on what `apply` gives for any `a` and `b` in the (non-empty) ranges, leaving out the
+    /// combinations that crash. `None` if every one of them does.
+    fn apply_range(&self, a: Interval, b: Interval) -> Option<Interval> {
+        let (alo, ahi) = (a.start as i128, a.end as i128 - 1);
+        let (blo, bhi) = (b.start as i128, b.end as i128 - 1);
+        let hull = |values: &[i128]| (*values.iter().min().unwrap(), *values.iter().max().unwrap());
 
-            assert!(div_z == 1 || div_z == 26);
+        let (lo, hi) = match self {
+            Instruction::Inp(_) => unreachable!("inp has no operand"),
+            Instruction::Add(..) => (alo + blo, ahi + bhi),
+            Instruction::Mul(..) => hull(&[alo * blo, alo * bhi, ahi * blo, ahi * bhi]),
+            Instruction::Div(..) => {
+                // the quotient is monotonic in each argument as long as the divisor keeps its sign
+                let quotients = [(blo, bhi.min(-1)), (blo.max(1), bhi)]
+                    .into_iter()
+                    .filter(|(lo, hi)| lo <= hi)
+                    .flat_map(|(lo, hi)| [alo / lo, alo / hi, ahi / lo, ahi / hi])
+                    .collect::<Vec<_>>();
+                if quotients.is_empty() {
+                    return None;
+                }
+                hull(&quotients)
+            }
+            Instruction::Mod(..) => {
+                let (alo, blo) = (alo.max(0), blo.max(1));
+                if alo > ahi || blo > bhi {
+                    return None;
+                }
+                if ahi < blo {
+                    (alo, ahi)
+                } else if blo == bhi && ahi - alo < blo && alo % blo <= ahi % blo {
+                    (alo % blo, ahi % blo)
+                } else {
+                    (0, ahi.min(bhi - 1))
+                }
+            }
+            Instruction::Eql(..) if alo == ahi && blo == bhi && alo == blo => (1, 1),
+            Instruction::Eql(..) if ahi < blo || bhi < alo => (0, 0),
+            Instruction::Eql(..) => (0, 1),
+        };
 
-            Block { div_z, add_x, add_y }
-        })
-        .collect()
+        let (lo, hi) = (lo.max(LOWEST), hi.min(HIGHEST));
+        (lo <= hi).then(|| Interval::inclusive(lo as i64, hi as i64))
+    }
 }
 
-impl Block {
-    fn calculate(&self, z: i64, w: i64) -> i64 {
-        let x = if (z % 26 + self.add_x) == w { 0 } else { 1 };
-        let z = z / self.div_z;
+/// Inputs are read from the state, and `inp` waits when there are none left. The machine halts
+/// when the ALU crashes.
+impl machine::Instruction<VecDeque<i64>> for Instruction {
+    type Register = char;
 
-        z * (25 * x + 1) + (w + self.add_y) * x
+    fn execute(&self, registers: &mut Registers, input: &mut VecDeque<i64>) -> Flow {
+        match (self, self.operand()) {
+            (Instruction::Inp(a), _) => match input.pop_front() {
+                Some(v) => registers.set(*a, v),
+                None => return Flow::Wait,
+            },
+            (_, Some(b)) => match self.apply(registers.get(&self.target()), registers.value(&b)) {
+                Some(v) => registers.set(self.target(), v),
+                None => return Flow::Halt,
+            },
+            (_, None) => unreachable!(),
+        }
+        Flow::Next
     }
 }
 
-enum SearchType {
-    Largest,
-    Smallest,
+/// Runs the program on `input`, returning the registers if it gets to the end without crashing or
+/// running out of input
+fn run_alu(program: &[Instruction], input: &[i64]) -> Option<Registers> {
+    let mut machine = Machine::with_state(program, input.iter().copied().collect());
+    match machine.run() {
+        Stop::Exited => Some(machine.into_parts().0),
+        _ => None,
+    }
 }
 
-impl SearchType {
-    fn get_range(&self) -> Vec<i64> {
-        match self {
-            SearchType::Smallest => (1..=9).collect(),
-            SearchType::Largest => (1..=9).rev().collect(),
-        }
-    }
+/// Whether MONAD accepts the model number, which has to have a digit for every `inp`
+fn is_valid(program: &[Instruction], model_number: i64) -> bool {
+    let digits = model_number.to_string().bytes().map(|b| (b - b'0') as i64).collect::<Vec<_>>();
+    run_alu(program, &digits).is_some_and(|registers| registers.get(&'z') == 0)
 }
 
-fn search(blocks: &[Block], search_type: &SearchType) -> i64 {
-    for next_w in search_type.get_range() {
-        if let Some(ans) = search_node(blocks, 0, next_w, 0, search_type) {
-            return ans
-                .iter()
-                .fold(0, |acc, n| acc * 10 + *n);
-        }
+/// Bounds on every register after running the program from `pc`, starting within `registers` and
+/// with every input within `input`. `None` if it is certain to crash.
+fn evaluate_ranges(program: &[Instruction], pc: usize, registers: [Interval; 4], input: Interval) -> Option<[Interval; 4]> {
+    let mut registers = registers;
+    for instruction in program[pc..].iter() {
+        let a = index(instruction.target());
+        registers[a] = match instruction.operand() {
+            None => input,
+            Some(Value::Register(b)) => instruction.apply_range(registers[a], registers[index(b)])?,
+            Some(Value::Literal(b)) => instruction.apply_range(registers[a], Interval::inclusive(b, b))?,
+        };
     }
+    Some(registers)
+}
 
-    unreachable!()
+/// Searches for model numbers digit by digit. Before every `inp` it works out the range `z` could
+/// end in, and gives up on the digits so far when that can't include 0. It remembers the states that
+/// failed, looking only at the registers that are read again before they are written.
+struct Monad {
+    program: Vec<Instruction>,
+    /// The registers whose values matter before each instruction
+    live: Vec<[bool; 4]>,
 }
 
-fn search_node(blocks: &[Block], z: i64, w: i64, index: usize, search_type: &SearchType) -> Option<Vec<i64>> {
-    let block = &blocks[index];
+impl Monad {
+    fn new(program: Vec<Instruction>) -> Self {
+        let mut live = vec![[false; 4]; program.len() + 1];
+        live[program.len()][index('z')] = true;
+        for (pc, instruction) in program.iter().enumerate().rev() {
+            let mut before = live[pc + 1];
+            let a = index(instruction.target());
+            match (instruction, instruction.operand()) {
+                // these overwrite the register without reading it
+                (Instruction::Inp(_), _) | (Instruction::Mul(_, Value::Literal(0)), _) => before[a] = false,
+                (_, Some(Value::Register(b))) => {
+                    before[a] = true;
+                    before[index(b)] = true;
+                }
+                _ => before[a] = true,
+            }
+            live[pc] = before;
+        }
+
+        Monad { program, live }
+    }
+
+    /// Runs up to the next `inp` or the end, returning where it stopped, or `None` if it crashed
+    fn run_to_input(&self, pc: usize, registers: &mut [i64; 4]) -> Option<usize> {
+        for (pc, instruction) in self.program.iter().enumerate().skip(pc) {
+            let a = index(instruction.target());
+            registers[a] = match instruction.operand() {
+                None => return Some(pc),
+                Some(b) => {
+                    let b = match b {
+                        Value::Register(b) => registers[index(b)],
+                        Value::Literal(b) => b,
+                    };
+                    instruction.apply(registers[a], b)?
+                }
+            };
+        }
+        Some(self.program.len())
+    }
 
-    // based on block.calculate, if this condition is not true, we diverge from z
-    if block.div_z == 26 && z % 26 + block.add_x != w {
-        return None;
+    /// The model number made from the first of `digits` that works in every position, trying them
+    /// in order
+    fn search(&self, digits: &[i64]) -> Option<i64> {
+        let mut failed = HashSet::new();
+        let number = self.search_from(0, [0; 4], digits, &mut failed)?;
+        Some(number.iter().fold(0, |acc, d| acc * 10 + d))
     }
 
-    let z = blocks[index].calculate(z, w);
+    fn search_from(&self, pc: usize, registers: [i64; 4], digits: &[i64], failed: &mut HashSet<(usize, [i64; 4])>) -> Option<Vec<i64>> {
+        let mut registers = registers;
+        let pc = self.run_to_input(pc, &mut registers)?;
+        if pc == self.program.len() {
+            return (registers[index('z')] == 0).then(Vec::new);
+        }
 
-    if index == 13 {
-        if z == 0 {
-            return Some(vec![w]);
-        } else {
+        let mut key = registers;
+        for (value, live) in key.iter_mut().zip(self.live[pc]) {
+            if !live {
+                *value = 0;
+            }
+        }
+        if failed.contains(&(pc, key)) {
             return None;
         }
-    }
 
-    for next_w in search_type.get_range() {
-        if let Some(ans) = search_node(blocks, z, next_w, index + 1, search_type) {
-            return Some([vec![w], ans].concat());
+        let input = Interval::inclusive(*digits.iter().min()?, *digits.iter().max()?);
+        let ranges = evaluate_ranges(&self.program, pc, registers.map(|v| Interval::inclusive(v, v)), input);
+        if ranges.is_some_and(|ranges| ranges[index('z')].contains(0)) {
+            for &digit in digits {
+                let mut next = registers;
+                next[index(self.program[pc].target())] = digit;
+                if let Some(mut rest) = self.search_from(pc + 1, next, digits, failed) {
+                    rest.insert(0, digit);
+                    return Some(rest);
+                }
+            }
         }
+
+        failed.insert((pc, key));
+        None
     }
-    None
 }
 
+#[aoc(year = 2021, day = 24, part = 1)]
 pub fn solve_a() {
-    let blocks = parse_blocks(&read_contents(24));
+    let program = parse_program(&read_contents(24));
+    let ans = Monad::new(program.clone()).search(&[9, 8, 7, 6, 5, 4, 3, 2, 1]).expect("No model number is valid");
 
-    let ans = search(&blocks, &SearchType::Largest);
+    assert!(is_valid(&program, ans));
     println!("Solution A: {}", ans);
 }
 
+#[aoc(year = 2021, day = 24, part = 2)]
 pub fn solve_b() {
-    let blocks = parse_blocks(&read_contents(24));
+    let program = parse_program(&read_contents(24));
+    let ans = Monad::new(program.clone()).search(&[1, 2, 3, 4, 5, 6, 7, 8, 9]).expect("No model number is valid");
 
-    let ans = search(&blocks, &SearchType::Smallest);
+    assert!(is_valid(&program, ans));
     println!("Solution B: {}", ans);
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BINARY: &str = "inp w
+add z w
+mod z 2
+div w 2
+add y w
+mod y 2
+div w 2
+add x w
+mod x 2
+div w 2
+mod w 2";
+
+    #[test]
+    fn test_run_alu() {
+        let negate = parse_program("inp x\nmul x -1");
+        assert_eq!(run_alu(&negate, &[7]).unwrap().get(&'x'), -7);
+        assert_eq!(run_alu(&negate, &[]), None);
+
+        let triple = parse_program("inp z\ninp x\nmul z 3\neql z x");
+        assert_eq!(run_alu(&triple, &[3, 9]).unwrap().get(&'z'), 1);
+        assert_eq!(run_alu(&triple, &[3, 8]).unwrap().get(&'z'), 0);
+
+        let registers = run_alu(&parse_program(BINARY), &[13]).unwrap();
+        assert_eq!(REGISTERS.map(|r| registers.get(&r)), [1, 1, 0, 1]);
+
+        assert_eq!(run_alu(&parse_program("inp w\nmod w 2"), &[-3]), None);
+        assert_eq!(run_alu(&parse_program("inp w\ndiv w z"), &[5]), None);
+    }
+
+    #[test]
+    fn test_evaluate_ranges() {
+        let binary = parse_program(BINARY);
+        let zero = Interval::inclusive(0, 0);
+        let ranges = evaluate_ranges(&binary, 0, [zero; 4], Interval::inclusive(0, 15)).unwrap();
+        assert_eq!(ranges, [Interval::inclusive(0, 1); 4]);
+
+        // exact for a single input
+        let ranges = evaluate_ranges(&binary, 0, [zero; 4], Interval::inclusive(13, 13)).unwrap();
+        assert_eq!(ranges.map(|r| r.start), [1, 1, 0, 1]);
+
+        let ranges = evaluate_ranges(&parse_program("inp x\nmul x -3\nadd x 2\ndiv x 2"), 0, [zero; 4], Interval::inclusive(-1, 4)).unwrap();
+        assert_eq!(ranges[index('x')], Interval::inclusive(-5, 2));
+
+        assert_eq!(evaluate_ranges(&parse_program("inp w\nadd w -20\nmod w 3"), 0, [zero; 4], Interval::inclusive(1, 9)), None);
+    }
+
+    #[test]
+    fn test_search() {
+        // accepts exactly the numbers whose first digit is three more than the second
+        let program = parse_program("inp w\ninp x\nadd z w\nmul x -1\nadd z x\nadd z -3");
+        let monad = Monad::new(program.clone());
+
+        assert_eq!(monad.search(&[9, 8, 7, 6, 5, 4, 3, 2, 1]), Some(96));
+        assert_eq!(monad.search(&[1, 2, 3, 4, 5, 6, 7, 8, 9]), Some(41));
+        assert!(is_valid(&program, 63) && !is_valid(&program, 64));
+
+        let program = parse_program("inp w\nadd z w\nadd z 10");
+        assert_eq!(Monad::new(program).search(&[1, 2, 3, 4, 5, 6, 7, 8, 9]), None);
+    }
+}
@@ -1,7 +1,7 @@
 use std::collections::{HashMap, HashSet};
 use std::fmt::{Display, Formatter};
 
-use aoc_macros::hashmap;
+use aoc_macros::{aoc, hashmap};
 
 use crate::inputs::read_contents;
 
@@ -174,6 +174,7 @@ impl Grid {
 }
 
 
+#[aoc(year = 2021, day = 25, part = 1)]
 pub fn solve_a() {
     let grid = Grid::from(read_contents(25).as_str());
     let ans = grid.get_stop_step();
@@ -181,6 +182,7 @@ pub fn solve_a() {
     println!("Solution A: {}", ans);
 }
 
+#[aoc(year = 2021, day = 25, part = 2)]
 pub fn solve_b() {
     println!("Completed AOC 2021")
 }
@@ -1,5 +1,7 @@
 use std::collections::HashMap;
 
+use aoc_macros::aoc;
+
 use crate::inputs::read_contents;
 
 fn parse_input(input: &str) -> Vec<Vec<u8>> {
@@ -14,6 +16,7 @@ fn parse_input(input: &str) -> Vec<Vec<u8>> {
          .collect()
 }
 
+#[aoc(year = 2021, day = 3, part = 1)]
 pub fn solve_a() {
     let numbers = parse_input(&read_contents(3));
     let (gamma, epsilon) = get_gamma_epsilon(&numbers);
@@ -48,6 +51,7 @@ fn get_gamma_epsilon(numbers: &Vec<Vec<u8>>) -> (usize, usize) {
     (gamma, epsilon)
 }
 
+#[aoc(year = 2021, day = 3, part = 2)]
 pub fn solve_b() {
     let numbers = parse_input(&read_contents(3));
     let (oxygen_rating, co2_rating) = get_oxygen_and_co2_ratings(&numbers);
@@ -1,6 +1,7 @@
 use std::collections::{HashMap, HashSet};
 use std::fmt::{Display, Formatter};
 
+use aoc_macros::aoc;
 use itertools::Itertools;
 
 use crate::inputs::read_contents;
@@ -93,6 +94,7 @@ fn parse_input(input: &str) -> (Vec<usize>, Vec<Board>) {
     (numbers, boards)
 }
 
+#[aoc(year = 2021, day = 4, part = 1)]
 pub fn solve_a() {
     let (numbers, mut boards) = parse_input(&read_contents(4));
 
@@ -112,6 +114,7 @@ fn get_winning_bingo(numbers: &Vec<usize>, boards: &mut Vec<Board>) -> usize {
     panic!("No winning bingo board!")
 }
 
+#[aoc(year = 2021, day = 4, part = 2)]
 pub fn solve_b() {
     let (numbers, mut boards) = parse_input(&read_contents(4));
 
@@ -1,5 +1,7 @@
 use std::collections::HashMap;
 
+use aoc_macros::aoc;
+
 use crate::inputs::read_contents;
 
 #[derive(Eq, PartialEq, Hash, Clone, Debug)]
@@ -95,6 +97,7 @@ fn count_vent_overlaps<F>(vent_lines: &Vec<VentLine>,
     overlaps
 }
 
+#[aoc(year = 2021, day = 5, part = 1)]
 pub fn solve_a() {
     let vent_lines = parse_input(&read_contents(5));
     let overlaps = count_vent_overlaps(&vent_lines, keep_non_diagonals);
@@ -110,6 +113,7 @@ fn keep_non_diagonals(v: &VentLine) -> bool {
     !matches!(v.line_type, LineType::Diagonal)
 }
 
+#[aoc(year = 2021, day = 5, part = 2)]
 pub fn solve_b() {
     let vent_lines = parse_input(&read_contents(5));
     let overlaps = count_vent_overlaps(&vent_lines, |_| true);
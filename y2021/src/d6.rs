@@ -1,5 +1,7 @@
 use std::collections::{HashMap, VecDeque};
 
+use aoc_macros::aoc;
+
 use crate::inputs::read_contents;
 
 fn parse_input(input: &str) -> Vec<usize> {
@@ -8,6 +10,7 @@ fn parse_input(input: &str) -> Vec<usize> {
          .collect()
 }
 
+#[aoc(year = 2021, day = 6, part = 1)]
 pub fn solve_a() {
     let fishes = parse_input(&read_contents(6));
     let ans = count_total_spawns(&fishes, 80);
@@ -47,6 +50,7 @@ fn count_spawns(end_day: usize, start_day: usize, cache: &mut HashMap<usize, usi
     num_spawns
 }
 
+#[aoc(year = 2021, day = 6, part = 2)]
 pub fn solve_b() {
     let fishes = parse_input(&read_contents(6));
     let ans = count_total_spawns(&fishes, 256);
@@ -1,5 +1,6 @@
 use std::cmp::min;
 
+use aoc_macros::aoc;
 use itertools::{Itertools, MinMaxResult};
 
 use crate::inputs::read_contents;
@@ -21,6 +22,7 @@ fn get_position_with_min_fuel_requirement<F>(positions: &Vec<usize>, fuel_func:
     (start..=end).fold(usize::MAX, |acc, p| min(fuel_func(positions, p), acc))
 }
 
+#[aoc(year = 2021, day = 7, part = 1)]
 pub fn solve_a() {
     let positions = parse_positions(&read_contents(7));
 
@@ -34,6 +36,7 @@ fn get_fuel_required(positions: &Vec<usize>, position: usize) -> usize {
              .sum()
 }
 
+#[aoc(year = 2021, day = 7, part = 2)]
 pub fn solve_b() {
     let positions = parse_positions(&read_contents(7));
 
@@ -1,6 +1,8 @@
 use std::collections::{HashMap, HashSet};
 use std::hash::Hash;
 
+use aoc_macros::aoc;
+
 use crate::inputs::read_contents;
 
 fn parse_line(line: &str) -> (Vec<HashSet<char>>, Vec<HashSet<char>>) {
@@ -12,6 +14,7 @@ fn parse_line(line: &str) -> (Vec<HashSet<char>>, Vec<HashSet<char>>) {
     (input, output)
 }
 
+#[aoc(year = 2021, day = 8, part = 1)]
 pub fn solve_a() {
     let total: usize = read_contents(8)
         .lines()
@@ -28,6 +31,7 @@ pub fn solve_a() {
     println!("Solution A: {}", total)
 }
 
+#[aoc(year = 2021, day = 8, part = 2)]
 pub fn solve_b() {
     let total: usize = read_contents(8)
         .lines()
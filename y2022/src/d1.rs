@@ -2,6 +2,8 @@ use std::cmp::max;
 use std::collections::BinaryHeap;
 use crate::inputs::read_contents;
 
+use aoc_macros::aoc;
+
 fn get_calories(input: &str) -> Vec<Vec<usize>> {
     input.replace("\r", "")
          .split("\n\n")
@@ -13,6 +15,7 @@ fn get_calories(input: &str) -> Vec<Vec<usize>> {
          .collect()
 }
 
+#[aoc(year = 2022, day = 1, part = 1)]
 pub fn solve_a() {
     let ans = get_calories(&read_contents(1))
         .iter()
@@ -21,6 +24,7 @@ pub fn solve_a() {
     println!("Solution A: {}", ans);
 }
 
+#[aoc(year = 2022, day = 1, part = 2)]
 pub fn solve_b() {
     let mut heap: BinaryHeap<usize> = BinaryHeap::new();
     get_calories(&read_contents(1))
@@ -1,3 +1,5 @@
+use aoc_macros::aoc;
+
 use crate::inputs::read_contents;
 
 #[derive(PartialEq, Clone)]
@@ -68,6 +70,7 @@ fn create_rounds<'a, T: From<&'a str>>(input: &'a str) -> Vec<T> {
          .collect()
 }
 
+#[aoc(year = 2022, day = 2, part = 1)]
 pub fn solve_a() {
     let ans: usize = create_rounds::<RoundA>(&read_contents(2))
         .iter()
@@ -111,6 +114,7 @@ impl Score for RoundB {
     fn opponent(&self) -> &Move { &self.opponent }
 }
 
+#[aoc(year = 2022, day = 2, part = 2)]
 pub fn solve_b() {
     let ans: usize = create_rounds::<RoundB>(&read_contents(2))
         .iter()
@@ -1,5 +1,7 @@
 use std::collections::HashSet;
 
+use aoc_macros::aoc;
+
 use crate::inputs::read_contents;
 
 struct Rucksack<'a> {
@@ -30,6 +32,7 @@ fn get_priority(c: char) -> usize {
     }) as usize
 }
 
+#[aoc(year = 2022, day = 3, part = 1)]
 pub fn solve_a() {
     let input = read_contents(3);
     let rucksacks = get_rucksacks(&input);
@@ -48,6 +51,7 @@ pub fn solve_a() {
     println!("Solution A: {}", ans);
 }
 
+#[aoc(year = 2022, day = 3, part = 2)]
 pub fn solve_b() {
     let input = read_contents(3);
     let rucksacks = get_rucksacks(&input);
@@ -1,8 +1,10 @@
+use aoc_macros::aoc;
+use aoc_utils::Interval;
 use regex::Regex;
 
 use crate::inputs::read_contents;
 
-fn get_numbers() -> Vec<[usize; 4]> {
+fn get_assignments() -> Vec<(Interval, Interval)> {
     let re = Regex::new(r"(\d+)-(\d+),(\d+)-(\d+)").unwrap();
 
     read_contents(4)
@@ -10,37 +12,31 @@ fn get_numbers() -> Vec<[usize; 4]> {
         .map(|line| {
             let caps = re.captures(line).unwrap();
 
-            let x1 = caps.get(1).unwrap().as_str().parse::<usize>().unwrap();
-            let y1 = caps.get(2).unwrap().as_str().parse::<usize>().unwrap();
-            let x2 = caps.get(3).unwrap().as_str().parse::<usize>().unwrap();
-            let y2 = caps.get(4).unwrap().as_str().parse::<usize>().unwrap();
+            let x1 = caps.get(1).unwrap().as_str().parse::<i64>().unwrap();
+            let y1 = caps.get(2).unwrap().as_str().parse::<i64>().unwrap();
+            let x2 = caps.get(3).unwrap().as_str().parse::<i64>().unwrap();
+            let y2 = caps.get(4).unwrap().as_str().parse::<i64>().unwrap();
 
-            [x1, y1, x2, y2]
+            (Interval::inclusive(x1, y1), Interval::inclusive(x2, y2))
         })
         .collect()
 }
 
+#[aoc(year = 2022, day = 4, part = 1)]
 pub fn solve_a() {
-    let ans = get_numbers()
+    let ans = get_assignments()
         .iter()
-        .filter(|[x1, y1, x2, y2]|
-            (x2 <= x1 && x1 <= y2 && x2 <= y1 && y1 <= y2)
-                || (x1 <= x2 && x2 <= y1 && x1 <= y2 && y2 <= y1)
-        )
+        .filter(|(a, b)| a.contains_interval(b) || b.contains_interval(a))
         .count();
 
     println!("Solution A: {}", ans);
 }
 
+#[aoc(year = 2022, day = 4, part = 2)]
 pub fn solve_b() {
-    let ans = get_numbers()
+    let ans = get_assignments()
         .iter()
-        .filter(|[x1, y1, x2, y2]|
-            (x2 <= x1 && x1 <= y2)
-                || (x2 <= y1 && y1 <= y2)
-                || (x1 <= x2 && x2 <= y1)
-                || (x1 <= y2 && y2 <= y1)
-        )
+        .filter(|(a, b)| a.overlaps(b))
         .count();
 
     println!("Solution B: {}", ans);
@@ -1,9 +1,8 @@
 use std::collections::HashMap;
 
+use aoc_macros::{aoc, hashmap};
 use regex::Regex;
 
-use aoc_macros::hashmap;
-
 use crate::inputs::read_contents;
 
 fn parse_input(inputs: &str) -> (HashMap<usize, Vec<char>>, Vec<CrateProcedure>) {
@@ -90,6 +89,7 @@ fn get_crate_code(layout: &HashMap<usize, Vec<char>>) -> String {
         .collect::<String>()
 }
 
+#[aoc(year = 2022, day = 5, part = 1)]
 pub fn solve_a() {
     let (mut layout, procedures) = parse_input(&read_contents(5));
     move_crates_9000(&mut layout, &procedures);
@@ -109,6 +109,7 @@ fn move_crates_9001(layout: &mut HashMap<usize, Vec<char>>, procedures: &Vec<Cra
     }
 }
 
+#[aoc(year = 2022, day = 5, part = 2)]
 pub fn solve_b() {
     let (mut layout, procedures) = parse_input(&read_contents(5));
     move_crates_9001(&mut layout, &procedures);